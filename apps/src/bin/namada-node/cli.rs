@@ -3,7 +3,8 @@
 use eyre::{Context, Result};
 use namada::types::time::{DateTimeUtc, Utc};
 use namada_apps::cli::{self, cmds};
-use namada_apps::config::ValidatorLocalConfig;
+use namada_apps::client::utils;
+use namada_apps::config::{Config, ValidatorLocalConfig};
 use namada_apps::node::ledger;
 
 pub fn main() -> Result<()> {
@@ -76,6 +77,19 @@ pub fn main() -> Result<()> {
                 std::fs::write(config_path, updated_config).unwrap();
             }
         },
+        cmds::NamadaNode::Dev(cmds::Dev(args)) => {
+            // The chain's base directory is removed when dropped on exit
+            let base_dir = tempfile::tempdir()
+                .wrap_err("Failed to create a temporary base directory")?;
+            let chain_id = utils::init_dev_network(base_dir.path(), args);
+            let config = Config::load(base_dir.path(), &chain_id, None);
+            let wasm_dir = config.ledger.chain_dir().join(&config.wasm_dir);
+            println!(
+                "Running a dev chain {chain_id} with base directory {}",
+                base_dir.path().to_string_lossy()
+            );
+            ledger::run(config.ledger, wasm_dir);
+        }
     }
     Ok(())
 }
@@ -123,17 +123,21 @@ pub mod cmds {
     pub enum NamadaNode {
         Ledger(Ledger),
         Config(Config),
+        Dev(Dev),
     }
 
     impl Cmd for NamadaNode {
         fn add_sub(app: App) -> App {
-            app.subcommand(Ledger::def()).subcommand(Config::def())
+            app.subcommand(Ledger::def())
+                .subcommand(Config::def())
+                .subcommand(Dev::def())
         }
 
         fn parse(matches: &ArgMatches) -> Option<Self> {
             let ledger = SubCmd::parse(matches).map(Self::Ledger);
             let config = SubCmd::parse(matches).map(Self::Config);
-            ledger.or(config)
+            let dev = SubCmd::parse(matches).map(Self::Dev);
+            ledger.or(config).or(dev)
         }
     }
     impl SubCmd for NamadaNode {
@@ -244,6 +248,7 @@ pub mod cmds {
                 // Queries
                 .subcommand(QueryEpoch::def().display_order(5))
                 .subcommand(QueryAccount::def().display_order(5))
+                .subcommand(QueryStorageUsage::def().display_order(5))
                 .subcommand(QueryTransfers::def().display_order(5))
                 .subcommand(QueryConversions::def().display_order(5))
                 .subcommand(QueryMaspRewardTokens::def().display_order(5))
@@ -311,6 +316,8 @@ pub mod cmds {
             let claim_rewards = Self::parse_with_ctx(matches, ClaimRewards);
             let query_epoch = Self::parse_with_ctx(matches, QueryEpoch);
             let query_account = Self::parse_with_ctx(matches, QueryAccount);
+            let query_storage_usage =
+                Self::parse_with_ctx(matches, QueryStorageUsage);
             let query_transfers = Self::parse_with_ctx(matches, QueryTransfers);
             let query_conversions =
                 Self::parse_with_ctx(matches, QueryConversions);
@@ -392,6 +399,7 @@ pub mod cmds {
                 .or(query_commission)
                 .or(query_metadata)
                 .or(query_account)
+                .or(query_storage_usage)
                 .or(sign_tx)
                 .or(gen_ibc_shielded)
                 .or(utils)
@@ -458,6 +466,7 @@ pub mod cmds {
         TxResignSteward(TxResignSteward),
         QueryEpoch(QueryEpoch),
         QueryAccount(QueryAccount),
+        QueryStorageUsage(QueryStorageUsage),
         QueryTransfers(QueryTransfers),
         QueryConversions(QueryConversions),
         QueryMaspRewardTokens(QueryMaspRewardTokens),
@@ -992,6 +1001,30 @@ pub mod cmds {
         }
     }
 
+    #[derive(Clone, Debug)]
+    pub struct Dev(pub args::Dev);
+
+    impl SubCmd for Dev {
+        const CMD: &'static str = "dev";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| Self(args::Dev::parse(matches)))
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Run an ephemeral single validator chain for development. \
+                     The chain is initialized from the localnet genesis \
+                     templates in a temporary directory that is removed on \
+                     exit.",
+                )
+                .add_args::<args::Dev>()
+        }
+    }
+
     #[derive(Clone, Debug)]
     pub struct QueryResult(pub args::QueryResult<args::CliTypes>);
 
@@ -1458,6 +1491,28 @@ pub mod cmds {
         }
     }
 
+    #[derive(Clone, Debug)]
+    pub struct QueryStorageUsage(pub args::QueryStorageUsage<args::CliTypes>);
+
+    impl SubCmd for QueryStorageUsage {
+        const CMD: &'static str = "query-storage-usage";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                QueryStorageUsage(args::QueryStorageUsage::parse(matches))
+            })
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Query the number of bytes stored in the substorage space \
+                     of an address.",
+                )
+                .add_args::<args::QueryStorageUsage<args::CliTypes>>()
+        }
+    }
+
     #[derive(Clone, Debug)]
     pub struct QueryConversions(pub args::QueryConversions<args::CliTypes>);
 
@@ -2882,6 +2937,10 @@ pub mod args {
     );
     pub const CONVERSION_TABLE: Arg<PathBuf> = arg("conversion-table");
     pub const DAEMON_MODE: ArgFlag = flag("daemon");
+    pub const DEV_TEMPLATES_PATH: ArgDefault<PathBuf> = arg_default(
+        "templates-path",
+        DefaultFn(|| PathBuf::from("genesis/localnet")),
+    );
     pub const DAEMON_MODE_RETRY_DUR: ArgOpt<Duration> = arg_opt("retry-sleep");
     pub const DAEMON_MODE_SUCCESS_DUR: ArgOpt<Duration> =
         arg_opt("success-sleep");
@@ -3060,9 +3119,12 @@ pub mod args {
     pub const VALUE: Arg<String> = arg("value");
     pub const VIEWING_KEY: Arg<WalletViewingKey> = arg("key");
     pub const VP: ArgOpt<String> = arg_opt("vp");
+    pub const WAIT_TIMEOUT: ArgOpt<Duration> = arg_opt("wait-timeout");
     pub const WALLET_ALIAS_FORCE: ArgFlag = flag("wallet-alias-force");
     pub const WASM_CHECKSUMS_PATH: Arg<PathBuf> = arg("wasm-checksums-path");
     pub const WASM_DIR: ArgOpt<PathBuf> = arg_opt("wasm-dir");
+    pub const WASM_SOURCE_DIR: ArgDefault<PathBuf> =
+        arg_default("wasm-source-dir", DefaultFn(|| PathBuf::from("wasm")));
     pub const WEBSITE_OPT: ArgOpt<String> = arg_opt("website");
     pub const TX_PATH: Arg<PathBuf> = arg("tx-path");
     pub const TX_PATH_OPT: ArgOpt<PathBuf> = TX_PATH.opt();
@@ -3142,6 +3204,50 @@ pub mod args {
         }
     }
 
+    #[derive(Clone, Debug)]
+    pub struct Dev {
+        pub templates_path: PathBuf,
+        pub pre_genesis_path: Option<PathBuf>,
+        pub wasm_source_dir: PathBuf,
+        pub consensus_timeout_commit: Timeout,
+    }
+
+    impl Args for Dev {
+        fn parse(matches: &ArgMatches) -> Self {
+            let templates_path = DEV_TEMPLATES_PATH.parse(matches);
+            let pre_genesis_path = PRE_GENESIS_PATH.parse(matches);
+            let wasm_source_dir = WASM_SOURCE_DIR.parse(matches);
+            let consensus_timeout_commit =
+                CONSENSUS_TIMEOUT_COMMIT.parse(matches);
+            Self {
+                templates_path,
+                pre_genesis_path,
+                wasm_source_dir,
+                consensus_timeout_commit,
+            }
+        }
+
+        fn def(app: App) -> App {
+            app.arg(DEV_TEMPLATES_PATH.def().help(
+                "Path to the directory with genesis templates to be used to \
+                 initialize the chain. Defaults to \"genesis/localnet\".",
+            ))
+            .arg(PRE_GENESIS_PATH.def().help(
+                "The path to the pre-genesis directory of the validator that \
+                 runs the chain. Defaults to \"src/pre-genesis/validator-0\" \
+                 inside the templates directory.",
+            ))
+            .arg(WASM_SOURCE_DIR.def().help(
+                "Path to the directory with the built WASM files and their \
+                 checksums. Defaults to \"wasm\".",
+            ))
+            .arg(CONSENSUS_TIMEOUT_COMMIT.def().help(
+                "The Tendermint consensus timeout_commit configuration as \
+                 e.g. `1s` or `1000ms`. Defaults to 1 second.",
+            ))
+        }
+    }
+
     #[derive(Clone, Debug)]
     pub struct LedgerRunUntil {
         pub time: Option<DateTimeUtc>,
@@ -5043,6 +5149,32 @@ pub mod args {
         }
     }
 
+    impl CliToSdk<QueryStorageUsage<SdkTypes>> for QueryStorageUsage<CliTypes> {
+        fn to_sdk(self, ctx: &mut Context) -> QueryStorageUsage<SdkTypes> {
+            QueryStorageUsage::<SdkTypes> {
+                query: self.query.to_sdk(ctx),
+                owner: ctx.borrow_chain_or_exit().get(&self.owner),
+            }
+        }
+    }
+
+    impl Args for QueryStorageUsage<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let query = Query::parse(matches);
+            let owner = OWNER.parse(matches);
+            Self { query, owner }
+        }
+
+        fn def(app: App) -> App {
+            app.add_args::<Query<CliTypes>>().arg(
+                OWNER
+                    .def()
+                    .help("The substorage space address to query.")
+                    .required(true),
+            )
+        }
+    }
+
     impl CliToSdk<QueryBalance<SdkTypes>> for QueryBalance<CliTypes> {
         fn to_sdk(self, ctx: &mut Context) -> QueryBalance<SdkTypes> {
             let query = self.query.to_sdk(ctx);
@@ -5493,6 +5625,11 @@ pub mod args {
                 tx: self.tx.to_sdk(ctx),
                 tx_data: std::fs::read(self.tx_data).expect(""),
                 owner: ctx.borrow_chain_or_exit().get(&self.owner),
+                account_public_keys: self
+                    .account_public_keys
+                    .iter()
+                    .map(|key| ctx.borrow_chain_or_exit().get(key))
+                    .collect(),
             }
         }
     }
@@ -5502,10 +5639,12 @@ pub mod args {
             let tx = Tx::parse(matches);
             let tx_path = TX_PATH.parse(matches);
             let owner = OWNER.parse(matches);
+            let account_public_keys = PUBLIC_KEYS.parse(matches);
             Self {
                 tx,
                 tx_data: tx_path,
                 owner,
+                account_public_keys,
             }
         }
 
@@ -5517,6 +5656,11 @@ pub mod args {
                     ),
                 )
                 .arg(OWNER.def().help("The address of the account owner"))
+                .arg(PUBLIC_KEYS.def().help(
+                    "The public keys of the owner's account, in the order of \
+                     the account's keys. When given, the transaction is \
+                     signed offline, without a connection to a node.",
+                ))
         }
     }
 
@@ -5805,6 +5949,7 @@ pub mod args {
                 output_folder: self.output_folder,
                 force: self.force,
                 broadcast_only: self.broadcast_only,
+                wait_timeout: self.wait_timeout,
                 ledger_address: (),
                 initialized_account_alias: self.initialized_account_alias,
                 wallet_alias_force: self.wallet_alias_force,
@@ -5862,6 +6007,10 @@ pub mod args {
                 "Do not wait for the transaction to be applied. This will \
                  return once the transaction is added to the mempool.",
             ))
+            .arg(WAIT_TIMEOUT.def().conflicts_with(BROADCAST_ONLY.name).help(
+                "The maximum time to wait for the transaction to be accepted \
+                 and applied, e.g. \"2min\". Defaults to 60 seconds.",
+            ))
             .arg(
                 LEDGER_ADDRESS_DEFAULT
                     .def()
@@ -5954,6 +6103,7 @@ pub mod args {
             let dump_tx = DUMP_TX.parse(matches);
             let force = FORCE.parse(matches);
             let broadcast_only = BROADCAST_ONLY.parse(matches);
+            let wait_timeout = WAIT_TIMEOUT.parse(matches).map(|dur| dur.0);
             let ledger_address = LEDGER_ADDRESS_DEFAULT.parse(matches);
             let initialized_account_alias = ALIAS_OPT.parse(matches);
             let fee_amount =
@@ -5979,6 +6129,7 @@ pub mod args {
                 dump_tx,
                 force,
                 broadcast_only,
+                wait_timeout,
                 ledger_address,
                 initialized_account_alias,
                 wallet_alias_force,
@@ -615,13 +615,27 @@ impl CliApi {
                         let namada = ctx.to_sdk(client, io);
                         rpc::query_account(&namada, args).await;
                     }
+                    Sub::QueryStorageUsage(QueryStorageUsage(mut args)) => {
+                        let client = client.unwrap_or_else(|| {
+                            C::from_tendermint_address(
+                                &mut args.query.ledger_address,
+                            )
+                        });
+                        client.wait_until_node_is_synced(&io).await?;
+                        let args = args.to_sdk(&mut ctx);
+                        let namada = ctx.to_sdk(client, io);
+                        rpc::query_storage_usage(&namada, args).await;
+                    }
                     Sub::SignTx(SignTx(mut args)) => {
                         let client = client.unwrap_or_else(|| {
                             C::from_tendermint_address(
                                 &mut args.tx.ledger_address,
                             )
                         });
-                        client.wait_until_node_is_synced(&io).await?;
+                        // A tx can be signed offline with the account's keys
+                        if args.account_public_keys.is_empty() {
+                            client.wait_until_node_is_synced(&io).await?;
+                        }
                         let args = args.to_sdk(&mut ctx);
                         let namada = ctx.to_sdk(client, io);
                         tx::sign_tx(&namada, args).await?;
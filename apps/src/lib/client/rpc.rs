@@ -1261,6 +1261,22 @@ pub async fn query_account(context: &impl Namada, args: args::QueryAccount) {
     }
 }
 
+/// Query the number of bytes stored in the substorage space of an address
+pub async fn query_storage_usage(
+    context: &impl Namada,
+    args: args::QueryStorageUsage,
+) {
+    let usage = rpc::get_storage_usage(context.client(), &args.owner)
+        .await
+        .unwrap();
+    display_line!(
+        context.io(),
+        "Storage used by {}: {} bytes",
+        args.owner,
+        usage
+    );
+}
+
 pub async fn query_pgf(context: &impl Namada, _args: args::QueryPgf) {
     let stewards = query_pgf_stewards(context.client()).await;
     let fundings = query_pgf_fundings(context.client()).await;
@@ -16,6 +16,7 @@ use namada::core::ledger::governance::cli::onchain::{
 use namada::core::ledger::storage::EPOCH_SWITCH_BLOCKS_DELAY;
 use namada::ibc::apps::transfer::types::Memo;
 use namada::proto::{CompressedSignature, Section, Signer, Tx};
+use namada::types::account::AccountPublicKeysMap;
 use namada::types::address::{Address, ImplicitAddress};
 use namada::types::dec::Dec;
 use namada::types::io::Io;
@@ -1201,6 +1202,7 @@ pub async fn sign_tx<N: Namada>(
         tx: tx_args,
         tx_data,
         owner,
+        account_public_keys,
     }: args::SignTx,
 ) -> Result<(), error::Error>
 where
@@ -1212,14 +1214,30 @@ where
         edisplay_line!(namada.io(), "Couldn't decode the transaction.");
         safe_exit(1)
     };
-    let default_signer = Some(owner.clone());
-    let signing_data =
-        aux_signing_data(namada, &tx_args, Some(owner.clone()), default_signer)
+    // The account's public keys are only queried from the node when they are
+    // not given, so that the tx can be signed offline
+    let (public_keys, account_public_keys_map) =
+        if account_public_keys.is_empty() {
+            let default_signer = Some(owner.clone());
+            let signing_data = aux_signing_data(
+                namada,
+                &tx_args,
+                Some(owner.clone()),
+                default_signer,
+            )
             .await?;
+            (
+                signing_data.public_keys,
+                signing_data.account_public_keys_map,
+            )
+        } else {
+            let account_public_keys_map =
+                AccountPublicKeysMap::from_iter(account_public_keys.clone());
+            (account_public_keys, Some(account_public_keys_map))
+        };
 
     let mut wallet = namada.wallet_mut().await;
-    let secret_keys = &signing_data
-        .public_keys
+    let secret_keys = &public_keys
         .iter()
         .filter_map(|public_key| {
             if let Ok(secret_key) =
@@ -1238,8 +1256,7 @@ where
         })
         .collect::<Vec<common::SecretKey>>();
 
-    if let Some(account_public_keys_map) = signing_data.account_public_keys_map
-    {
+    if let Some(account_public_keys_map) = account_public_keys_map {
         let signatures = tx.compute_section_signature(
             secret_keys,
             &account_public_keys_map,
@@ -1563,12 +1580,14 @@ pub async fn broadcast_tx(
 /// 2. The tx with encrypted payload has been included on the blockchain
 /// 3. The decrypted payload of the tx has been included on the blockchain.
 ///
-/// In the case of errors in any of those stages, an error message is returned
+/// In the case of errors in any of those stages, an error message is returned.
+/// The tx is awaited for up to `wait_timeout`, or a default of 60 seconds.
 pub async fn submit_tx(
     namada: &impl Namada,
     to_broadcast: TxBroadcastData,
+    wait_timeout: Option<std::time::Duration>,
 ) -> Result<TxResponse, error::Error> {
-    tx::submit_tx(namada, to_broadcast).await
+    tx::submit_tx(namada, to_broadcast, wait_timeout).await
 }
 
 pub async fn gen_ibc_shielded_transfer(
@@ -14,6 +14,7 @@ use namada::core::types::string_encoding::StringEncoded;
 use namada::types::chain::ChainId;
 use namada::types::dec::Dec;
 use namada::types::key::*;
+use namada::types::time::DateTimeUtc;
 use namada::types::token;
 use namada::types::uint::Uint;
 use namada::vm::validate_untrusted_wasm;
@@ -52,6 +53,12 @@ const DEFAULT_NETWORK_CONFIGS_SERVER: &str =
 /// We do pre-genesis validator set up in this directory
 pub const PRE_GENESIS_DIR: &str = "pre-genesis";
 
+/// The chain ID prefix of the chains started with `namadan dev`
+const DEV_CHAIN_ID_PREFIX: &str = "dev";
+/// The alias of the validator that runs the chain started with `namadan dev`,
+/// if no pre-genesis path is given
+const DEV_VALIDATOR_ALIAS: &str = "validator-0";
+
 /// Configure Namada to join an existing network. The chain must be released in
 /// the <https://github.com/heliaxdev/anoma-network-config> repository.
 pub async fn join_network(
@@ -235,6 +242,10 @@ pub async fn join_network(
             );
             safe_exit(1)
         });
+    if let Err(err) = genesis.validate_chain_id() {
+        eprintln!("Refusing to join the network with invalid genesis: {err}");
+        safe_exit(1)
+    }
 
     // Try to find validator data when using a pre-genesis validator
     let validator_alias = validator_alias_and_pre_genesis_wallet
@@ -340,6 +351,117 @@ pub async fn join_network(
     println!("Successfully configured for chain ID {}", chain_id);
 }
 
+/// Initialize an ephemeral single validator chain for `namadan dev` in the
+/// given base directory and return its chain ID. The validator's
+/// pre-genesis wallet and the wallet with keys of the pre-funded accounts are
+/// loaded from the genesis templates' sources.
+pub fn init_dev_network(
+    base_dir: &Path,
+    args::Dev {
+        templates_path,
+        pre_genesis_path,
+        wasm_source_dir,
+        consensus_timeout_commit,
+    }: args::Dev,
+) -> ChainId {
+    // Load and validate the templates
+    let templates = genesis::templates::load_and_validate(&templates_path)
+        .unwrap_or_else(|| {
+            eprintln!("Invalid templates, aborting.");
+            safe_exit(1)
+        });
+
+    // Load the validator's pre-genesis wallet
+    let pre_genesis_path = pre_genesis_path.unwrap_or_else(|| {
+        templates_path
+            .join("src")
+            .join(PRE_GENESIS_DIR)
+            .join(DEV_VALIDATOR_ALIAS)
+    });
+    let validator_alias = pre_genesis_path
+        .file_name()
+        .map(|alias| alias::Alias::from(alias.to_string_lossy().to_string()))
+        .unwrap_or_else(|| {
+            eprintln!(
+                "Invalid validator pre-genesis path {}.",
+                pre_genesis_path.to_string_lossy()
+            );
+            safe_exit(1)
+        });
+    let validator_wallet =
+        pre_genesis::load(&pre_genesis_path).unwrap_or_else(|err| {
+            eprintln!("Error loading validator pre-genesis wallet {err}");
+            safe_exit(1)
+        });
+    let tendermint_node_key = validator_wallet.tendermint_node_key.clone();
+    let consensus_key = validator_wallet.consensus_key.clone();
+
+    // Finalize the genesis config to derive the chain ID
+    let genesis = genesis::chain::finalize(
+        templates,
+        FromStr::from_str(DEV_CHAIN_ID_PREFIX).unwrap(),
+        DateTimeUtc::now(),
+        consensus_timeout_commit,
+    );
+    let chain_id = genesis.metadata.chain_id.clone();
+    let chain_dir = base_dir.join(chain_id.as_str());
+    fs::create_dir_all(&chain_dir).unwrap();
+    genesis.write_toml_files(&chain_dir).unwrap_or_else(|err| {
+        eprintln!(
+            "Failed to write finalized genesis TOML files to {} with {err}.",
+            chain_dir.to_string_lossy()
+        );
+        safe_exit(1)
+    });
+    GlobalConfig::new(chain_id.clone()).write(base_dir).unwrap();
+
+    // Copy the built WASMs and their checksums
+    let wasm_dir = chain_dir.join(config::DEFAULT_WASM_DIR);
+    fs::create_dir_all(&wasm_dir).unwrap();
+    let wasm_files = fs::read_dir(&wasm_source_dir).unwrap_or_else(|err| {
+        eprintln!(
+            "Failed to read the WASM directory {} with {err}.",
+            wasm_source_dir.to_string_lossy()
+        );
+        safe_exit(1)
+    });
+    for entry in wasm_files {
+        let path = entry.unwrap().path();
+        let is_wasm = path.extension().map_or(false, |ext| ext == "wasm");
+        let is_checksums = path
+            .file_name()
+            .map_or(false, |name| name == config::DEFAULT_WASM_CHECKSUMS_FILE);
+        if is_wasm || is_checksums {
+            fs::copy(&path, wasm_dir.join(path.file_name().unwrap())).unwrap();
+        }
+    }
+
+    // Derive the config and the wallet from genesis
+    let config = genesis.derive_config(
+        &chain_dir,
+        TendermintMode::Validator,
+        Some(&tendermint_node_key.ref_to()),
+        false,
+    );
+    let pre_genesis_wallet =
+        pre_genesis_path.parent().and_then(crate::wallet::load);
+    let wallet = genesis.derive_wallet(
+        &chain_dir,
+        pre_genesis_wallet,
+        Some((validator_alias, validator_wallet)),
+    );
+    config.write(base_dir, &chain_id, true).unwrap();
+    crate::wallet::save(&wallet).unwrap();
+
+    // Setup the validator keys in CometBFT
+    let tm_home_dir = chain_dir.join(config::COMETBFT_DIR);
+    tendermint_node::write_validator_key(&tm_home_dir, &consensus_key).unwrap();
+    write_tendermint_node_key(&tm_home_dir, tendermint_node_key);
+    tendermint_node::write_validator_state(&tm_home_dir).unwrap();
+
+    chain_id
+}
+
 pub async fn fetch_wasms(
     global_args: args::Global,
     args::FetchWasms { chain_id }: args::FetchWasms,
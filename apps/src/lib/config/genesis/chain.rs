@@ -8,7 +8,7 @@ use namada::ledger::parameters::EpochDuration;
 use namada::types::address::{
     Address, EstablishedAddress, EstablishedAddressGen,
 };
-use namada::types::chain::{ChainId, ChainIdPrefix};
+use namada::types::chain::{ChainId, ChainIdPrefix, CHAIN_ID_PREFIX_SEP};
 use namada::types::dec::Dec;
 use namada::types::hash::Hash;
 use namada::types::key::{common, RefTo};
@@ -108,6 +108,53 @@ impl Finalized {
         })
     }
 
+    /// Check that the chain ID was derived from the hash of this genesis, so
+    /// that every participant provably starts from the same state.
+    pub fn validate_chain_id(&self) -> eyre::Result<()> {
+        let chain_id_prefix = self
+            .metadata
+            .chain_id
+            .as_str()
+            .rsplit_once(CHAIN_ID_PREFIX_SEP)
+            .and_then(|(prefix, _hash)| ChainIdPrefix::from_str(prefix).ok())
+            .ok_or_else(|| {
+                eyre::eyre!(
+                    "Invalid chain ID prefix in {}",
+                    self.metadata.chain_id
+                )
+            })?;
+        let to_finalize = ToFinalize {
+            vps: self.vps.clone(),
+            tokens: self.tokens.clone(),
+            balances: self.balances.clone(),
+            parameters: self.parameters.clone(),
+            transactions: self.transactions.clone(),
+            metadata: Metadata {
+                chain_id: chain_id_prefix,
+                genesis_time: self.metadata.genesis_time.clone(),
+                consensus_timeout_commit: self
+                    .metadata
+                    .consensus_timeout_commit,
+                address_gen: self.metadata.address_gen.clone(),
+            },
+        };
+        let errors = self
+            .metadata
+            .chain_id
+            .validate(to_finalize.serialize_to_vec());
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            let errors: Vec<String> =
+                errors.into_iter().map(|err| err.to_string()).collect();
+            Err(eyre::eyre!(
+                "The genesis doesn't match the chain ID {}: {}",
+                self.metadata.chain_id,
+                errors.join(", ")
+            ))
+        }
+    }
+
     /// Find the address of the configured native token
     pub fn get_native_token(&self) -> &Address {
         let alias = &self.parameters.parameters.native_token;
@@ -688,6 +735,7 @@ pub struct FinalizedParameters {
     pub gov_params: templates::GovernanceParams,
     pub pgf_params: namada::core::ledger::pgf::parameters::PgfParameters,
     pub eth_bridge_params: Option<templates::EthBridgeParams>,
+    pub faucet_params: Option<templates::FaucetParams>,
 }
 
 impl FinalizedParameters {
@@ -698,6 +746,7 @@ impl FinalizedParameters {
             gov_params,
             pgf_params,
             eth_bridge_params,
+            faucet_params,
         }: templates::Parameters<Validated>,
     ) -> Self {
         use namada::core::ledger::pgf::parameters::PgfParameters;
@@ -712,6 +761,7 @@ impl FinalizedParameters {
             gov_params,
             pgf_params: finalized_pgf_params,
             eth_bridge_params,
+            faucet_params,
         }
     }
 }
@@ -832,4 +882,43 @@ mod test {
 
         pretty_assertions::assert_eq!(finalized_0, finalized_1);
     }
+
+    /// Test that the chain ID of a finalized genesis is valid after writing
+    /// and reading back the genesis files and that any modification of the
+    /// genesis is detected.
+    #[test]
+    fn test_validate_chain_id() {
+        // Load the localnet templates
+        let templates_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .parent()
+            .unwrap()
+            .join("genesis/localnet");
+        let templates = templates::load_and_validate(&templates_dir).unwrap();
+
+        let chain_id_prefix: ChainIdPrefix =
+            FromStr::from_str("test-prefix").unwrap();
+
+        let genesis_time =
+            DateTimeUtc::from_str("2021-12-31T00:00:00Z").unwrap();
+
+        let consensus_timeout_commit =
+            crate::facade::tendermint::Timeout::from_str("1s").unwrap();
+
+        let finalized = finalize(
+            templates,
+            chain_id_prefix,
+            genesis_time,
+            consensus_timeout_commit,
+        );
+        finalized.validate_chain_id().unwrap();
+
+        let genesis_dir = tempfile::tempdir().unwrap();
+        finalized.write_toml_files(genesis_dir.path()).unwrap();
+        let mut finalized =
+            Finalized::read_toml_files(genesis_dir.path()).unwrap();
+        finalized.validate_chain_id().unwrap();
+
+        finalized.parameters.parameters.epochs_per_year += 1;
+        assert!(finalized.validate_chain_id().is_err());
+    }
 }
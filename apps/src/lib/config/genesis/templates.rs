@@ -228,6 +228,7 @@ pub struct Parameters<T: TemplateValidation> {
     pub gov_params: GovernanceParams,
     pub pgf_params: PgfParams<T>,
     pub eth_bridge_params: Option<EthBridgeParams>,
+    pub faucet_params: Option<FaucetParams>,
 }
 
 #[derive(
@@ -483,6 +484,23 @@ pub struct EthBridgeParams {
     pub contracts: Contracts,
 }
 
+/// The testnet faucet is only enabled when these parameters are set
+#[derive(
+    Clone,
+    Debug,
+    Deserialize,
+    Serialize,
+    BorshDeserialize,
+    BorshSerialize,
+    PartialEq,
+    Eq,
+)]
+pub struct FaucetParams {
+    /// Maximum number of whole tokens of any kind that can be withdrawn from
+    /// the faucet in a single tx
+    pub withdrawal_limit: u64,
+}
+
 impl TokenBalances {
     pub fn get(&self, addr: &GenesisAddress) -> Option<token::Amount> {
         self.0.get(addr).map(|amt| amt.amount())
@@ -845,6 +863,7 @@ pub fn validate_parameters(
         gov_params,
         pgf_params,
         eth_bridge_params,
+        faucet_params,
     } = parameters;
     match parameters.denominate(tokens) {
         Err(e) => {
@@ -862,6 +881,7 @@ pub fn validate_parameters(
                 valid: Default::default(),
             },
             eth_bridge_params,
+            faucet_params,
         }),
     }
 }
@@ -71,6 +71,7 @@ fn get_tx_args(use_device: bool) -> TxArgs {
         output_folder: None,
         force: false,
         broadcast_only: false,
+        wait_timeout: None,
         ledger_address: (),
         initialized_account_alias: None,
         wallet_alias_force: false,
@@ -992,6 +993,7 @@ pub fn validate(
         (Vec<common::PublicKey>, u8),
     > = BTreeMap::default();
     let mut validator_accounts = BTreeSet::new();
+    let mut validator_keys = BTreeSet::new();
 
     let Transactions {
         ref established_account,
@@ -1020,6 +1022,7 @@ pub fn validate(
                 &all_used_addresses,
                 &established_accounts,
                 &mut validator_accounts,
+                &mut validator_keys,
             ) {
                 is_valid = false;
             }
@@ -1318,6 +1321,7 @@ pub fn validate_validator_account(
     all_used_addresses: &BTreeSet<Address>,
     established_accounts: &BTreeMap<Address, (Vec<common::PublicKey>, u8)>,
     validator_accounts: &mut BTreeSet<Address>,
+    validator_keys: &mut BTreeSet<common::PublicKey>,
 ) -> bool {
     let tx = &signed_tx.data;
 
@@ -1414,6 +1418,28 @@ pub fn validate_validator_account(
         is_valid = false;
     }
 
+    // Check that the keys are not used by any other validator. A validator
+    // may use the same key for more than one of its own roles.
+    let mut own_keys = BTreeSet::new();
+    for (key_name, key) in [
+        ("consensus_key", &tx.consensus_key),
+        ("protocol_key", &tx.protocol_key),
+        ("tendermint_node_key", &tx.tendermint_node_key),
+        ("eth_hot_key", &tx.eth_hot_key),
+        ("eth_cold_key", &tx.eth_cold_key),
+    ] {
+        own_keys.insert(key.pk.raw.clone());
+        if validator_keys.contains(&key.pk.raw) {
+            eprintln!(
+                "The `{key_name}` \"{}\" of the `validator_account` tx with \
+                 address \"{}\" is used by another validator.",
+                key.pk.raw, established_address
+            );
+            is_valid = false;
+        }
+    }
+    validator_keys.extend(own_keys);
+
     // Check keys authorizations
     let unsigned = UnsignedValidatorAccountTx::from(tx);
     if !validate_signature(
@@ -8,12 +8,15 @@ pub mod utils;
 use std::collections::HashMap;
 use std::fs::{create_dir_all, File};
 use std::io::Write;
+use std::net::SocketAddr;
 use std::path::{Path, PathBuf};
 
 use directories::ProjectDirs;
+use namada::types::address::Address;
 use namada::types::chain::ChainId;
 use namada::types::storage::BlockHeight;
 use namada::types::time::Rfc3339String;
+use namada::vm::wasm::WasmCompiler;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
@@ -92,6 +95,11 @@ pub struct Ledger {
     pub chain_id: ChainId,
     pub shell: Shell,
     pub cometbft: TendermintConfig,
+    /// The mempool version set in the `[ledger.cometbft.mempool]` section of
+    /// the config, if any. It's not a part of the typed CometBFT config, so
+    /// it's read separately.
+    #[serde(skip)]
+    pub cometbft_mempool_version: Option<String>,
     pub ethereum_bridge: ethereum_bridge::ledger::Config,
 }
 
@@ -111,6 +119,12 @@ pub struct Shell {
     /// When set, will limit the how many block heights in the past can the
     /// storage be queried for reading values.
     pub storage_read_past_height_limit: Option<u64>,
+    /// When set, the subspace diffs and the results of the blocks older than
+    /// this many heights are pruned from the DB and the values at these
+    /// heights can no longer be read. When not set, the whole history is
+    /// retained (archive mode).
+    #[serde(default)]
+    pub block_data_retain_height: Option<u64>,
     /// Use the [`Ledger::db_dir()`] method to read the value.
     db_dir: PathBuf,
     /// Use the [`Ledger::cometbft_dir()`] method to read the value.
@@ -119,6 +133,58 @@ pub struct Shell {
     pub action_at_height: Option<ActionAtHeight>,
     /// Specify if tendermint is started as validator, fullnode or seednode
     pub tendermint_mode: TendermintMode,
+    /// When set, the node serves an admin endpoint for operational actions
+    /// (log filter reload, graceful shutdown) on this address. Only loopback
+    /// addresses are accepted.
+    pub admin_rpc_address: Option<SocketAddr>,
+    /// When set, responses of the queries matching the configured paths are
+    /// cached in memory.
+    pub query_cache: Option<QueryCache>,
+    /// When set, wrapper txs that don't pass the filter are rejected from
+    /// this node's mempool.
+    pub tx_filter: Option<TxFilter>,
+    /// The compiler of the tx and VP WASM code, either `singlepass` (the
+    /// default, fast compilation) or `cranelift` (faster execution, e.g. for
+    /// archive and query nodes)
+    #[serde(default)]
+    pub wasm_compiler: WasmCompiler,
+    /// When set, a query or CheckTx ABCI request that the shell doesn't
+    /// respond to within this many seconds fails with an error, instead of
+    /// waiting for the shell indefinitely. Consensus requests are always
+    /// waited for.
+    pub abci_response_timeout_secs: Option<u64>,
+}
+
+/// Configuration of the in-memory cache of query responses. Only queries for
+/// a specific, already committed block height are cached, keyed by their
+/// path, data and height.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct QueryCache {
+    /// Prefixes of the query paths to cache (e.g. `/shell/value`). Only
+    /// queries whose response is fully determined by the block height should
+    /// be included.
+    pub paths: Vec<String>,
+    /// Maximum number of cached responses
+    pub capacity: usize,
+}
+
+/// Configuration of the operator's filter of the wrapper txs accepted into the
+/// node's mempool. The filter only applies to the node's own mempool, it
+/// doesn't affect the validation of block proposals.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct TxFilter {
+    /// When set, txs larger than this number of bytes are rejected
+    pub max_tx_bytes: Option<usize>,
+    /// Hex-encoded hashes of the tx code that is rejected
+    #[serde(default)]
+    pub deny_code_hashes: Vec<String>,
+    /// Hex-encoded hashes of the tx code that is accepted. Any code that is
+    /// not denied is accepted when empty.
+    #[serde(default)]
+    pub allow_code_hashes: Vec<String>,
+    /// The fee payers whose txs are rejected
+    #[serde(default)]
+    pub deny_sources: Vec<Address>,
 }
 
 impl Ledger {
@@ -143,12 +209,19 @@ impl Ledger {
                 tx_wasm_compilation_cache_bytes: None,
                 // Default corresponds to 1 hour of past blocks at 1 block/sec
                 storage_read_past_height_limit: Some(3600),
+                block_data_retain_height: None,
                 db_dir: DB_DIR.into(),
                 cometbft_dir: COMETBFT_DIR.into(),
                 action_at_height: None,
                 tendermint_mode: mode,
+                admin_rpc_address: None,
+                query_cache: None,
+                tx_filter: None,
+                wasm_compiler: WasmCompiler::default(),
+                abci_response_timeout_secs: None,
             },
             cometbft: tendermint_config,
+            cometbft_mempool_version: None,
             ethereum_bridge: ethereum_bridge::ledger::Config::default(),
         }
     }
@@ -283,7 +356,12 @@ impl Config {
                 )
             })
             .map_err(Error::ReadError)?;
-        config.try_into().map_err(Error::DeserializationError)
+        let cometbft_mempool_version =
+            config.get_str("ledger.cometbft.mempool.version").ok();
+        let mut config: Self =
+            config.try_into().map_err(Error::DeserializationError)?;
+        config.ledger.cometbft_mempool_version = cometbft_mempool_version;
+        Ok(config)
     }
 
     /// Generate configuration and write it to a file.
@@ -649,7 +727,11 @@ dial_timeout = "3s"
 # Mempool version to use:
 #   1) "v0" - (default) FIFO mempool.
 #   2) "v1" - prioritized mempool (deprecated; will be removed in the next release).
-version = "v0"
+# NOTE: Namada defaults to the prioritized mempool to order wrapper txs by
+# their fees. It's only available up to CometBFT v0.37, which Namada requires.
+# A version set in the `[ledger.cometbft.mempool]` section of the node's config
+# takes precedence.
+version = "v1"
 
 recheck = true
 broadcast = true
@@ -845,11 +927,43 @@ namespace = "cometbft"
 
 #[cfg(test)]
 mod tests {
-    use super::DEFAULT_COMETBFT_CONFIG;
+    use namada::types::chain::ChainId;
+
+    use super::{Config, TendermintMode, DEFAULT_COMETBFT_CONFIG};
     use crate::facade::tendermint_config::TendermintConfig;
 
     #[test]
     fn test_default_cometbft_config() {
         assert!(TendermintConfig::parse_toml(DEFAULT_COMETBFT_CONFIG).is_ok());
     }
+
+    /// Test that the CometBFT mempool version is only set when the operator
+    /// sets it in the config file
+    #[test]
+    fn test_read_cometbft_mempool_version() {
+        let base_dir = tempfile::tempdir().unwrap();
+        let base_dir = base_dir.path();
+        let chain_id = ChainId::default();
+        let config =
+            Config::generate(base_dir, &chain_id, TendermintMode::Full, false)
+                .unwrap();
+        assert_eq!(config.ledger.cometbft_mempool_version, None);
+        let config = Config::read(base_dir, &chain_id, None).unwrap();
+        assert_eq!(config.ledger.cometbft_mempool_version, None);
+
+        let file_path = Config::file_path(base_dir, &chain_id);
+        let mut value: toml::Value =
+            toml::from_str(&std::fs::read_to_string(&file_path).unwrap())
+                .unwrap();
+        value["ledger"]["cometbft"]["mempool"]
+            .as_table_mut()
+            .unwrap()
+            .insert("version".to_owned(), "v0".into());
+        std::fs::write(&file_path, toml::to_string(&value).unwrap()).unwrap();
+        let config = Config::read(base_dir, &chain_id, None).unwrap();
+        assert_eq!(
+            config.ledger.cometbft_mempool_version.as_deref(),
+            Some("v0")
+        );
+    }
 }
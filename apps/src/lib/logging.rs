@@ -3,6 +3,7 @@ use std::env;
 
 use color_eyre::eyre::Result;
 use eyre::WrapErr;
+use once_cell::sync::OnceCell;
 use tracing_appender::non_blocking::WorkerGuard;
 use tracing_log::LogTracer;
 use tracing_subscriber::filter::{Directive, EnvFilter};
@@ -21,6 +22,14 @@ const ROLLING_ENV_KEY: &str = "NAMADA_LOG_ROLLING";
 
 const LOG_FILE_NAME_PREFIX: &str = "namada.log";
 
+/// A function that replaces the filter of the global log subscriber
+type ReloadFilterFn =
+    Box<dyn Fn(EnvFilter) -> std::result::Result<(), String> + Send + Sync>;
+
+/// Set once the global log subscriber has been initialized with
+/// [`set_subscriber`]
+static RELOAD_FILTER: OnceCell<ReloadFilterFn> = OnceCell::new();
+
 #[derive(Clone, Debug)]
 enum Fmt {
     Full,
@@ -84,7 +93,12 @@ pub fn set_subscriber(filter: EnvFilter) -> Result<Option<WorkerGuard>> {
     macro_rules! finish {
         ($($builder:tt)*) => {
             {
-                let my_collector = $($builder)*.finish();
+                let builder = $($builder)*.with_filter_reloading();
+                let reload_handle = builder.reload_handle();
+                let _ = RELOAD_FILTER.set(Box::new(move |filter| {
+                    reload_handle.reload(filter).map_err(|e| e.to_string())
+                }));
+                let my_collector = builder.finish();
                 tracing::subscriber::set_global_default(my_collector)
                     .wrap_err("Failed to set log subscriber")
             }
@@ -123,6 +137,17 @@ pub fn set_subscriber(filter: EnvFilter) -> Result<Option<WorkerGuard>> {
     }
 }
 
+/// Replace the filter of the global log subscriber with the given directives,
+/// using the same syntax as the `NAMADA_LOG` env var.
+pub fn reload_filter(directives: &str) -> Result<()> {
+    let filter = EnvFilter::try_new(directives)
+        .wrap_err_with(|| format!("Invalid log filter \"{directives}\""))?;
+    let reload = RELOAD_FILTER
+        .get()
+        .ok_or_else(|| eyre::eyre!("The log subscriber is not initialized"))?;
+    reload(filter).map_err(|e| eyre::eyre!("Failed to reload log filter: {e}"))
+}
+
 enum RollingFreq {
     Never,
     Minutely,
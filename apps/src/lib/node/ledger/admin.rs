@@ -0,0 +1,136 @@
+//! An HTTP endpoint for operational actions on a running node, served only on
+//! a loopback address configured with `admin_rpc_address`.
+//!
+//! Supported actions:
+//!
+//! - `POST /log_filter` replaces the log filter with the request body, using
+//!   the same syntax as the `NAMADA_LOG` env var (e.g. `debug` or
+//!   `info,namada::ledger=debug`).
+//! - `POST /shutdown` gracefully shuts down the node, as if it received an
+//!   interrupt signal.
+//!
+//! The mempool is held by CometBFT, whose RPC already serves its contents
+//! (`unconfirmed_txs`), and the ledger doesn't produce ABCI state sync
+//! snapshots, so there are no actions for these here.
+
+use std::future::Future;
+use std::net::SocketAddr;
+
+use tokio::sync::mpsc;
+use tokio::sync::oneshot::Receiver;
+use warp::http::StatusCode;
+use warp::reply::WithStatus;
+use warp::Filter;
+
+use crate::logging;
+
+/// The endpoint to which new log filter directives should be sent to
+const LOG_FILTER_ENDPOINT: &str = "log_filter";
+
+/// The endpoint that initiates a graceful shutdown of the node
+const SHUTDOWN_ENDPOINT: &str = "shutdown";
+
+/// Check that the admin endpoint is not exposed outside of the host
+pub fn is_allowed_address(addr: &SocketAddr) -> bool {
+    addr.ip().is_loopback()
+}
+
+/// Binds a [`warp::Server`] that serves the admin actions on `listen_addr`
+/// and returns the future that runs it. The server shuts down if a signal is
+/// sent on the `abort_recv` channel or when a shutdown is requested from the
+/// endpoint, in which case the node will be shut down together with it.
+pub fn bind(
+    listen_addr: SocketAddr,
+    abort_recv: Receiver<()>,
+) -> Result<(SocketAddr, impl Future<Output = ()>), warp::Error> {
+    let (shutdown_send, mut shutdown_recv) = mpsc::channel::<()>(1);
+
+    let log_filter = warp::post()
+        .and(warp::path(LOG_FILTER_ENDPOINT))
+        .and(warp::path::end())
+        .and(warp::body::bytes())
+        .map(|bytes: bytes::Bytes| reload_log_filter(&bytes));
+    let shutdown = warp::post()
+        .and(warp::path(SHUTDOWN_ENDPOINT))
+        .and(warp::path::end())
+        .map(move || {
+            tracing::info!("Received a shutdown request from admin endpoint");
+            // The channel has a single slot, so any further requests while
+            // the shutdown is in progress are a no-op
+            let _ = shutdown_send.try_send(());
+            warp::reply::with_status("OK", StatusCode::OK)
+        });
+
+    warp::serve(log_filter.or(shutdown)).try_bind_with_graceful_shutdown(
+        listen_addr,
+        async move {
+            tokio::select! {
+                _ = abort_recv => {},
+                _ = shutdown_recv.recv() => {},
+            }
+            tracing::info!(?listen_addr, "Stopping the admin endpoint");
+        },
+    )
+}
+
+/// Callback to replace the log filter with the directives in the request body
+fn reload_log_filter(bytes: &[u8]) -> WithStatus<String> {
+    let directives = match std::str::from_utf8(bytes) {
+        Ok(directives) => directives.trim(),
+        Err(_) => {
+            return warp::reply::with_status(
+                "The log filter must be valid UTF-8".to_string(),
+                StatusCode::BAD_REQUEST,
+            );
+        }
+    };
+    match logging::reload_filter(directives) {
+        Ok(()) => {
+            tracing::info!(directives, "Reloaded the log filter");
+            warp::reply::with_status("OK".to_string(), StatusCode::OK)
+        }
+        Err(error) => {
+            tracing::warn!(%error, "Couldn't reload the log filter");
+            warp::reply::with_status(error.to_string(), StatusCode::BAD_REQUEST)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_admin {
+    use std::net::TcpListener;
+
+    use super::*;
+
+    /// Test that binding the admin endpoint to an address that is already in
+    /// use fails, instead of panicking
+    #[tokio::test]
+    async fn test_bind_address_in_use() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        let (_abort_send, abort_recv) = tokio::sync::oneshot::channel();
+        assert!(bind(addr, abort_recv).is_err());
+
+        // The endpoint can be bound once the address is free again
+        drop(listener);
+        let (_abort_send, abort_recv) = tokio::sync::oneshot::channel();
+        let (bound_addr, _server) = bind(addr, abort_recv).unwrap();
+        assert_eq!(bound_addr, addr);
+    }
+
+    /// Test that the admin endpoint can only be served on loopback addresses
+    #[test]
+    fn test_only_loopback_addresses_allowed() {
+        for (addr, allowed) in [
+            ("127.0.0.1:26670", true),
+            ("[::1]:26670", true),
+            ("0.0.0.0:26670", false),
+            ("192.168.1.10:26670", false),
+            ("[::]:26670", false),
+        ] {
+            let addr: SocketAddr = addr.parse().unwrap();
+            assert_eq!(is_allowed_address(&addr), allowed, "{addr}");
+        }
+    }
+}
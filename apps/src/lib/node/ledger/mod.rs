@@ -1,4 +1,5 @@
 mod abortable;
+mod admin;
 mod broadcaster;
 pub mod ethereum_oracle;
 pub mod shell;
@@ -7,7 +8,7 @@ pub mod storage;
 pub mod tendermint_node;
 
 use std::convert::TryInto;
-use std::net::SocketAddr;
+use std::net::{SocketAddr, ToSocketAddrs};
 use std::path::PathBuf;
 use std::str::FromStr;
 use std::thread;
@@ -33,6 +34,7 @@ use crate::cli::args;
 use crate::config::utils::{convert_tm_addr_to_socket_addr, num_of_threads};
 use crate::config::{ethereum_bridge, TendermintMode};
 use crate::facade::tendermint::v0_37::abci::response;
+use crate::facade::tendermint_config::net::Address as TendermintAddress;
 use crate::facade::tower_abci::{split, Server};
 use crate::node::ledger::broadcaster::Broadcaster;
 use crate::node::ledger::ethereum_oracle as oracle;
@@ -266,6 +268,9 @@ async fn run_aux(config: config::Ledger, wasm_dir: PathBuf) {
             }
         };
 
+    // Start the admin endpoint if configured
+    let admin = maybe_start_admin_endpoint(&mut spawner, &config);
+
     tracing::info!("Loading MASP verifying keys.");
     let _ = namada_sdk::masp::preload_verifying_keys();
     tracing::info!("Done loading MASP verifying keys.");
@@ -284,10 +289,11 @@ async fn run_aux(config: config::Ledger, wasm_dir: PathBuf) {
     let aborted = spawner.wait_for_abort().await.child_terminated();
 
     // Wait for all managed tasks to finish.
-    let res = tokio::try_join!(tendermint_node, abci, eth_oracle, broadcaster);
+    let res =
+        tokio::try_join!(tendermint_node, abci, eth_oracle, broadcaster, admin);
 
     match res {
-        Ok((tendermint_res, abci_res, _, _)) => {
+        Ok((tendermint_res, abci_res, _, _, _)) => {
             // we ignore errors on user-initiated shutdown
             if aborted {
                 if let Err(err) = tendermint_res {
@@ -474,8 +480,7 @@ fn start_abci_broadcaster_shell(
 
     // Construct our ABCI application.
     let tendermint_mode = config.shell.tendermint_mode.clone();
-    let proxy_app_address =
-        convert_tm_addr_to_socket_addr(&config.cometbft.proxy_app);
+    let proxy_app_address = config.cometbft.proxy_app.clone();
 
     let (shell, abci_service, service_handle) = AbcippShim::new(
         config,
@@ -533,9 +538,10 @@ fn start_abci_broadcaster_shell(
 async fn run_abci(
     abci_service: AbciService,
     service_handle: tokio::sync::broadcast::Sender<()>,
-    proxy_app_address: SocketAddr,
+    proxy_app_address: TendermintAddress,
     abort_recv: tokio::sync::oneshot::Receiver<()>,
 ) -> shell::Result<()> {
+    let listen_address = AbciListenAddress::new(&proxy_app_address)?;
     // Split it into components.
     let (consensus, mempool, snapshot, info) = split::service(abci_service, 5);
 
@@ -554,9 +560,17 @@ async fn run_abci(
         )
         .finish()
         .unwrap();
+    let listen = async move {
+        match listen_address {
+            AbciListenAddress::Tcp(addr) => server.listen_tcp(addr).await,
+            #[cfg(unix)]
+            AbciListenAddress::Unix(path) => server.listen_unix(path).await,
+        }
+    };
+    tracing::info!("Starting the ABCI server on {proxy_app_address}");
     tokio::select! {
         // Run the server with the ABCI service
-        status = server.listen_tcp(proxy_app_address) => {
+        status = listen => {
             status.map_err(|err| Error::TowerServer(err.to_string()))
         },
         resp_sender = abort_recv => {
@@ -575,6 +589,86 @@ async fn run_abci(
     }
 }
 
+/// The address on which the ABCI server accepts connections from CometBFT
+enum AbciListenAddress {
+    /// A TCP socket address
+    Tcp(SocketAddr),
+    /// A Unix domain socket path, for a co-located CometBFT
+    #[cfg(unix)]
+    Unix(PathBuf),
+}
+
+impl AbciListenAddress {
+    /// Check that the ABCI server can listen on the configured address
+    fn new(address: &TendermintAddress) -> shell::Result<Self> {
+        match address {
+            TendermintAddress::Tcp { host, port, .. } => (host.as_str(), *port)
+                .to_socket_addrs()
+                .ok()
+                .and_then(|mut addrs| addrs.next())
+                .map(Self::Tcp)
+                .ok_or_else(|| {
+                    Error::AbciAddress(format!("Couldn't resolve {address}"))
+                }),
+            #[cfg(unix)]
+            TendermintAddress::Unix { path } => {
+                use std::os::unix::fs::FileTypeExt;
+
+                let path = PathBuf::from(path);
+                if let Some(dir) = path.parent() {
+                    if !dir.as_os_str().is_empty() && !dir.is_dir() {
+                        return Err(Error::AbciAddress(format!(
+                            "The directory of {address} doesn't exist"
+                        )));
+                    }
+                }
+                // A socket left over from a previous run would prevent the
+                // server from binding to the path. It's only removed if no
+                // process is listening on it anymore.
+                match std::fs::metadata(&path) {
+                    Ok(metadata) if metadata.file_type().is_socket() => {
+                        match std::os::unix::net::UnixStream::connect(&path) {
+                            Ok(_) => {
+                                return Err(Error::AbciAddress(format!(
+                                    "{address} is in use by another process"
+                                )));
+                            }
+                            Err(err)
+                                if err.kind()
+                                    == std::io::ErrorKind::ConnectionRefused =>
+                            {
+                                std::fs::remove_file(&path).map_err(|err| {
+                                    Error::AbciAddress(format!(
+                                        "Couldn't remove the stale socket at \
+                                         {address}: {err}"
+                                    ))
+                                })?;
+                            }
+                            Err(err) => {
+                                return Err(Error::AbciAddress(format!(
+                                    "Couldn't check the socket at {address}: \
+                                     {err}"
+                                )));
+                            }
+                        }
+                    }
+                    Ok(_) => {
+                        return Err(Error::AbciAddress(format!(
+                            "{address} exists and is not a socket"
+                        )));
+                    }
+                    Err(_) => {}
+                }
+                Ok(Self::Unix(path))
+            }
+            #[cfg(not(unix))]
+            TendermintAddress::Unix { .. } => Err(Error::AbciAddress(format!(
+                "Unix sockets aren't supported on this platform: {address}"
+            ))),
+        }
+    }
+}
+
 /// Launches a new task managing a Tendermint process into the asynchronous
 /// runtime, and returns its [`task::JoinHandle`].
 fn start_tendermint(
@@ -637,6 +731,58 @@ fn start_tendermint(
         })
 }
 
+/// Potentially starts the admin endpoint, if an address is configured for it.
+/// Shutting down the node from the endpoint terminates the task, which in turn
+/// shuts down all the other tasks.
+fn maybe_start_admin_endpoint(
+    spawner: &mut AbortableSpawner,
+    config: &config::Ledger,
+) -> task::JoinHandle<()> {
+    let Some(listen_addr) = config.shell.admin_rpc_address else {
+        return spawn_dummy_task(());
+    };
+    if !admin::is_allowed_address(&listen_addr) {
+        tracing::error!(
+            ?listen_addr,
+            "The admin endpoint can only be served on a loopback address, it \
+             will not be started"
+        );
+        return spawn_dummy_task(());
+    }
+
+    // Channel for signalling shut down to the admin endpoint
+    let (admin_abort_send, admin_abort_recv) =
+        tokio::sync::oneshot::channel::<()>();
+
+    // Bind the endpoint before spawning its task, so that a failure to bind
+    // only leaves the endpoint out instead of shutting down the node
+    let server = match admin::bind(listen_addr, admin_abort_recv) {
+        Ok((addr, server)) => {
+            tracing::info!(listen_addr = ?addr, "Admin endpoint is starting");
+            server
+        }
+        Err(error) => {
+            tracing::error!(
+                ?listen_addr,
+                %error,
+                "Failed to bind the admin endpoint, it will not be started"
+            );
+            return spawn_dummy_task(());
+        }
+    };
+
+    spawner
+        .spawn_abortable("Admin endpoint", move |aborter| async move {
+            server.await;
+            tracing::info!("Admin endpoint is no longer running.");
+
+            drop(aborter);
+        })
+        .with_cleanup(async move {
+            let _ = admin_abort_send.send(());
+        })
+}
+
 /// Represents a [`tokio::task`] in which an Ethereum oracle may be running, and
 /// if so, channels for communicating with it.
 enum EthereumOracleTask {
@@ -821,3 +967,71 @@ async fn sleep_until(time: DateTimeUtc) -> std::ops::ControlFlow<()> {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Test that a TCP address of the ABCI server is resolved
+    #[test]
+    fn test_abci_listen_address_tcp() {
+        let address: TendermintAddress =
+            "tcp://127.0.0.1:26658".parse().unwrap();
+        assert!(matches!(
+            AbciListenAddress::new(&address),
+            Ok(AbciListenAddress::Tcp(addr)) if addr.port() == 26658
+        ));
+    }
+
+    /// Test the validation of a Unix socket address of the ABCI server
+    #[cfg(unix)]
+    #[test]
+    fn test_abci_listen_address_unix() {
+        use std::os::unix::net::UnixListener;
+
+        let dir = tempfile::tempdir().unwrap();
+        let unix_address = |path: PathBuf| TendermintAddress::Unix {
+            path: path.to_string_lossy().into_owned(),
+        };
+
+        // A path that doesn't exist yet is accepted
+        let path = dir.path().join("abci.sock");
+        assert!(matches!(
+            AbciListenAddress::new(&unix_address(path.clone())),
+            Ok(AbciListenAddress::Unix(socket)) if socket == path
+        ));
+
+        // The directory of the socket must exist
+        let address =
+            unix_address(dir.path().join("missing").join("abci.sock"));
+        assert!(matches!(
+            AbciListenAddress::new(&address),
+            Err(Error::AbciAddress(_))
+        ));
+
+        // A file that is not a socket is not removed
+        let file = dir.path().join("file");
+        std::fs::write(&file, b"").unwrap();
+        assert!(matches!(
+            AbciListenAddress::new(&unix_address(file.clone())),
+            Err(Error::AbciAddress(_))
+        ));
+        assert!(file.exists());
+
+        // A socket in use by another process is not removed
+        let listener = UnixListener::bind(&path).unwrap();
+        assert!(matches!(
+            AbciListenAddress::new(&unix_address(path.clone())),
+            Err(Error::AbciAddress(_))
+        ));
+        assert!(path.exists());
+
+        // A stale socket is removed
+        drop(listener);
+        assert!(matches!(
+            AbciListenAddress::new(&unix_address(path.clone())),
+            Ok(AbciListenAddress::Unix(_))
+        ));
+        assert!(!path.exists());
+    }
+}
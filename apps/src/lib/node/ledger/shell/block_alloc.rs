@@ -269,28 +269,6 @@ impl EncryptedTxsBins {
             gas: TxBin::init(max_gas),
         }
     }
-
-    pub fn try_dump(&mut self, tx: &[u8], gas: u64) -> Result<(), String> {
-        self.space.try_dump(tx).map_err(|e| match e {
-            AllocFailure::Rejected { .. } => {
-                "No more space left in the block for wrapper txs".to_string()
-            }
-            AllocFailure::OverflowsBin { .. } => "The given wrapper tx is \
-                                                  larger than 1/3 of the \
-                                                  available block space"
-                .to_string(),
-        })?;
-        self.gas.try_dump(gas).map_err(|e| match e {
-            AllocFailure::Rejected { .. } => {
-                "No more gas left in the block for wrapper txs".to_string()
-            }
-            AllocFailure::OverflowsBin { .. } => {
-                "The given wrapper tx requires more gas than available to the \
-                 entire block"
-                    .to_string()
-            }
-        })
-    }
 }
 
 pub mod threshold {
@@ -328,8 +306,9 @@ mod tests {
     use proptest::prelude::*;
 
     use super::states::{
-        BuildingEncryptedTxBatch, NextState, TryAlloc, WithEncryptedTxs,
-        WithoutEncryptedTxs,
+        BuildingEncryptedTxBatch, EncryptedTxBatchAllocator, NextState,
+        ProposalAllocFailure, ProposalAllocator, ProposedTx, ProposedTxKind,
+        TryAlloc, WithEncryptedTxs, WithoutEncryptedTxs,
     };
     use super::*;
     use crate::node::ledger::shims::abcipp_shim_types::shim::TxBytes;
@@ -397,6 +376,62 @@ mod tests {
         );
     }
 
+    /// Check that a [`ProposalAllocator`] allocates the txs of a proposal in
+    /// the bins of the allocator's states, and only in the order of the
+    /// states.
+    #[test]
+    fn test_proposal_allocator() {
+        const BLOCK_SIZE: u64 = 60;
+        const BLOCK_GAS: u64 = 1_000;
+
+        fn encrypted_tx(tx: &[u8]) -> ProposedTx<'_> {
+            ProposedTx::Encrypted(BlockResources::new(tx, 0))
+        }
+
+        let mut alloc = ProposalAllocator::from(
+            EncryptedTxBatchAllocator::WithEncryptedTxs(BsaWrapperTxs::init(
+                BLOCK_SIZE, BLOCK_GAS,
+            )),
+        );
+
+        // encrypted txs can take up at most 1/3 of the block space
+        assert_matches!(
+            alloc.try_alloc(encrypted_tx(&[0; 21])),
+            Err(ProposalAllocFailure::Alloc {
+                kind: ProposedTxKind::Encrypted,
+                failure: AllocFailure::OverflowsBin { .. },
+            })
+        );
+        assert!(alloc.try_alloc(encrypted_tx(&[0; 18])).is_ok());
+
+        // decrypted txs take up as much of the remaining space as needed
+        assert!(alloc.try_alloc(ProposedTx::Decrypted(&[0; 17])).is_ok());
+        assert_matches!(
+            alloc.try_alloc(encrypted_tx(&[0; 1])),
+            Err(ProposalAllocFailure::OutOfOrder {
+                kind: ProposedTxKind::Encrypted,
+                after: ProposedTxKind::Decrypted,
+            })
+        );
+
+        // protocol txs fill the rest of the block
+        assert!(alloc.try_alloc(ProposedTx::Protocol(&[0; 25])).is_ok());
+        assert_matches!(
+            alloc.try_alloc(ProposedTx::Protocol(&[0; 1])),
+            Err(ProposalAllocFailure::Alloc {
+                kind: ProposedTxKind::Protocol,
+                failure: AllocFailure::Rejected { .. },
+            })
+        );
+        assert_matches!(
+            alloc.try_alloc(ProposedTx::Decrypted(&[0; 1])),
+            Err(ProposalAllocFailure::OutOfOrder {
+                kind: ProposedTxKind::Decrypted,
+                after: ProposedTxKind::Protocol,
+            })
+        );
+    }
+
     // Test that we cannot include encrypted txs in a block
     // when the state invariants banish them from inclusion.
     #[test]
@@ -429,6 +464,15 @@ mod tests {
         fn test_tx_dump_doesnt_fill_up_bin(args in arb_transactions()) {
             proptest_tx_dump_doesnt_fill_up_bin(args)
         }
+
+        /// Test that, whatever the txs dumped through all the states of a
+        /// [`BlockAllocator`], its bins never exceed their allotted space
+        /// nor the space of the block, and a failed allocation leaves the
+        /// bin untouched.
+        #[test]
+        fn test_bins_never_exceed_their_budget(args in arb_transactions()) {
+            proptest_bins_never_exceed_their_budget(args)
+        }
     }
 
     /// Implementation of [`test_reject_tx_on_bin_cap_reached`].
@@ -522,6 +566,88 @@ mod tests {
         }
     }
 
+    /// Implementation of [`test_bins_never_exceed_their_budget`].
+    fn proptest_bins_never_exceed_their_budget(args: PropTx) {
+        let PropTx {
+            tendermint_max_block_space_in_bytes,
+            max_block_gas,
+            protocol_txs,
+            encrypted_txs,
+            decrypted_txs,
+        } = args;
+
+        let mut bins = BsaWrapperTxs::init(
+            tendermint_max_block_space_in_bytes,
+            max_block_gas,
+        );
+        for tx in encrypted_txs {
+            let occupied = bins.encrypted_txs.space.occupied;
+            let result = bins.try_alloc(BlockResources::new(&tx, 0));
+            assert_bin_invariants(
+                &bins.encrypted_txs.space,
+                occupied,
+                &tx,
+                result,
+            );
+            assert_block_invariants(&bins);
+        }
+
+        let mut bins = bins.next_state();
+        assert_eq!(
+            bins.encrypted_txs.space.allotted,
+            bins.encrypted_txs.space.occupied
+        );
+        for tx in decrypted_txs {
+            let occupied = bins.decrypted_txs.occupied;
+            let result = bins.try_alloc(&tx);
+            assert_bin_invariants(&bins.decrypted_txs, occupied, &tx, result);
+            assert_block_invariants(&bins);
+        }
+
+        let mut bins = bins.next_state();
+        assert_eq!(bins.decrypted_txs.allotted, bins.decrypted_txs.occupied);
+        for tx in protocol_txs {
+            let occupied = bins.protocol_txs.occupied;
+            let result = bins.try_alloc(&tx);
+            assert_bin_invariants(&bins.protocol_txs, occupied, &tx, result);
+            assert_block_invariants(&bins);
+        }
+    }
+
+    /// Check the space occupied by a bin after trying to dump a tx in it.
+    fn assert_bin_invariants(
+        bin: &TxBin<BlockSpace>,
+        occupied_before: u64,
+        tx: &[u8],
+        result: Result<(), AllocFailure>,
+    ) {
+        assert!(bin.occupied <= bin.allotted);
+        match result {
+            Ok(()) => {
+                assert_eq!(bin.occupied, occupied_before + tx.len() as u64)
+            }
+            Err(AllocFailure::Rejected { bin_resource_left }) => {
+                assert_eq!(bin.occupied, occupied_before);
+                assert_eq!(bin_resource_left, bin.resource_left());
+                assert!(tx.len() as u64 > bin_resource_left);
+            }
+            Err(AllocFailure::OverflowsBin { bin_resource }) => {
+                assert_eq!(bin.occupied, occupied_before);
+                assert_eq!(bin_resource, bin.allotted);
+                assert!(tx.len() as u64 > bin_resource);
+            }
+        }
+    }
+
+    /// Check that the bins of a [`BlockAllocator`] never claim more space
+    /// than the block has.
+    fn assert_block_invariants<State>(bins: &BlockAllocator<State>) {
+        let total_bin_space = bins.protocol_txs.allotted
+            + bins.encrypted_txs.space.allotted
+            + bins.decrypted_txs.allotted;
+        assert!(total_bin_space <= bins.block.allotted);
+    }
+
     prop_compose! {
         /// Generate arbitrarily sized txs of different kinds.
         fn arb_transactions()
@@ -18,12 +18,16 @@
 //!    this state, we populate a block with DKG decrypted txs.
 //! 3. [`BuildingProtocolTxBatch`] - the third state. In
 //!    this state, we populate a block with protocol txs.
+//!
+//! `ProcessProposal` walks the same DAG with a [`ProposalAllocator`], to
+//! check that a proposal respects the order and the bins of the states.
 
 mod decrypted_txs;
 mod encrypted_txs;
+mod proposal;
 mod protocol_txs;
 
-use super::{AllocFailure, BlockAllocator};
+use super::{AllocFailure, BlockAllocator, BlockResources};
 
 /// Convenience wrapper for a [`BlockAllocator`] state that allocates
 /// encrypted transactions.
@@ -37,6 +41,57 @@ pub enum EncryptedTxBatchAllocator {
     ),
 }
 
+/// The [`BlockAllocator`] state machine, driven over the txs of a proposal
+/// that has already been built. The txs must be allocated in the order of
+/// the states, each in the bin that `PrepareProposal` would have allocated
+/// it in.
+pub enum ProposalAllocator {
+    BuildingEncryptedTxBatch(EncryptedTxBatchAllocator),
+    BuildingDecryptedTxBatch(BlockAllocator<BuildingDecryptedTxBatch>),
+    BuildingProtocolTxBatch(BlockAllocator<BuildingProtocolTxBatch>),
+}
+
+/// The kinds of txs in a block proposal, in the order of the
+/// [`BlockAllocator`] states that allocate them.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd)]
+pub enum ProposedTxKind {
+    Encrypted,
+    Decrypted,
+    Protocol,
+}
+
+impl std::fmt::Display for ProposedTxKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Encrypted => write!(f, "encrypted"),
+            Self::Decrypted => write!(f, "decrypted"),
+            Self::Protocol => write!(f, "protocol"),
+        }
+    }
+}
+
+/// A tx of a block proposal, with the resources it requires.
+pub enum ProposedTx<'tx> {
+    Encrypted(BlockResources<'tx>),
+    Decrypted(&'tx [u8]),
+    Protocol(&'tx [u8]),
+}
+
+/// Failure to allocate a tx with a [`ProposalAllocator`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum ProposalAllocFailure {
+    /// The tx was proposed after txs of a later kind.
+    OutOfOrder {
+        kind: ProposedTxKind,
+        after: ProposedTxKind,
+    },
+    /// The tx doesn't fit in the bin of its kind.
+    Alloc {
+        kind: ProposedTxKind,
+        failure: AllocFailure,
+    },
+}
+
 /// The leader of the current Tendermint round is building
 /// a new batch of DKG decrypted transactions.
 ///
@@ -0,0 +1,95 @@
+use std::marker::PhantomData;
+
+use super::super::{BlockAllocator, EncryptedTxsBins, TxBin};
+use super::{
+    EncryptedTxBatchAllocator, NextState, ProposalAllocFailure,
+    ProposalAllocator, ProposedTx, ProposedTxKind, TryAlloc,
+};
+
+impl From<EncryptedTxBatchAllocator> for ProposalAllocator {
+    #[inline]
+    fn from(alloc: EncryptedTxBatchAllocator) -> Self {
+        Self::BuildingEncryptedTxBatch(alloc)
+    }
+}
+
+impl<'tx> ProposedTx<'tx> {
+    /// The kind of this tx
+    #[inline]
+    pub fn kind(&self) -> ProposedTxKind {
+        match self {
+            ProposedTx::Encrypted(_) => ProposedTxKind::Encrypted,
+            ProposedTx::Decrypted(_) => ProposedTxKind::Decrypted,
+            ProposedTx::Protocol(_) => ProposedTxKind::Protocol,
+        }
+    }
+}
+
+impl ProposalAllocator {
+    /// The kind of txs allocated in the current state
+    #[inline]
+    pub fn kind(&self) -> ProposedTxKind {
+        match self {
+            Self::BuildingEncryptedTxBatch(_) => ProposedTxKind::Encrypted,
+            Self::BuildingDecryptedTxBatch(_) => ProposedTxKind::Decrypted,
+            Self::BuildingProtocolTxBatch(_) => ProposedTxKind::Protocol,
+        }
+    }
+
+    /// Try to allocate the next tx of a proposal, transitioning to the
+    /// state of its kind. Fails if a state of a later kind of txs has
+    /// already been reached, or if the tx doesn't fit in its bin.
+    pub fn try_alloc(
+        &mut self,
+        tx: ProposedTx<'_>,
+    ) -> Result<(), ProposalAllocFailure> {
+        let kind = tx.kind();
+        if kind < self.kind() {
+            return Err(ProposalAllocFailure::OutOfOrder {
+                kind,
+                after: self.kind(),
+            });
+        }
+        while self.kind() < kind {
+            self.next_state();
+        }
+        match (self, tx) {
+            (
+                Self::BuildingEncryptedTxBatch(alloc),
+                ProposedTx::Encrypted(resources),
+            ) => alloc.try_alloc(resources),
+            (
+                Self::BuildingDecryptedTxBatch(alloc),
+                ProposedTx::Decrypted(tx),
+            ) => alloc.try_alloc(tx),
+            (
+                Self::BuildingProtocolTxBatch(alloc),
+                ProposedTx::Protocol(tx),
+            ) => alloc.try_alloc(tx),
+            _ => unreachable!("The allocator is in the state of the tx"),
+        }
+        .map_err(|failure| ProposalAllocFailure::Alloc { kind, failure })
+    }
+
+    /// Transition to the next state of the [`BlockAllocator`]
+    fn next_state(&mut self) {
+        // Swap in an empty allocator, to be able to move out the current
+        // state
+        let empty = Self::BuildingProtocolTxBatch(BlockAllocator {
+            _state: PhantomData,
+            block: TxBin::default(),
+            protocol_txs: TxBin::default(),
+            encrypted_txs: EncryptedTxsBins::default(),
+            decrypted_txs: TxBin::default(),
+        });
+        *self = match std::mem::replace(self, empty) {
+            Self::BuildingEncryptedTxBatch(alloc) => {
+                Self::BuildingDecryptedTxBatch(alloc.next_state())
+            }
+            Self::BuildingDecryptedTxBatch(alloc) => {
+                Self::BuildingProtocolTxBatch(alloc.next_state())
+            }
+            state @ Self::BuildingProtocolTxBatch(_) => state,
+        };
+    }
+}
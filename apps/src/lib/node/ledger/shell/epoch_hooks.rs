@@ -0,0 +1,191 @@
+//! Handlers applied by [`Shell::finalize_block`] when a new epoch begins.
+//!
+//! All the epoch transition logic is registered in [`epoch_hooks`], so that
+//! the order in which the hooks are applied is defined in a single place.
+//! Each hook belongs to an [`EpochHookStage`], which determines at which point
+//! of `FinalizeBlock` it gets applied relative to the processing of the block's
+//! votes and evidence.
+
+use namada::core::ledger::masp_conversions::update_allowed_conversions;
+use namada::ledger::pos::namada_proof_of_stake;
+use namada::types::storage::Epoch;
+
+use super::governance::execute_governance_proposals;
+use super::*;
+
+/// The point of [`Shell::finalize_block`] at which an epoch hook is applied
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EpochHookStage {
+    /// Applied as soon as the new epoch has begun, before the block's votes
+    /// are used to log rewards
+    NewEpoch,
+    /// Applied after the slashes from the block's evidence have been recorded
+    AfterEvidence,
+    /// Applied after validators have been jailed for inactivity
+    AfterLiveness,
+}
+
+/// A handler of an epoch transition
+pub struct EpochHook<D, H>
+where
+    D: DB + for<'iter> DBIter<'iter> + Sync + 'static,
+    H: StorageHasher + Sync + 'static,
+{
+    /// Name of the hook, used for logging
+    pub name: &'static str,
+    /// When the hook is applied
+    pub stage: EpochHookStage,
+    /// Apply the hook in the given (newly started) epoch
+    pub apply: fn(
+        &mut Shell<D, H>,
+        &mut shim::response::FinalizeBlock,
+        Epoch,
+    ) -> Result<()>,
+}
+
+/// The registry of epoch transition hooks. Within the same stage, the hooks
+/// are applied in the order in which they appear here.
+pub fn epoch_hooks<D, H>() -> [EpochHook<D, H>; 8]
+where
+    D: DB + for<'iter> DBIter<'iter> + Sync + 'static,
+    H: StorageHasher + Sync + 'static,
+{
+    [
+        EpochHook {
+            name: "new epoch event",
+            stage: EpochHookStage::NewEpoch,
+            apply: |shell, response, current_epoch| {
+                let height = shell.wl_storage.storage.block.height;
+                response
+                    .events
+                    .push(Event::new_epoch_event(current_epoch, height));
+                Ok(())
+            },
+        },
+        EpochHook {
+            name: "MASP conversions update",
+            stage: EpochHookStage::NewEpoch,
+            apply: |shell, _response, _current_epoch| {
+                update_allowed_conversions(&mut shell.wl_storage)?;
+                Ok(())
+            },
+        },
+        EpochHook {
+            name: "governance proposals execution",
+            stage: EpochHookStage::NewEpoch,
+            apply: |shell, response, _current_epoch| {
+                execute_governance_proposals(shell, response)?;
+                Ok(())
+            },
+        },
+        EpochHook {
+            name: "validator sets copy",
+            stage: EpochHookStage::NewEpoch,
+            apply: |shell, _response, current_epoch| {
+                let pos_params = read_pos_params(&shell.wl_storage)?;
+                // Copy the new_epoch + pipeline_len - 1 validator set into
+                // new_epoch + pipeline_len
+                namada_proof_of_stake::validator_set_update::copy_validator_sets_and_positions(
+                    &mut shell.wl_storage,
+                    &pos_params,
+                    current_epoch,
+                    current_epoch + pos_params.pipeline_len,
+                )?;
+                Ok(())
+            },
+        },
+        EpochHook {
+            name: "total consensus stake",
+            stage: EpochHookStage::NewEpoch,
+            apply: |shell, _response, current_epoch| {
+                // Compute the total stake of the consensus validator set and
+                // record it in storage
+                namada_proof_of_stake::compute_and_store_total_consensus_stake(
+                    &mut shell.wl_storage,
+                    current_epoch,
+                )?;
+                Ok(())
+            },
+        },
+        EpochHook {
+            name: "slashes processing",
+            stage: EpochHookStage::AfterEvidence,
+            apply: |shell, _response, _current_epoch| {
+                // Invariant: Process slashes before inflation as they may
+                // affect the rewards in the current epoch.
+                shell.process_slashes();
+                Ok(())
+            },
+        },
+        EpochHook {
+            name: "inflation",
+            stage: EpochHookStage::AfterEvidence,
+            apply: |shell, _response, current_epoch| {
+                shell.apply_inflation(current_epoch)
+            },
+        },
+        EpochHook {
+            name: "liveness data pruning",
+            stage: EpochHookStage::AfterLiveness,
+            apply: |shell, _response, current_epoch| {
+                // Prune liveness data from validators that are no longer in
+                // the consensus set
+                namada_proof_of_stake::prune_liveness_data(
+                    &mut shell.wl_storage,
+                    current_epoch,
+                )?;
+                Ok(())
+            },
+        },
+    ]
+}
+
+impl<D, H> Shell<D, H>
+where
+    D: DB + for<'iter> DBIter<'iter> + Sync + 'static,
+    H: StorageHasher + Sync + 'static,
+{
+    /// Apply all the registered epoch hooks of the given stage, in order
+    pub(super) fn apply_epoch_hooks(
+        &mut self,
+        stage: EpochHookStage,
+        response: &mut shim::response::FinalizeBlock,
+        current_epoch: Epoch,
+    ) -> Result<()> {
+        for hook in epoch_hooks::<D, H>()
+            .into_iter()
+            .filter(|hook| hook.stage == stage)
+        {
+            tracing::debug!(
+                "Applying epoch hook \"{}\" in epoch {current_epoch}",
+                hook.name
+            );
+            (hook.apply)(self, response, current_epoch)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test_epoch_hooks {
+    use super::*;
+    use crate::node::ledger::storage::{PersistentDB, PersistentStorageHasher};
+
+    /// Test that slashes are processed before inflation is applied, as they
+    /// may affect the rewards of the current epoch.
+    #[test]
+    fn test_slashes_processed_before_inflation() {
+        let hooks = epoch_hooks::<PersistentDB, PersistentStorageHasher>();
+        let position = |name: &str| {
+            hooks
+                .iter()
+                .position(|hook| hook.name == name)
+                .expect("The hook must be registered")
+        };
+        assert_eq!(
+            hooks[position("slashes processing")].stage,
+            hooks[position("inflation")].stage
+        );
+        assert!(position("slashes processing") < position("inflation"));
+    }
+}
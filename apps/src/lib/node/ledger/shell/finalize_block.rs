@@ -4,12 +4,11 @@ use data_encoding::HEXUPPER;
 use masp_primitives::merkle_tree::CommitmentTree;
 use masp_primitives::sapling::Node;
 use masp_proofs::bls12_381;
-use namada::core::ledger::masp_conversions::update_allowed_conversions;
 use namada::core::ledger::pgf::inflation as pgf_inflation;
 use namada::core::types::storage::KeySeg;
 use namada::ledger::events::EventType;
 use namada::ledger::gas::{GasMetering, TxGasMeter};
-use namada::ledger::pos::namada_proof_of_stake;
+use namada::ledger::pos::{namada_proof_of_stake, PosQueries};
 use namada::ledger::protocol;
 use namada::ledger::storage::wl_storage::WriteLogAndStorage;
 use namada::ledger::storage::write_log::StorageModification;
@@ -20,6 +19,7 @@ use namada::proof_of_stake::storage::{
     write_last_block_proposer_address,
 };
 use namada::types::address::MASP;
+use namada::types::hash::Hash;
 use namada::types::key::tm_raw_hash_to_string;
 use namada::types::storage::{BlockHash, BlockResults, Epoch, Header};
 use namada::types::token::{
@@ -30,7 +30,7 @@ use namada::types::transaction::protocol::{
 };
 use namada::types::vote_extensions::ethereum_events::MultiSignedEthEvent;
 
-use super::governance::execute_governance_proposals;
+use super::epoch_hooks::EpochHookStage;
 use super::*;
 use crate::facade::tendermint::abci::types::{Misbehavior, VoteInfo};
 use crate::node::ledger::shell::stats::InternalStats;
@@ -66,10 +66,28 @@ where
     ) -> Result<shim::response::FinalizeBlock> {
         let mut response = shim::response::FinalizeBlock::default();
 
+        // Halt at the height of an upgrade scheduled by governance, unless
+        // this node already runs the upgraded app
+        self.check_upgrade_plan(
+            self.wl_storage.storage.get_last_block_height() + 1,
+        )?;
+
         // Begin the new block and check if a new epoch has begun
         let (height, new_epoch) =
             self.update_state(req.header, req.hash, req.byzantine_validators);
 
+        // Migrate the storage written by an older version of the node, at
+        // the height of the chain upgrade
+        migrations::apply_pending_migrations(&mut self.wl_storage, height)?;
+
+        // Update the randomness beacon with the new block's hash
+        let block_hash = self.wl_storage.storage.block.hash.clone();
+        parameters::update_block_randomness(
+            &mut self.wl_storage,
+            &block_hash,
+            height,
+        )?;
+
         let (current_epoch, _gas) = self.wl_storage.storage.get_current_epoch();
         let update_for_tendermint = matches!(
             self.wl_storage.storage.update_epoch_blocks_delay,
@@ -98,23 +116,9 @@ where
             namada_proof_of_stake::storage::read_pos_params(&self.wl_storage)?;
 
         if new_epoch {
-            update_allowed_conversions(&mut self.wl_storage)?;
-
-            execute_governance_proposals(self, &mut response)?;
-
-            // Copy the new_epoch + pipeline_len - 1 validator set into
-            // new_epoch + pipeline_len
-            namada_proof_of_stake::validator_set_update::copy_validator_sets_and_positions(
-                &mut self.wl_storage,
-                &pos_params,
-                current_epoch,
-                current_epoch + pos_params.pipeline_len,
-            )?;
-
-            // Compute the total stake of the consensus validator set and record
-            // it in storage
-            namada_proof_of_stake::compute_and_store_total_consensus_stake(
-                &mut self.wl_storage,
+            self.apply_epoch_hooks(
+                EpochHookStage::NewEpoch,
+                &mut response,
                 current_epoch,
             )?;
         }
@@ -140,10 +144,11 @@ where
         // Invariant: This has to be applied after
         // `copy_validator_sets_and_positions` if we're starting a new epoch
         if new_epoch {
-            // Invariant: Process slashes before inflation as they may affect
-            // the rewards in the current epoch.
-            self.process_slashes();
-            self.apply_inflation(current_epoch)?;
+            self.apply_epoch_hooks(
+                EpochHookStage::AfterEvidence,
+                &mut response,
+                current_epoch,
+            )?;
         }
 
         // Consensus set liveness check
@@ -180,10 +185,9 @@ where
         )?;
 
         if new_epoch {
-            // Prune liveness data from validators that are no longer in the
-            // consensus set
-            namada_proof_of_stake::prune_liveness_data(
-                &mut self.wl_storage,
+            self.apply_epoch_hooks(
+                EpochHookStage::AfterLiveness,
+                &mut response,
                 current_epoch,
             )?;
         }
@@ -203,8 +207,10 @@ where
 
         // Tracks the accepted transactions
         self.wl_storage.storage.block.results = BlockResults::default();
+        self.wl_storage.storage.block.address_txs.clear();
         let mut changed_keys = BTreeSet::new();
         for (tx_index, processed_tx) in req.txs.iter().enumerate() {
+            stats.add_bytes_used(processed_tx.tx.len());
             let tx = if let Ok(tx) = Tx::try_from(processed_tx.tx.as_ref()) {
                 tx
             } else {
@@ -438,6 +444,13 @@ where
                             );
                             changed_keys
                                 .extend(result.changed_keys.iter().cloned());
+                            if let Ok(hash) = tx_event["hash"].parse() {
+                                self.index_address_txs(
+                                    tx_index,
+                                    hash,
+                                    &result.vps_result.accepted_vps,
+                                );
+                            }
                             stats.increment_successful_txs();
                             if let Some(wrapper) = embedding_wrapper {
                                 self.commit_inner_tx_hash(wrapper);
@@ -472,7 +485,18 @@ where
                                         .eth_bridge_events
                                         .iter()
                                         .map(Event::from),
-                                ),
+                                )
+                                // custom events emitted by the tx code
+                                .chain(result.events.iter().cloned().map(
+                                    |custom_event| {
+                                        let mut event =
+                                            Event::from(custom_event);
+                                        event["height"] = height.to_string();
+                                        event["hash"] =
+                                            tx_event["hash"].clone();
+                                        event
+                                    },
+                                )),
                         );
                     } else {
                         tracing::trace!(
@@ -495,6 +519,7 @@ where
                         self.wl_storage.drop_tx();
                         tx_event["code"] = ResultCode::InvalidTx.into();
                     }
+                    stats.add_gas_used(&tx_header.tx_type, result.gas_used);
                     tx_event["gas_used"] = result.gas_used.to_string();
                     tx_event["info"] = "Check inner_tx for result.".to_string();
                     tx_event["inner_tx"] = result.to_string();
@@ -538,6 +563,10 @@ where
                     }
 
                     stats.increment_errored_txs();
+                    stats.add_gas_used(
+                        &tx_header.tx_type,
+                        tx_gas_meter.get_tx_consumed_gas(),
+                    );
                     self.wl_storage.drop_tx();
 
                     tx_event["gas_used"] =
@@ -565,6 +594,17 @@ where
 
         tracing::info!("{}", stats);
         tracing::info!("{}", stats.format_tx_executed());
+        let max_block_gas = Gas::from_whole_units(
+            namada::core::ledger::gas::get_max_block_gas(&self.wl_storage)
+                .expect("Max block gas parameter must be in storage"),
+        );
+        let max_proposal_bytes =
+            self.wl_storage.pos_queries().get_max_proposal_bytes().get();
+        self.record_block_stats(stats.block_stats(
+            height,
+            max_block_gas,
+            max_proposal_bytes,
+        ));
 
         // Update the MASP commitment tree anchor if the tree was updated
         let tree_key = Key::from(MASP.to_db_key())
@@ -603,6 +643,29 @@ where
         Ok(response)
     }
 
+    /// Check that this node can process the block at the given height, i.e.
+    /// that it's not scheduled for an upgrade to a newer app version
+    fn check_upgrade_plan(&self, height: BlockHeight) -> Result<()> {
+        match parameters::read_upgrade_plan(&self.wl_storage)? {
+            Some(plan)
+                if height >= plan.height && APP_VERSION < plan.app_version =>
+            {
+                tracing::error!(
+                    "Reached the height {} of the chain upgrade to app \
+                     version {}, this node runs the app version {}",
+                    plan.height,
+                    plan.app_version,
+                    APP_VERSION
+                );
+                Err(Error::UpgradeRequired {
+                    height: plan.height,
+                    app_version: plan.app_version,
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+
     /// Sets the metadata necessary for a new block, including
     /// the hash, height, validator changes, and evidence of
     /// byzantine behavior. Applies slashes if necessary.
@@ -659,7 +722,10 @@ where
     /// account, then update the reward products of the validators. This is
     /// executed while finalizing the first block of a new epoch and is applied
     /// with respect to the previous epoch.
-    fn apply_inflation(&mut self, current_epoch: Epoch) -> Result<()> {
+    pub(super) fn apply_inflation(
+        &mut self,
+        current_epoch: Epoch,
+    ) -> Result<()> {
         let last_epoch = current_epoch.prev();
 
         // Get the number of blocks in the last epoch
@@ -740,6 +806,27 @@ where
             .delete_tx_hash(wrapper_tx.header_hash())
             .expect("Error while deleting tx hash from storage");
     }
+
+    // Stage the applied tx to be written to the node's index of txs by the
+    // addresses whose VPs it triggered when the block is committed. The index
+    // is not a part of the ledger's state.
+    fn index_address_txs(
+        &mut self,
+        tx_index: usize,
+        hash: Hash,
+        addresses: &BTreeSet<Address>,
+    ) {
+        let tx_index = TxIndex(
+            tx_index
+                .try_into()
+                .expect("transaction index out of bounds"),
+        );
+        self.wl_storage.storage.block.address_txs.extend(
+            addresses
+                .iter()
+                .map(|owner| (owner.clone(), tx_index, hash)),
+        );
+    }
 }
 
 /// Convert ABCI vote info to PoS vote info. Any info which fails the conversion
@@ -829,7 +916,7 @@ mod test_finalize_block {
     use namada::ledger::gas::VpGasMeter;
     use namada::ledger::native_vp::parameters::ParametersVp;
     use namada::ledger::native_vp::NativeVp;
-    use namada::ledger::parameters::EpochDuration;
+    use namada::ledger::parameters::{EpochDuration, UpgradePlan};
     use namada::ledger::pos::PosQueries;
     use namada::ledger::storage_api;
     use namada::ledger::storage_api::StorageWrite;
@@ -1096,6 +1183,11 @@ mod test_finalize_block {
         }
         // check that the corresponding wrapper tx was removed from the queue
         assert!(shell.wl_storage.storage.tx_queue.is_empty());
+        // check that the space used by the tx was recorded in the block
+        // stats, but no gas since the tx was not applied
+        let stats = shell.block_stats.back().expect("Test failed");
+        assert_eq!(stats.bytes_used, outer_tx.to_bytes().len() as u64);
+        assert!(stats.gas_used_by_type.is_empty());
     }
 
     /// Test that if a tx is undecryptable, it is applied
@@ -1577,6 +1669,102 @@ mod test_finalize_block {
         });
     }
 
+    /// Test that the randomness beacon value is updated in every block and
+    /// depends on the block hash.
+    #[test]
+    fn test_block_randomness_updated() {
+        let (mut shell, _, _, _) = setup();
+        let read_randomness = |shell: &TestShell| {
+            parameters::read_block_randomness(&shell.wl_storage)
+                .expect("Test failed")
+        };
+        assert!(read_randomness(&shell).is_none());
+
+        let mut randomness = vec![];
+        for hash in [[1u8; 32], [2u8; 32]] {
+            shell
+                .finalize_block(FinalizeBlock {
+                    hash: BlockHash(hash),
+                    ..Default::default()
+                })
+                .expect("Test failed");
+            randomness.push(read_randomness(&shell).expect("Test failed"));
+            shell.commit();
+        }
+        assert_ne!(randomness[0], randomness[1]);
+    }
+
+    /// Test that an event is emitted in the block in which a new epoch begins
+    #[test]
+    fn test_new_epoch_event() {
+        let (mut shell, _, _, _) = setup();
+        let is_new_epoch_event =
+            |event: &Event| event.event_type == EventType::NewEpoch;
+
+        let response = shell
+            .finalize_block(FinalizeBlock::default())
+            .expect("Test failed");
+        assert!(!response.events.iter().any(is_new_epoch_event));
+        shell.commit();
+
+        shell.start_new_epoch_in(1);
+        let mut req = FinalizeBlock::default();
+        req.header.time = DateTimeUtc::now();
+        let response = shell.finalize_block(req).expect("Test failed");
+        let events: Vec<_> = response
+            .events
+            .into_iter()
+            .filter(is_new_epoch_event)
+            .collect();
+        assert_eq!(events.len(), 1);
+        let (current_epoch, _gas) =
+            shell.wl_storage.storage.get_current_epoch();
+        assert_eq!(events[0]["epoch"], current_epoch.to_string());
+        assert_eq!(
+            events[0]["height"],
+            shell.wl_storage.storage.block.height.to_string()
+        );
+    }
+
+    /// Test that the node halts at the height of a scheduled upgrade to a newer
+    /// app version, but not when it already runs the upgraded app
+    #[test]
+    fn test_upgrade_plan_halts() {
+        let (mut shell, _, _, _) = setup();
+        let height = shell.wl_storage.storage.get_last_block_height() + 1;
+
+        let plan = UpgradePlan {
+            height,
+            app_version: APP_VERSION + 1,
+        };
+        shell
+            .wl_storage
+            .write(&parameters::storage::get_upgrade_plan_key(), &plan)
+            .expect("Test failed");
+        match shell.finalize_block(FinalizeBlock::default()) {
+            Err(Error::UpgradeRequired {
+                height: halt_height,
+                app_version,
+            }) => {
+                assert_eq!(halt_height, height);
+                assert_eq!(app_version, APP_VERSION + 1);
+            }
+            _ => panic!("Test failed"),
+        }
+
+        let plan = UpgradePlan {
+            height,
+            app_version: APP_VERSION,
+        };
+        shell
+            .wl_storage
+            .write(&parameters::storage::get_upgrade_plan_key(), &plan)
+            .expect("Test failed");
+        shell
+            .finalize_block(FinalizeBlock::default())
+            .expect("Test failed");
+    }
+
     /// Test that the finalize block handler never commits changes directly to
     /// the DB.
     #[test]
@@ -11,7 +11,7 @@ use namada::ledger::storage::traits::StorageHasher;
 use namada::ledger::storage::{DBIter, DB};
 use namada::ledger::storage_api::token::{credit_tokens, write_denom};
 use namada::ledger::storage_api::StorageWrite;
-use namada::ledger::{ibc, pos};
+use namada::ledger::{faucet, ibc, pos};
 use namada::proof_of_stake::BecomeValidator;
 use namada::types::address::{Address, MASP};
 use namada::types::hash::Hash as CodeHash;
@@ -111,8 +111,14 @@ where
         ))]
         let genesis = {
             let chain_dir = self.base_dir.join(chain_id);
-            genesis::chain::Finalized::read_toml_files(&chain_dir)
-                .expect("Missing genesis files")
+            let genesis =
+                genesis::chain::Finalized::read_toml_files(&chain_dir)
+                    .expect("Missing genesis files");
+            // Refuse to start from genesis files that don't match the chain
+            genesis
+                .validate_chain_id()
+                .map_err(|err| Error::ChainId(err.to_string()))?;
+            genesis
         };
         #[cfg(all(
             any(test, feature = "benches"),
@@ -237,6 +243,16 @@ where
                 .unwrap();
         }
 
+        // enable the testnet faucet if the configuration is set.
+        if let Some(config) = &genesis.parameters.faucet_params {
+            tracing::debug!("Enabling the testnet faucet.");
+            faucet::write_withdrawal_limit(
+                &mut self.wl_storage,
+                config.withdrawal_limit,
+            )
+            .unwrap();
+        }
+
         // Depends on parameters being initialized
         self.wl_storage
             .storage
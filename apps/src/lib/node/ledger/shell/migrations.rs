@@ -0,0 +1,334 @@
+//! Migrations of the storage schema.
+//!
+//! The version of the storage schema is persisted with every committed block.
+//! When a node runs on a DB written with an older schema, the pending
+//! [`migrations`] are applied in order at the beginning of the `FinalizeBlock`
+//! of the height of the chain upgrade scheduled by governance, so that all the
+//! upgraded nodes apply them to the same state and their changes are
+//! committed with the block like any other state change. A node refuses to
+//! start on a DB written with a schema version newer than [`SCHEMA_VERSION`].
+
+use std::collections::BTreeMap;
+
+use namada::ledger::storage::{DBIter, Storage, StorageHasher, WlStorage, DB};
+use namada::ledger::storage_api::{account, ResultExt, StorageWrite};
+use namada::ledger::{parameters, storage_api};
+use namada::types::address::Address;
+use namada::types::storage::{BlockHeight, Key};
+
+use super::{Error, Result};
+
+/// The version of the storage schema of this node
+pub const SCHEMA_VERSION: u64 = 1;
+
+/// A migration of the storage schema
+pub struct Migration<D, H>
+where
+    D: DB + for<'iter> DBIter<'iter>,
+    H: StorageHasher,
+{
+    /// The schema version that the migration upgrades the storage to
+    pub version: u64,
+    /// Name of the migration, used for logging
+    pub name: &'static str,
+    /// Apply the migration to the storage
+    pub apply: fn(&mut WlStorage<D, H>) -> storage_api::Result<()>,
+}
+
+/// The registry of the storage migrations, ordered by their version. A
+/// migration to e.g. re-key the balances of a token must be added here
+/// together with a bump of the [`SCHEMA_VERSION`].
+pub fn migrations<D, H>() -> [Migration<D, H>; 1]
+where
+    D: 'static + DB + for<'iter> DBIter<'iter>,
+    H: 'static + StorageHasher,
+{
+    [Migration {
+        version: 1,
+        name: "count the storage usage of the accounts",
+        apply: count_storage_usage,
+    }]
+}
+
+/// Count the storage usage of every account from the committed subspace. The
+/// usage is otherwise only tracked by the changes of the committed blocks, so
+/// the counters of a chain started before it was tracked miss the keys that
+/// the accounts already held.
+fn count_storage_usage<D, H>(
+    wl_storage: &mut WlStorage<D, H>,
+) -> storage_api::Result<()>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter>,
+    H: 'static + StorageHasher,
+{
+    let mut usages: BTreeMap<Address, u64> = BTreeMap::new();
+    for (key, value, _gas) in wl_storage.storage.db.iter_prefix(None) {
+        let key = Key::parse(key).into_storage_result()?;
+        if let Some(owner) = account::storage_usage_owner(&key) {
+            *usages.entry(owner.clone()).or_default() +=
+                (key.len() + value.len()) as u64;
+        }
+    }
+    // Replace all the counters, including the ones of the accounts that no
+    // longer hold any keys
+    let counters = storage_api::iter_prefix_bytes(
+        wl_storage,
+        &account::storage_usage_prefix(),
+    )?
+    .map(|res| res.map(|(key, _value)| key))
+    .collect::<storage_api::Result<Vec<_>>>()?;
+    for key in counters {
+        wl_storage.delete(&key)?;
+    }
+    for (owner, usage) in usages {
+        wl_storage.write(&account::storage_usage_key(&owner), usage)?;
+    }
+    Ok(())
+}
+
+/// Check the schema version of the storage loaded from the DB. A fresh DB
+/// starts at the current version.
+pub fn check_schema_version<D, H>(storage: &mut Storage<D, H>) -> Result<()>
+where
+    D: DB + for<'iter> DBIter<'iter>,
+    H: StorageHasher,
+{
+    if storage.last_block.is_none() {
+        storage.schema_version = SCHEMA_VERSION;
+    } else if storage.schema_version > SCHEMA_VERSION {
+        return Err(Error::UnknownSchemaVersion {
+            stored: storage.schema_version,
+            supported: SCHEMA_VERSION,
+        });
+    } else if storage.schema_version < SCHEMA_VERSION {
+        tracing::info!(
+            "The storage schema version {} will be migrated to {} at the \
+             height of the chain upgrade",
+            storage.schema_version,
+            SCHEMA_VERSION
+        );
+    }
+    Ok(())
+}
+
+/// Apply the registered migrations that are pending for the storage, if the
+/// block at the given height is the first one of a chain upgrade
+pub fn apply_pending_migrations<D, H>(
+    wl_storage: &mut WlStorage<D, H>,
+    height: BlockHeight,
+) -> storage_api::Result<()>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter>,
+    H: 'static + StorageHasher,
+{
+    apply_migrations(wl_storage, height, &migrations(), SCHEMA_VERSION)
+}
+
+/// Apply the given migrations above the storage's schema version up to the
+/// target version, in order, if the block at the given height is the first
+/// one of a chain upgrade
+fn apply_migrations<D, H>(
+    wl_storage: &mut WlStorage<D, H>,
+    height: BlockHeight,
+    migrations: &[Migration<D, H>],
+    target_version: u64,
+) -> storage_api::Result<()>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter>,
+    H: 'static + StorageHasher,
+{
+    let current_version = wl_storage.storage.schema_version;
+    if current_version >= target_version {
+        return Ok(());
+    }
+    // The migrations must be applied at a height agreed by the chain, or the
+    // state of the nodes upgraded at different heights would diverge
+    let is_upgrade_height = parameters::read_upgrade_plan(&*wl_storage)?
+        .map_or(false, |plan| plan.height == height);
+    if !is_upgrade_height {
+        return Ok(());
+    }
+    debug_assert!(
+        migrations.windows(2).all(|w| w[0].version < w[1].version),
+        "The migrations must be ordered by their version"
+    );
+    for migration in migrations.iter().filter(|migration| {
+        migration.version > current_version
+            && migration.version <= target_version
+    }) {
+        tracing::info!(
+            "Applying the storage migration \"{}\" to schema version {}",
+            migration.name,
+            migration.version
+        );
+        (migration.apply)(wl_storage)?;
+    }
+    wl_storage.storage.schema_version = target_version;
+    Ok(())
+}
+
+#[cfg(test)]
+mod test_migrations {
+    use borsh_ext::BorshSerializeExt;
+    use namada::ledger::parameters::UpgradePlan;
+    use namada::ledger::storage::testing::TestWlStorage;
+    use namada::ledger::storage::LastBlock;
+    use namada::ledger::storage_api::StorageRead;
+    use namada::types::hash::Hash;
+    use namada::types::storage::KeySeg;
+    use namada::types::time::DateTimeUtc;
+    use namada::types::{address, token};
+
+    use super::*;
+
+    /// Test that only the pending migrations are applied, in order, at the
+    /// height of the chain upgrade, and that the schema version is bumped
+    #[test]
+    fn test_apply_migrations() {
+        let mut wl_storage = TestWlStorage::default();
+        let key = Key::parse("migrated").unwrap();
+        let migrations = [
+            Migration {
+                version: 1,
+                name: "first",
+                apply: |wl_storage: &mut TestWlStorage| {
+                    let key = Key::parse("migrated").unwrap();
+                    wl_storage.write(&key, vec![1_u64])
+                },
+            },
+            Migration {
+                version: 2,
+                name: "second",
+                apply: |wl_storage: &mut TestWlStorage| {
+                    let key = Key::parse("migrated").unwrap();
+                    let mut applied: Vec<u64> =
+                        wl_storage.read(&key)?.unwrap_or_default();
+                    applied.push(2);
+                    wl_storage.write(&key, applied)
+                },
+            },
+            Migration {
+                version: 3,
+                name: "third",
+                apply: |wl_storage: &mut TestWlStorage| {
+                    let key = Key::parse("migrated").unwrap();
+                    let mut applied: Vec<u64> =
+                        wl_storage.read(&key)?.unwrap_or_default();
+                    applied.push(3);
+                    wl_storage.write(&key, applied)
+                },
+            },
+        ];
+
+        // Nothing is applied without a chain upgrade
+        apply_migrations(&mut wl_storage, BlockHeight(5), &migrations, 2)
+            .unwrap();
+        assert_eq!(wl_storage.storage.schema_version, 0);
+        assert!(!wl_storage.has_key(&key).unwrap());
+
+        // Nor before the height of the chain upgrade
+        let plan = UpgradePlan {
+            height: BlockHeight(5),
+            app_version: 1,
+        };
+        wl_storage
+            .write(&parameters::storage::get_upgrade_plan_key(), plan)
+            .unwrap();
+        apply_migrations(&mut wl_storage, BlockHeight(4), &migrations, 2)
+            .unwrap();
+        assert_eq!(wl_storage.storage.schema_version, 0);
+        assert!(!wl_storage.has_key(&key).unwrap());
+
+        apply_migrations(&mut wl_storage, BlockHeight(5), &migrations, 2)
+            .unwrap();
+        assert_eq!(wl_storage.storage.schema_version, 2);
+        let applied: Vec<u64> = wl_storage.read(&key).unwrap().unwrap();
+        assert_eq!(applied, vec![1, 2]);
+
+        // Only the remaining migration is applied
+        wl_storage.write(&key, Vec::<u64>::new()).unwrap();
+        apply_migrations(&mut wl_storage, BlockHeight(5), &migrations, 3)
+            .unwrap();
+        assert_eq!(wl_storage.storage.schema_version, 3);
+        let applied: Vec<u64> = wl_storage.read(&key).unwrap().unwrap();
+        assert_eq!(applied, vec![3]);
+
+        // Nothing left to apply
+        wl_storage.write(&key, Vec::<u64>::new()).unwrap();
+        apply_migrations(&mut wl_storage, BlockHeight(5), &migrations, 3)
+            .unwrap();
+        let applied: Vec<u64> = wl_storage.read(&key).unwrap().unwrap();
+        assert!(applied.is_empty());
+    }
+
+    /// Test that the storage usage of the accounts is counted from their
+    /// committed keys and that the changes of the same block are added to it
+    #[test]
+    fn test_count_storage_usage() {
+        let mut wl_storage = TestWlStorage::default();
+        let owner = address::testing::established_address_1();
+        let other = address::testing::established_address_2();
+        let key = Key::from(owner.to_db_key())
+            .push(&"key".to_owned())
+            .unwrap();
+        let balance_key = token::balance_key(&address::nam(), &owner);
+        let value = vec![1_u8; 10];
+        wl_storage.storage.write(&key, value.clone()).unwrap();
+        wl_storage
+            .storage
+            .write(&balance_key, value.clone())
+            .unwrap();
+        // A stale counter of an account that doesn't hold any keys
+        wl_storage
+            .storage
+            .write(
+                &account::storage_usage_key(&other),
+                10_u64.serialize_to_vec(),
+            )
+            .unwrap();
+
+        count_storage_usage(&mut wl_storage).unwrap();
+        let expected = (key.len() + balance_key.len() + 2 * value.len()) as u64;
+        assert_eq!(
+            account::storage_usage(&wl_storage, &owner).unwrap(),
+            expected
+        );
+        assert_eq!(account::storage_usage(&wl_storage, &other).unwrap(), 0);
+
+        let new_key = Key::from(owner.to_db_key())
+            .push(&"new_key".to_owned())
+            .unwrap();
+        wl_storage.write_bytes(&new_key, &value).unwrap();
+        wl_storage.commit_block().unwrap();
+        assert_eq!(
+            account::storage_usage(&wl_storage, &owner).unwrap(),
+            expected + (new_key.len() + value.len()) as u64
+        );
+        assert_eq!(account::storage_usage(&wl_storage, &other).unwrap(), 0);
+    }
+
+    /// Test that a node refuses to start on a storage with a newer schema
+    /// version and that a fresh storage starts at the current version
+    #[test]
+    fn test_check_schema_version() {
+        let mut wl_storage = TestWlStorage::default();
+        wl_storage.storage.schema_version = SCHEMA_VERSION + 1;
+        check_schema_version(&mut wl_storage.storage).unwrap();
+        assert_eq!(wl_storage.storage.schema_version, SCHEMA_VERSION);
+
+        wl_storage.storage.last_block = Some(LastBlock {
+            height: BlockHeight(1),
+            hash: Hash::default().into(),
+            time: DateTimeUtc::now(),
+        });
+        check_schema_version(&mut wl_storage.storage).unwrap();
+        wl_storage.storage.schema_version = SCHEMA_VERSION + 1;
+        match check_schema_version(&mut wl_storage.storage) {
+            Err(Error::UnknownSchemaVersion { stored, supported }) => {
+                assert_eq!(stored, SCHEMA_VERSION + 1);
+                assert_eq!(supported, SCHEMA_VERSION);
+            }
+            _ => panic!("Test failed"),
+        }
+    }
+}
@@ -6,26 +6,31 @@
 //! (unless we can simply overwrite them in the next block).
 //! More info in <https://github.com/anoma/namada/issues/362>.
 pub mod block_alloc;
+mod epoch_hooks;
 mod finalize_block;
 mod governance;
 mod init_chain;
+mod migrations;
 pub use init_chain::InitChainValidation;
 pub mod prepare_proposal;
 pub mod process_proposal;
 pub(super) mod queries;
+mod query_cache;
 mod stats;
 #[cfg(any(test, feature = "testing"))]
 #[allow(dead_code)]
 pub mod testing;
+mod tx_filter;
 pub mod utils;
 mod vote_extensions;
 
-use std::collections::{BTreeSet, HashSet};
+use std::collections::{BTreeSet, HashSet, VecDeque};
 use std::convert::{TryFrom, TryInto};
 use std::mem;
 use std::path::{Path, PathBuf};
 #[allow(unused_imports)]
 use std::rc::Rc;
+use std::sync::Mutex;
 
 use borsh::BorshDeserialize;
 use borsh_ext::BorshSerializeExt;
@@ -44,13 +49,14 @@ use namada::ledger::protocol::{
     apply_wasm_tx, get_fee_unshielding_transaction,
     get_transfer_hash_from_storage, ShellParams,
 };
+use namada::ledger::queries::{AllocRejections, BlockStats};
 use namada::ledger::storage::wl_storage::WriteLogAndStorage;
 use namada::ledger::storage::write_log::WriteLog;
 use namada::ledger::storage::{
     DBIter, Sha256Hasher, Storage, StorageHasher, TempWlStorage, WlStorage, DB,
     EPOCH_SWITCH_BLOCKS_DELAY,
 };
-use namada::ledger::storage_api::tx::validate_tx_bytes;
+use namada::ledger::storage_api::tx::{is_tx_whitelisted, validate_tx_bytes};
 use namada::ledger::storage_api::{self, StorageRead};
 use namada::ledger::{parameters, pos, protocol};
 use namada::proof_of_stake::slashing::{process_slashes, slash};
@@ -74,7 +80,10 @@ use namada_sdk::tendermint::AppHash;
 use thiserror::Error;
 use tokio::sync::mpsc::{Receiver, UnboundedSender};
 
+use self::query_cache::QueryCache;
+use self::tx_filter::TxFilter;
 use super::ethereum_oracle::{self as oracle, last_processed_block};
+use crate::cli::namada_version;
 use crate::config::{self, genesis, TendermintMode, ValidatorLocalConfig};
 use crate::facade::tendermint::abci::types::{Misbehavior, MisbehaviorKind};
 use crate::facade::tendermint::v0_37::abci::{request, response};
@@ -115,6 +124,8 @@ pub enum Error {
     Ethereum(super::ethereum_oracle::Error),
     #[error("Server error: {0}")]
     TowerServer(String),
+    #[error("Invalid ABCI address: {0}")]
+    AbciAddress(String),
     #[error("{0}")]
     Broadcaster(tokio::sync::mpsc::error::TryRecvError),
     #[error("Error executing proposal {0}: {1}")]
@@ -127,6 +138,19 @@ pub enum Error {
     StorageApi(#[from] storage_api::Error),
     #[error("Transaction replay attempt: {0}")]
     ReplayAttempt(String),
+    #[error(
+        "Halting for the chain upgrade to app version {app_version} at height \
+         {height}, the node must be restarted with an upgraded binary"
+    )]
+    UpgradeRequired {
+        height: BlockHeight,
+        app_version: u64,
+    },
+    #[error(
+        "The storage schema version {stored} is newer than the version \
+         {supported} supported by this node"
+    )]
+    UnknownSchemaVersion { stored: u64, supported: u64 },
 }
 
 impl From<Error> for TxResult {
@@ -148,7 +172,8 @@ pub fn reset(config: config::Ledger) -> Result<()> {
         res => res.map_err(Error::RemoveDB)?,
     };
     // reset Tendermint state
-    tendermint_node::reset(config.cometbft_dir()).map_err(Error::Tendermint)?;
+    tendermint_node::reset(config.cometbft_dir(), config.chain_dir())
+        .map_err(Error::Tendermint)?;
     Ok(())
 }
 
@@ -355,12 +380,36 @@ where
     /// limit the how many block heights in the past can the storage be
     /// queried for reading values.
     storage_read_past_height_limit: Option<u64>,
+    /// Cache of query responses, enabled by config `query_cache`
+    query_cache: Option<QueryCache>,
+    /// Operator's filter of the mempool txs, enabled by config `tx_filter`
+    tx_filter: Option<TxFilter>,
     /// Proposal execution tracking
     pub proposal_data: HashSet<u64>,
     /// Log of events emitted by `FinalizeBlock` ABCI calls.
     event_log: EventLog,
+    /// Gas and space utilization of the most recent blocks
+    block_stats: VecDeque<BlockStats>,
+    /// Txs dropped from this node's block proposals for lack of space
+    alloc_rejections: Mutex<AllocRejections>,
 }
 
+/// The number of most recent blocks for which [`BlockStats`] are kept
+const BLOCK_STATS_HISTORY_LEN: usize = 100;
+
+/// The version of the application reported to CometBFT, which checks it
+/// against the app version of the block headers. It must only be bumped
+/// together with a consensus-breaking upgrade of the protocol.
+pub const APP_VERSION: u64 = 0;
+
+/// The maximum priority of a wrapper tx. The priorities above it are reserved
+/// for protocol txs.
+const MAX_WRAPPER_TX_PRIORITY: i64 = i64::MAX - 1;
+
+/// The priority of a wrapper tx is its fee per gas unit in these fractions of
+/// the minimum gas price of its fee token
+const WRAPPER_TX_PRIORITY_PRECISION: u64 = 1_000;
+
 /// Channels for communicating with an Ethereum oracle.
 #[derive(Debug)]
 pub struct EthereumOracleChannels {
@@ -404,8 +453,13 @@ where
         let db_path = config.shell.db_dir(&chain_id);
         let base_dir = config.shell.base_dir;
         let mode = config.shell.tendermint_mode;
+        let wasm_compiler = config.shell.wasm_compiler;
         let storage_read_past_height_limit =
             config.shell.storage_read_past_height_limit;
+        let query_cache = config.shell.query_cache.map(|query_cache| {
+            QueryCache::new(query_cache, storage_read_past_height_limit)
+        });
+        let tx_filter = config.shell.tx_filter.map(TxFilter::new);
         if !Path::new(&base_dir).is_dir() {
             std::fs::create_dir(&base_dir)
                 .expect("Creating directory for Namada should not fail");
@@ -430,12 +484,19 @@ where
             db_cache,
             config.shell.storage_read_past_height_limit,
         );
+        storage.block_data_retain_height =
+            config.shell.block_data_retain_height;
         storage
             .load_last_state()
             .map_err(|e| {
                 tracing::error!("Cannot load the last state from the DB {}", e);
             })
             .expect("PersistentStorage cannot be initialized");
+        migrations::check_schema_version(&mut storage)
+            .map_err(|e| {
+                tracing::error!("Cannot use the storage from the DB {}", e);
+            })
+            .expect("Unsupported storage schema version");
         let vp_wasm_cache_dir =
             base_dir.join(chain_id.as_str()).join("vp_wasm_cache");
         let tx_wasm_cache_dir =
@@ -518,15 +579,21 @@ where
             vp_wasm_cache: VpCache::new(
                 vp_wasm_cache_dir,
                 vp_wasm_compilation_cache as usize,
-            ),
+            )
+            .with_compiler(wasm_compiler),
             tx_wasm_cache: TxCache::new(
                 tx_wasm_cache_dir,
                 tx_wasm_compilation_cache as usize,
-            ),
+            )
+            .with_compiler(wasm_compiler),
             storage_read_past_height_limit,
+            query_cache,
+            tx_filter,
             proposal_data: HashSet::new(),
             // TODO: config event log params
             event_log: EventLog::default(),
+            block_stats: VecDeque::with_capacity(BLOCK_STATS_HISTORY_LEN),
+            alloc_rejections: Mutex::default(),
         };
         shell.update_eth_oracle(&Default::default());
         shell
@@ -544,6 +611,15 @@ where
         &mut self.event_log
     }
 
+    /// Record the [`BlockStats`] of a finalized block, dropping the oldest
+    /// record when the history is full
+    fn record_block_stats(&mut self, stats: BlockStats) {
+        if self.block_stats.len() == BLOCK_STATS_HISTORY_LEN {
+            self.block_stats.pop_front();
+        }
+        self.block_stats.push_back(stats);
+    }
+
     /// Iterate over the wrapper txs in order
     #[allow(dead_code)]
     fn iter_tx_queue(&mut self) -> impl Iterator<Item = &TxInQueue> {
@@ -551,9 +627,13 @@ where
     }
 
     /// Load the Merkle root hash and the height of the last committed block, if
-    /// any. This is returned when ABCI sends an `info` request.
+    /// any, along with the versions of the application and its chain id. This
+    /// is returned when ABCI sends an `info` request.
     pub fn last_state(&mut self) -> response::Info {
         let mut response = response::Info {
+            data: format!("Namada {}", self.chain_id),
+            version: namada_version().to_string(),
+            app_version: APP_VERSION,
             last_block_height: tendermint::block::Height::from(0_u32),
             ..Default::default()
         };
@@ -1082,6 +1162,17 @@ where
             }
         };
 
+        // Operator's tx filter
+        if let Some(Err(rejection)) = self
+            .tx_filter
+            .as_ref()
+            .map(|filter| filter.check(&tx, tx_bytes.len()))
+        {
+            response.code = rejection.code().into();
+            response.log = format!("{INVALID_MSG}: {rejection}");
+            return response;
+        }
+
         // Tx chain id
         if tx.header.chain_id != self.chain_id {
             response.code = ResultCode::InvalidChainId.into();
@@ -1249,6 +1340,16 @@ where
                     return response;
                 }
 
+                // Tx whitelist
+                if !is_tx_whitelisted(&self.wl_storage, &tx).expect(
+                    "Failed to read the tx whitelist param from storage",
+                ) {
+                    response.code = ResultCode::TxNotWhitelisted.into();
+                    response.log =
+                        format!("{INVALID_MSG}: Tx code is not whitelisted");
+                    return response;
+                }
+
                 // Replay protection check
                 let inner_tx_hash = tx.raw_header_hash();
                 if self
@@ -1300,6 +1401,9 @@ where
                     response.log = format!("{INVALID_MSG}: {e}");
                     return response;
                 }
+
+                // Order the mempool by the fees paid per gas unit
+                response.priority = self.wrapper_tx_priority(&wrapper);
             }
             TxType::Raw => {
                 response.code = ResultCode::InvalidTx.into();
@@ -1322,6 +1426,36 @@ where
         response
     }
 
+    /// Get the priority of a wrapper tx in the mempool and in block proposals,
+    /// which is its fee per gas unit in thousandths of the minimum gas price
+    /// of its fee token.
+    pub fn wrapper_tx_priority(&self, wrapper: &WrapperTx) -> i64 {
+        let Ok(amount_per_gas_unit) = wrapper
+            .fee
+            .amount_per_gas_unit
+            .to_amount(&wrapper.fee.token, &self.wl_storage)
+        else {
+            return 0;
+        };
+        let minimum_gas_price =
+            parameters::read_gas_cost(&self.wl_storage, &wrapper.fee.token)
+                .expect("Must be able to read gas cost parameter")
+                .filter(|price| !price.is_zero())
+                .unwrap_or_else(|| token::Amount::from_u64(1));
+        amount_per_gas_unit
+            .checked_mul(token::Amount::from_u64(WRAPPER_TX_PRIORITY_PRECISION))
+            .and_then(|amount| amount.checked_div(minimum_gas_price))
+            .map(|priority| {
+                let priority = priority.raw_amount();
+                if priority > MAX_WRAPPER_TX_PRIORITY.into() {
+                    MAX_WRAPPER_TX_PRIORITY
+                } else {
+                    priority.as_u64() as i64
+                }
+            })
+            .unwrap_or(MAX_WRAPPER_TX_PRIORITY)
+    }
+
     /// Check that the Wrapper's signer has enough funds to pay fees. If a block
     /// proposer is provided, updates the balance of the fee payer
     #[allow(clippy::too_many_arguments)]
@@ -2678,6 +2812,46 @@ mod shell_tests {
         )
     }
 
+    /// Test that the priority of a wrapper tx is its fee per gas unit in
+    /// thousandths of the minimum gas price of the fee token
+    #[test]
+    fn test_wrapper_tx_priority() {
+        let (shell, _recv, _, _) = test_utils::setup();
+        let keypair = super::test_utils::gen_keypair();
+        let native_token = shell.wl_storage.storage.native_token.clone();
+        let minimum_gas_price =
+            parameters::read_gas_cost(&shell.wl_storage, &native_token)
+                .expect("Test failed")
+                .expect("Test failed");
+        let wrapper = |amount_per_gas_unit: token::Amount| {
+            WrapperTx::new(
+                Fee {
+                    amount_per_gas_unit: DenominatedAmount::native(
+                        amount_per_gas_unit,
+                    ),
+                    token: native_token.clone(),
+                },
+                keypair.ref_to(),
+                Epoch(0),
+                Default::default(),
+                None,
+            )
+        };
+
+        assert_eq!(
+            shell.wrapper_tx_priority(&wrapper(minimum_gas_price)),
+            1_000
+        );
+        assert_eq!(
+            shell.wrapper_tx_priority(&wrapper(minimum_gas_price * 3_u64)),
+            3_000
+        );
+        assert_eq!(
+            shell.wrapper_tx_priority(&wrapper(token::Amount::max())),
+            MAX_WRAPPER_TX_PRIORITY
+        );
+    }
+
     /// Mempool validation must reject already applied wrapper and decrypted
     /// transactions
     #[test]
@@ -2816,6 +2990,18 @@ mod shell_tests {
         )
     }
 
+    /// Check that the ABCI info reports the versions of the application and
+    /// its chain id
+    #[test]
+    fn test_last_state_info() {
+        let (mut shell, _recv, _, _) = test_utils::setup();
+
+        let info = shell.last_state();
+        assert_eq!(info.version, namada_version());
+        assert_eq!(info.app_version, APP_VERSION);
+        assert_eq!(info.data, format!("Namada {}", shell.chain_id));
+    }
+
     /// Check that an expired transaction gets rejected
     #[test]
     fn test_expired_tx() {
@@ -2836,6 +3022,64 @@ mod shell_tests {
         assert_eq!(result.code, ResultCode::ExpiredTx.into());
     }
 
+    /// Check that a tx with a code that isn't in a non-empty tx whitelist gets
+    /// rejected
+    #[test]
+    fn test_tx_not_whitelisted() {
+        let (mut shell, _recv, _, _) = test_utils::setup();
+
+        let block_gas_limit =
+            namada::core::ledger::gas::get_max_block_gas(&shell.wl_storage)
+                .unwrap();
+        let keypair = super::test_utils::gen_keypair();
+
+        let mut wrapper =
+            Tx::from_type(TxType::Wrapper(Box::new(WrapperTx::new(
+                Fee {
+                    amount_per_gas_unit: DenominatedAmount::native(100.into()),
+                    token: shell.wl_storage.storage.native_token.clone(),
+                },
+                keypair.ref_to(),
+                Epoch(0),
+                block_gas_limit.into(),
+                None,
+            ))));
+        wrapper.header.chain_id = shell.chain_id.clone();
+        wrapper.set_code(Code::new("wasm_code".as_bytes().to_owned(), None));
+        wrapper.set_data(Data::new("transaction data".as_bytes().to_owned()));
+        wrapper.add_section(Section::Signature(Signature::new(
+            wrapper.sechashes(),
+            [(0, keypair)].into_iter().collect(),
+            None,
+        )));
+
+        let other_code_hash =
+            namada::types::hash::Hash::sha256("other_wasm_code".as_bytes());
+        parameters::update_tx_whitelist_parameter(
+            &mut shell.wl_storage,
+            vec![other_code_hash.to_string()],
+        )
+        .unwrap();
+        let result = shell.mempool_validate(
+            wrapper.to_bytes().as_ref(),
+            MempoolTxType::NewTransaction,
+        );
+        assert_eq!(result.code, ResultCode::TxNotWhitelisted.into());
+
+        let code_hash =
+            namada::types::hash::Hash::sha256("wasm_code".as_bytes());
+        parameters::update_tx_whitelist_parameter(
+            &mut shell.wl_storage,
+            vec![other_code_hash.to_string(), code_hash.to_string()],
+        )
+        .unwrap();
+        let result = shell.mempool_validate(
+            wrapper.to_bytes().as_ref(),
+            MempoolTxType::NewTransaction,
+        );
+        assert_ne!(result.code, ResultCode::TxNotWhitelisted.into());
+    }
+
     /// Check that a tx requiring more gas than the block limit gets rejected
     #[test]
     fn test_exceeding_max_block_gas_tx() {
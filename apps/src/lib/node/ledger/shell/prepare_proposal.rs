@@ -1,17 +1,18 @@
 //! Implementation of the [`RequestPrepareProposal`] ABCI++ method for the Shell
 
+use std::cmp::Reverse;
+
 use namada::core::hints;
 use namada::core::ledger::gas::TxGasMeter;
 use namada::ledger::pos::PosQueries;
-use namada::ledger::protocol::get_fee_unshielding_transaction;
 use namada::ledger::storage::{DBIter, StorageHasher, TempWlStorage, DB};
+use namada::ledger::storage_api::tx::validate_tx_bytes;
 use namada::proof_of_stake::storage::find_validator_by_raw_hash;
 use namada::proto::Tx;
 use namada::types::address::Address;
-use namada::types::internal::TxInQueue;
 use namada::types::key::tm_raw_hash_to_string;
 use namada::types::time::DateTimeUtc;
-use namada::types::transaction::{DecryptedTx, TxType};
+use namada::types::transaction::TxType;
 use namada::vm::wasm::{TxCache, VpCache};
 use namada::vm::WasmCacheAccess;
 
@@ -21,6 +22,7 @@ use super::block_alloc::states::{
     EncryptedTxBatchAllocator, NextState, TryAlloc,
 };
 use super::block_alloc::{AllocFailure, BlockAllocator, BlockResources};
+use super::process_proposal::decrypted_tx;
 use crate::facade::tendermint_proto::google::protobuf::Timestamp;
 use crate::facade::tendermint_proto::v0_37::abci::RequestPrepareProposal;
 use crate::node::ledger::shell::ShellMode;
@@ -44,8 +46,21 @@ where
         req: RequestPrepareProposal,
     ) -> response::PrepareProposal {
         let txs = if let ShellMode::Validator { .. } = self.mode {
+            let dropped_txs =
+                self.alloc_rejections.lock().unwrap().dropped_txs();
+
             // start counting allotted space for txs
             let alloc = self.get_encrypted_txs_allocator();
+            if let EncryptedTxBatchAllocator::WithoutEncryptedTxs(_) = &alloc {
+                tracing::warn!(
+                    proposal_height = ?self
+                        .wl_storage
+                        .pos_queries()
+                        .get_current_decision_height(),
+                    "No mempool txs are being included in the current \
+                     proposal"
+                );
+            }
 
             // add encrypted txs
             let tm_raw_hash_string =
@@ -74,6 +89,12 @@ where
             let mut protocol_txs = self.build_protocol_txs(alloc, &req.txs);
             txs.append(&mut protocol_txs);
 
+            let mut alloc_rejections = self.alloc_rejections.lock().unwrap();
+            alloc_rejections.proposals += 1;
+            if alloc_rejections.dropped_txs() > dropped_txs {
+                alloc_rejections.constrained_proposals += 1;
+            }
+
             txs
         } else {
             vec![]
@@ -100,19 +121,14 @@ where
     /// [`EncryptedTxBatchAllocator::WithoutEncryptedTxs`] value.
     /// Otherwise, we return an allocator wrapped in an
     /// [`EncryptedTxBatchAllocator::WithEncryptedTxs`] value.
+    ///
+    /// `ProcessProposal` starts from the same allocator, to check the
+    /// proposed txs against the same bins.
     #[inline]
-    fn get_encrypted_txs_allocator(&self) -> EncryptedTxBatchAllocator {
-        let pos_queries = self.wl_storage.pos_queries();
-
-        let is_2nd_height_off = pos_queries.is_deciding_offset_within_epoch(1);
-        let is_3rd_height_off = pos_queries.is_deciding_offset_within_epoch(2);
-
-        if hints::unlikely(is_2nd_height_off || is_3rd_height_off) {
-            tracing::warn!(
-                proposal_height =
-                    ?pos_queries.get_current_decision_height(),
-                "No mempool txs are being included in the current proposal"
-            );
+    pub(super) fn get_encrypted_txs_allocator(
+        &self,
+    ) -> EncryptedTxBatchAllocator {
+        if hints::unlikely(self.encrypted_txs_not_allowed()) {
             EncryptedTxBatchAllocator::WithoutEncryptedTxs(
                 (&self.wl_storage).into(),
             )
@@ -133,64 +149,134 @@ where
         block_proposer: &Address,
     ) -> (Vec<TxBytes>, BlockAllocator<BuildingDecryptedTxBatch>) {
         let pos_queries = self.wl_storage.pos_queries();
-        let block_time = block_time.and_then(|block_time| {
-            // If error in conversion, default to last block datetime, it's
-            // valid because of mempool check
-            TryInto::<DateTimeUtc>::try_into(block_time).ok()
-        });
+        // If error in conversion, default to last block datetime, like
+        // `ProcessProposal` does
+        let block_time = self.get_block_timestamp(block_time);
         let mut temp_wl_storage = TempWlStorage::new(&self.wl_storage.storage);
         let mut vp_wasm_cache = self.vp_wasm_cache.clone();
         let mut tx_wasm_cache = self.tx_wasm_cache.clone();
 
-        let txs = txs
-            .iter()
-            .filter_map(|tx_bytes| {
-                match self.validate_wrapper_bytes(tx_bytes, block_time, &mut temp_wl_storage, &mut vp_wasm_cache, &mut tx_wasm_cache, block_proposer) {
-                    Ok(gas) => {
-                        temp_wl_storage.write_log.commit_tx();
-                        Some((tx_bytes.to_owned(), gas))
-                    },
-                    Err(()) => {
-                        temp_wl_storage.write_log.drop_tx();
-                        None
+        // Propose the txs with the highest priority first, keeping the
+        // mempool order of txs with the same priority
+        let mut txs: Vec<&TxBytes> = txs.iter().collect();
+        txs.sort_by_cached_key(|tx_bytes| {
+            Reverse(self.proposed_tx_priority(tx_bytes))
+        });
+
+        // The allocator without encrypted txs rejects all of them by design,
+        // so they're not counted as dropped for lack of space
+        let record_failures =
+            matches!(alloc, EncryptedTxBatchAllocator::WithEncryptedTxs(_));
+        let mut txs = txs.into_iter();
+        let mut rejected = None;
+        let valid_txs = txs.by_ref().filter_map(|tx_bytes| {
+            match self.validate_wrapper_bytes(
+                tx_bytes,
+                block_time,
+                &mut temp_wl_storage,
+                &mut vp_wasm_cache,
+                &mut tx_wasm_cache,
+                block_proposer,
+            ) {
+                Ok(gas) => {
+                    temp_wl_storage.write_log.commit_tx();
+                    Some((tx_bytes.to_owned(), gas))
+                }
+                Err(()) => {
+                    temp_wl_storage.write_log.drop_tx();
+                    None
+                }
+            }
+        });
+        let mut proposed_txs = vec![];
+        for (tx_bytes, tx_gas) in valid_txs {
+            match alloc.try_alloc(BlockResources::new(&tx_bytes[..], tx_gas)) {
+                Ok(()) => proposed_txs.push(tx_bytes),
+                Err(status) => {
+                    if record_failures {
+                        self.record_alloc_failure(
+                            "encrypted",
+                            status,
+                            tx_bytes.len(),
+                        );
+                    }
+                    match status {
+                        AllocFailure::Rejected { bin_resource_left } => {
+                            tracing::debug!(
+                                ?tx_bytes,
+                                bin_resource_left,
+                                proposal_height =
+                                    ?pos_queries.get_current_decision_height(),
+                                "Dropping encrypted tx from the current proposal",
+                            );
+                            rejected = Some(status);
+                            break;
+                        }
+                        AllocFailure::OverflowsBin { bin_resource } => {
+                            // `ProcessProposal` would reject a tx that
+                            // doesn't fit in its bin, so it's left out
+                            // of the proposal
+                            tracing::warn!(
+                                ?tx_bytes,
+                                bin_resource,
+                                proposal_height =
+                                    ?pos_queries.get_current_decision_height(),
+                                "Dropping large encrypted tx from the current \
+                                 proposal",
+                            );
+                        }
                     }
                 }
-            })
-            .take_while(|(tx_bytes, tx_gas)| {
-                alloc.try_alloc(BlockResources::new(&tx_bytes[..], tx_gas.to_owned()))
-                    .map_or_else(
-                        |status| match status {
-                            AllocFailure::Rejected { bin_resource_left} => {
-                                tracing::debug!(
-                                    ?tx_bytes,
-                                    bin_resource_left,
-                                    proposal_height =
-                                        ?pos_queries.get_current_decision_height(),
-                                    "Dropping encrypted tx from the current proposal",
-                                );
-                                false
-                            }
-                            AllocFailure::OverflowsBin { bin_resource} => {
-                                // TODO: handle tx whose size is greater
-                                // than bin size
-                                tracing::warn!(
-                                    ?tx_bytes,
-                                    bin_resource,
-                                    proposal_height =
-                                        ?pos_queries.get_current_decision_height(),
-                                    "Dropping large encrypted tx from the current proposal",
-                                );
-                                true
-                            }
-                        },
-                        |()| true,
-                    )
-            })
-            .map(|(tx, _)| tx)
-            .collect();
+            }
+        }
+        // The txs left after the bin was filled are dropped as well, without
+        // validating them
+        if let Some(status) = rejected.filter(|_| record_failures) {
+            for tx_bytes in txs {
+                self.record_alloc_failure("encrypted", status, tx_bytes.len());
+            }
+        }
         let alloc = alloc.next_state();
 
-        (txs, alloc)
+        (proposed_txs, alloc)
+    }
+
+    /// Get the priority of a proposed tx. Only wrapper txs are prioritized
+    /// by their fees.
+    fn proposed_tx_priority(&self, tx_bytes: &[u8]) -> i64 {
+        Tx::try_from(tx_bytes)
+            .ok()
+            .and_then(|tx| match tx.header().tx_type {
+                TxType::Wrapper(wrapper) => {
+                    Some(self.wrapper_tx_priority(&wrapper))
+                }
+                _ => None,
+            })
+            .unwrap_or_default()
+    }
+
+    /// Count a tx dropped from the proposal because the block space
+    /// allocator rejected it from the given bin
+    fn record_alloc_failure(
+        &self,
+        bin: &str,
+        failure: AllocFailure,
+        tx_len: usize,
+    ) {
+        let mut alloc_rejections = self.alloc_rejections.lock().unwrap();
+        let counters = match failure {
+            AllocFailure::Rejected { .. } => &mut alloc_rejections.rejected,
+            AllocFailure::OverflowsBin { .. } => {
+                &mut alloc_rejections.overflowed
+            }
+        };
+        *counters.entry(bin.to_string()).or_default() += 1;
+        if let AllocFailure::Rejected { bin_resource_left } = failure {
+            alloc_rejections
+                .bin_resource_left
+                .insert(bin.to_string(), bin_resource_left);
+        }
+        alloc_rejections.bytes_dropped += tx_len as u64;
     }
 
     /// Validity checks on a wrapper tx
@@ -198,7 +284,7 @@ where
     fn validate_wrapper_bytes<CA>(
         &self,
         tx_bytes: &[u8],
-        block_time: Option<DateTimeUtc>,
+        block_time: DateTimeUtc,
         temp_wl_storage: &mut TempWlStorage<D, H>,
         vp_wasm_cache: &mut VpCache<CA>,
         tx_wasm_cache: &mut TxCache<CA>,
@@ -207,41 +293,36 @@ where
     where
         CA: 'static + WasmCacheAccess + Sync,
     {
-        let tx = Tx::try_from(tx_bytes).map_err(|_| ())?;
-
-        // If tx doesn't have an expiration it is valid. If time cannot be
-        // retrieved from block default to last block datetime which has
-        // already been checked by mempool_validate, so it's valid
-        if let (Some(block_time), Some(exp)) =
-            (block_time.as_ref(), &tx.header().expiration)
+        // Drop txs that exceed the max tx bytes parameter before allocating
+        // them, as they could never be included in any block
+        if !validate_tx_bytes(&self.wl_storage, tx_bytes.len())
+            .expect("Failed to get max tx bytes param from storage")
         {
-            if block_time > exp {
-                return Err(());
-            }
+            return Err(());
         }
 
+        let tx = Tx::try_from(tx_bytes).map_err(|_| ())?;
+
         tx.validate_tx().map_err(|_| ())?;
         if let TxType::Wrapper(wrapper) = tx.header().tx_type {
             // Check tx gas limit for tx size
             let mut tx_gas_meter = TxGasMeter::new(wrapper.gas_limit);
             tx_gas_meter.add_wrapper_gas(tx_bytes).map_err(|_| ())?;
 
-            self.replay_protection_checks(&tx, temp_wl_storage)
-                .map_err(|_| ())?;
-
-            // Check fees
-            match self.wrapper_fee_check(
+            // Apply the same checks as `ProcessProposal`
+            self.check_proposed_wrapper(
+                &tx,
                 &wrapper,
-                get_fee_unshielding_transaction(&tx, &wrapper),
+                block_time,
                 temp_wl_storage,
                 vp_wasm_cache,
                 tx_wasm_cache,
-                Some(block_proposer),
+                block_proposer,
                 true,
-            ) {
-                Ok(()) => Ok(u64::from(wrapper.gas_limit)),
-                Err(_) => Err(()),
-            }
+            )
+            .map_err(|_| ())?;
+
+            Ok(u64::from(wrapper.gas_limit))
         } else {
             Err(())
         }
@@ -260,26 +341,22 @@ where
         mut alloc: BlockAllocator<BuildingDecryptedTxBatch>,
     ) -> (Vec<TxBytes>, BlockAllocator<BuildingProtocolTxBatch>) {
         let pos_queries = self.wl_storage.pos_queries();
-        let txs = self
+        let mut rejected = None;
+        let mut queued_txs = self
             .wl_storage
             .storage
             .tx_queue
             .iter()
-            .map(
-                |TxInQueue {
-                     tx,
-                     gas: _,
-                }| {
-                    let mut tx = tx.clone();
-                    tx.update_header(TxType::Decrypted(DecryptedTx::Decrypted));
-                    tx.to_bytes().into()
-                },
-            )
+            .map(|queued| TxBytes::from(decrypted_tx(queued).to_bytes()));
+        let txs = queued_txs
+            .by_ref()
             // TODO: make sure all decrypted txs are accepted
             .take_while(|tx_bytes: &TxBytes| {
                 alloc.try_alloc(&tx_bytes[..]).map_or_else(
                     |status| match status {
                         AllocFailure::Rejected { bin_resource_left: bin_space_left } => {
+                            self.record_alloc_failure("decrypted", status, tx_bytes.len());
+                            rejected = Some(status);
                             tracing::warn!(
                                 ?tx_bytes,
                                 bin_space_left,
@@ -290,12 +367,16 @@ where
                             false
                         }
                         AllocFailure::OverflowsBin { bin_resource: bin_size } => {
+                            // The tx is still included in the proposal, and
+                            // accepted by `ProcessProposal`, as the txs of
+                            // the queue can't be skipped. So it's not counted
+                            // as dropped
                             tracing::warn!(
                                 ?tx_bytes,
                                 bin_size,
                                 proposal_height =
                                     ?pos_queries.get_current_decision_height(),
-                                "Dropping large decrypted tx from the current proposal",
+                                "Proposing decrypted tx larger than its bin",
                             );
                             true
                         }
@@ -304,6 +385,12 @@ where
                 )
             })
             .collect();
+        // The txs left after the bin was filled are dropped as well
+        if let Some(status) = rejected {
+            for tx_bytes in queued_txs {
+                self.record_alloc_failure("decrypted", status, tx_bytes.len());
+            }
+        }
         let alloc = alloc.next_state();
 
         (txs, alloc)
@@ -324,14 +411,22 @@ where
             return vec![];
         }
 
-        let deserialized_iter = self.deserialize_vote_extensions(txs);
+        let mut deserialized_iter = self.deserialize_vote_extensions(txs);
         let pos_queries = self.wl_storage.pos_queries();
 
-        deserialized_iter.take_while(|tx_bytes|
-            alloc.try_alloc(&tx_bytes[..])
-                .map_or_else(
-                    |status| match status {
-                        AllocFailure::Rejected { bin_resource_left} => {
+        let mut rejected = None;
+        let mut txs = vec![];
+        for tx_bytes in deserialized_iter.by_ref() {
+            match alloc.try_alloc(&tx_bytes[..]) {
+                Ok(()) => txs.push(tx_bytes),
+                Err(status) => {
+                    self.record_alloc_failure(
+                        "protocol",
+                        status,
+                        tx_bytes.len(),
+                    );
+                    match status {
+                        AllocFailure::Rejected { bin_resource_left } => {
                             // TODO: maybe we should find a way to include
                             // validator set updates all the time. for instance,
                             // we could have recursive bins -> bin space within
@@ -347,11 +442,13 @@ where
                                     ?pos_queries.get_current_decision_height(),
                                 "Dropping protocol tx from the current proposal",
                             );
-                            false
+                            rejected = Some(status);
+                            break;
                         }
-                        AllocFailure::OverflowsBin { bin_resource} => {
-                            // TODO: handle tx whose size is greater
-                            // than bin size
+                        AllocFailure::OverflowsBin { bin_resource } => {
+                            // `ProcessProposal` would reject a tx that
+                            // doesn't fit in its bin, so it's left out
+                            // of the proposal
                             tracing::warn!(
                                 ?tx_bytes,
                                 bin_resource,
@@ -359,13 +456,18 @@ where
                                     ?pos_queries.get_current_decision_height(),
                                 "Dropping large protocol tx from the current proposal",
                             );
-                            true
                         }
-                    },
-                    |()| true,
-                )
-        )
-        .collect()
+                    }
+                }
+            }
+        }
+        // The txs left after the bin was filled are dropped as well
+        if let Some(status) = rejected {
+            for tx_bytes in deserialized_iter {
+                self.record_alloc_failure("protocol", status, tx_bytes.len());
+            }
+        }
+        txs
     }
 }
 
@@ -373,7 +475,7 @@ where
 // TODO: write tests for validator set update vote extensions in
 // prepare proposals
 mod test_prepare_proposal {
-    use std::collections::BTreeSet;
+    use std::collections::{BTreeMap, BTreeSet};
 
     use borsh_ext::BorshSerializeExt;
     use namada::core::ledger::storage_api::collections::lazy_map::{
@@ -391,6 +493,7 @@ mod test_prepare_proposal {
     use namada::proof_of_stake::Epoch;
     use namada::proto::{Code, Data, Header, Section, Signature, Signed};
     use namada::types::address::{self, Address};
+    use namada::types::chain::{ChainId, ProposalBytes};
     use namada::types::ethereum_events::EthereumEvent;
     use namada::types::key::RefTo;
     use namada::types::storage::{BlockHeight, InnerEthEventsQueue};
@@ -399,13 +502,13 @@ mod test_prepare_proposal {
     use namada::types::transaction::protocol::{
         ethereum_tx_data_variants, EthereumTxData,
     };
-    use namada::types::transaction::{Fee, TxType, WrapperTx};
+    use namada::types::transaction::{DecryptedTx, Fee, TxType, WrapperTx};
     use namada::types::vote_extensions::ethereum_events;
 
     use super::*;
     use crate::config::ValidatorLocalConfig;
     use crate::node::ledger::shell::test_utils::{
-        self, gen_keypair, get_pkh_from_address, TestShell,
+        self, gen_keypair, get_pkh_from_address, ProcessProposal, TestShell,
     };
     use crate::node::ledger::shims::abcipp_shim_types::shim::request::FinalizeBlock;
     use crate::wallet;
@@ -442,6 +545,106 @@ mod test_prepare_proposal {
         assert!(shell.prepare_proposal(req).txs.is_empty());
     }
 
+    /// Test that the txs dropped by the block space allocator are counted
+    /// per bin, and that the proposals from which some txs were dropped are
+    /// counted as constrained
+    #[test]
+    fn test_alloc_rejections() {
+        let (mut shell, _recv, _, _) = test_utils::setup();
+        let keypair = gen_keypair();
+
+        // Load some tokens to tx signer to pay fees
+        let balance_key = token::balance_key(
+            &shell.wl_storage.storage.native_token,
+            &Address::from(&keypair.ref_to()),
+        );
+        shell
+            .wl_storage
+            .storage
+            .write(&balance_key, Amount::native_whole(1_000).serialize_to_vec())
+            .unwrap();
+
+        let wrapper = |amount_per_gas_unit: u64, data_len: usize| {
+            let mut tx =
+                Tx::from_type(TxType::Wrapper(Box::new(WrapperTx::new(
+                    Fee {
+                        amount_per_gas_unit: DenominatedAmount::native(
+                            amount_per_gas_unit.into(),
+                        ),
+                        token: shell.wl_storage.storage.native_token.clone(),
+                    },
+                    keypair.ref_to(),
+                    Epoch(0),
+                    // Enough gas for the size of the large tx
+                    (10 * GAS_LIMIT_MULTIPLIER).into(),
+                    None,
+                ))));
+            tx.header.chain_id = shell.chain_id.clone();
+            tx.set_code(Code::new("wasm_code".as_bytes().to_owned(), None));
+            tx.set_data(Data::new(vec![0; data_len]));
+            tx.add_section(Section::Signature(Signature::new(
+                tx.sechashes(),
+                [(0, keypair.clone())].into_iter().collect(),
+                None,
+            )));
+            tx.to_bytes()
+        };
+        // A tx larger than the bin of encrypted txs, with the highest
+        // priority, followed by txs of which only two fit in the bin
+        let tx_len = wrapper(1, 16).len() as u64;
+        let txs = vec![
+            wrapper(5, 16 + 3 * tx_len as usize),
+            wrapper(4, 16),
+            wrapper(3, 16),
+            wrapper(2, 16),
+            wrapper(1, 16),
+        ];
+        let large_tx_len = txs[0].len() as u64;
+
+        // Encrypted txs get a third of the proposal space
+        let bin_len = 2 * tx_len + tx_len / 2;
+        shell
+            .wl_storage
+            .storage
+            .write(
+                &namada::ledger::parameters::storage::get_max_proposal_bytes_key(
+                ),
+                ProposalBytes::new(3 * bin_len).unwrap().serialize_to_vec(),
+            )
+            .unwrap();
+
+        let req = RequestPrepareProposal {
+            txs: txs.into_iter().map(Into::into).collect(),
+            ..Default::default()
+        };
+        assert_eq!(shell.prepare_proposal(req).txs.len(), 2);
+
+        // The txs after the one that didn't fit are dropped as well
+        let alloc_rejections = shell.alloc_rejections.lock().unwrap().clone();
+        assert_eq!(alloc_rejections.proposals, 1);
+        assert_eq!(alloc_rejections.constrained_proposals, 1);
+        assert_eq!(
+            alloc_rejections.rejected,
+            BTreeMap::from([("encrypted".to_string(), 2)])
+        );
+        assert_eq!(
+            alloc_rejections.overflowed,
+            BTreeMap::from([("encrypted".to_string(), 1)])
+        );
+        assert_eq!(
+            alloc_rejections.bin_resource_left,
+            BTreeMap::from([("encrypted".to_string(), bin_len - 2 * tx_len)])
+        );
+        assert_eq!(alloc_rejections.bytes_dropped, large_tx_len + 2 * tx_len);
+        assert_eq!(alloc_rejections.dropped_txs(), 3);
+
+        // A proposal with no dropped txs isn't constrained
+        shell.prepare_proposal(RequestPrepareProposal::default());
+        let alloc_rejections = shell.alloc_rejections.lock().unwrap().clone();
+        assert_eq!(alloc_rejections.proposals, 2);
+        assert_eq!(alloc_rejections.constrained_proposals, 1);
+    }
+
     /// Test that if an error is encountered while
     /// trying to process a tx from the mempool,
     /// we simply exclude it from the proposal
@@ -814,6 +1017,184 @@ mod test_prepare_proposal {
         );
     }
 
+    /// Test that a decrypted tx larger than its bin is proposed and that the
+    /// proposal is accepted by `ProcessProposal`
+    #[test]
+    fn test_oversized_decrypted_tx_round_trip() {
+        let (mut shell, _recv, _, _) = test_utils::setup();
+        let keypair = gen_keypair();
+
+        // Load some tokens to tx signer to pay fees
+        let balance_key = token::balance_key(
+            &shell.wl_storage.storage.native_token,
+            &Address::from(&keypair.ref_to()),
+        );
+        shell
+            .wl_storage
+            .storage
+            .write(&balance_key, Amount::native_whole(1_000).serialize_to_vec())
+            .unwrap();
+
+        let max_block_gas =
+            namada::core::ledger::gas::get_max_block_gas(&shell.wl_storage)
+                .unwrap();
+        let wrapper = |amount_per_gas_unit: u64, gas_limit: u64, data_len| {
+            let mut tx =
+                Tx::from_type(TxType::Wrapper(Box::new(WrapperTx::new(
+                    Fee {
+                        amount_per_gas_unit: DenominatedAmount::native(
+                            amount_per_gas_unit.into(),
+                        ),
+                        token: shell.wl_storage.storage.native_token.clone(),
+                    },
+                    keypair.ref_to(),
+                    Epoch(0),
+                    gas_limit.into(),
+                    None,
+                ))));
+            tx.header.chain_id = shell.chain_id.clone();
+            tx.set_code(Code::new("wasm_code".as_bytes().to_owned(), None));
+            tx.set_data(Data::new(vec![0; data_len]));
+            tx.add_section(Section::Signature(Signature::new(
+                tx.sechashes(),
+                [(0, keypair.clone())].into_iter().collect(),
+                None,
+            )));
+            tx
+        };
+        // Two wrappers that fit in the bin of encrypted txs, but not in the
+        // block gas together. The second one is dropped from the proposal,
+        // but the space it took in the allocator is no longer available to
+        // the decrypted txs
+        let gas_limit = max_block_gas / 2 + 1;
+        let first = wrapper(2, gas_limit, 16).to_bytes();
+        let second = wrapper(1, gas_limit, 16).to_bytes();
+        let tx_len = first.len() as u64;
+        assert_eq!(second.len() as u64, tx_len);
+        let max_proposal_bytes = 9 * tx_len;
+
+        // A decrypted tx larger than the space left to decrypted txs, but
+        // that fits in the proposal
+        let base_len = {
+            let mut tx = wrapper(1, GAS_LIMIT_MULTIPLIER, 0);
+            tx.update_header(TxType::Decrypted(DecryptedTx::Decrypted));
+            tx.to_bytes().len() as u64
+        };
+        let data_len = 7 * tx_len + tx_len / 2 - base_len;
+        let mut tx = wrapper(1, GAS_LIMIT_MULTIPLIER, data_len as usize);
+        shell.enqueue_tx(tx.clone(), GAS_LIMIT_MULTIPLIER.into());
+        tx.update_header(TxType::Decrypted(DecryptedTx::Decrypted));
+        let decrypted_tx = tx.to_bytes();
+        shell
+            .wl_storage
+            .storage
+            .write(
+                &namada::ledger::parameters::storage::get_max_proposal_bytes_key(
+                ),
+                ProposalBytes::new(max_proposal_bytes)
+                    .unwrap()
+                    .serialize_to_vec(),
+            )
+            .unwrap();
+        let decrypted_len = decrypted_tx.len() as u64;
+        assert!(decrypted_len > max_proposal_bytes - 2 * tx_len);
+        assert!(decrypted_len <= max_proposal_bytes - tx_len);
+
+        let req = RequestPrepareProposal {
+            txs: vec![first.clone().into(), second.into()],
+            ..Default::default()
+        };
+        let proposal: Vec<Vec<u8>> = shell
+            .prepare_proposal(req)
+            .txs
+            .into_iter()
+            .map(|tx_bytes| tx_bytes.to_vec())
+            .collect();
+        assert_eq!(proposal, vec![first, decrypted_tx]);
+
+        let processed = shell
+            .process_proposal(ProcessProposal { txs: proposal })
+            .expect("The proposal should be accepted");
+        for tx in processed {
+            assert_eq!(tx.result.code, u32::from(ResultCode::Ok));
+        }
+    }
+
+    /// Test that the wrapper txs paying higher fees per gas unit are proposed
+    /// first
+    #[test]
+    fn test_wrapper_txs_ordered_by_priority() {
+        let (mut shell, _recv, _, _) = test_utils::setup();
+        let keypair = gen_keypair();
+
+        // Load some tokens to tx signer to pay fees
+        let balance_key = token::balance_key(
+            &shell.wl_storage.storage.native_token,
+            &Address::from(&keypair.ref_to()),
+        );
+        shell
+            .wl_storage
+            .storage
+            .write(&balance_key, Amount::native_whole(1_000).serialize_to_vec())
+            .unwrap();
+
+        let wrappers: Vec<Tx> = [1, 2]
+            .into_iter()
+            .map(|amount_per_gas_unit: u64| {
+                let mut tx =
+                    Tx::from_type(TxType::Wrapper(Box::new(WrapperTx::new(
+                        Fee {
+                            amount_per_gas_unit: DenominatedAmount::native(
+                                amount_per_gas_unit.into(),
+                            ),
+                            token: shell
+                                .wl_storage
+                                .storage
+                                .native_token
+                                .clone(),
+                        },
+                        keypair.ref_to(),
+                        Epoch(0),
+                        GAS_LIMIT_MULTIPLIER.into(),
+                        None,
+                    ))));
+                tx.header.chain_id = shell.chain_id.clone();
+                tx.set_code(Code::new("wasm_code".as_bytes().to_owned(), None));
+                tx.set_data(Data::new(
+                    "transaction data".as_bytes().to_owned(),
+                ));
+                tx.add_section(Section::Signature(Signature::new(
+                    tx.sechashes(),
+                    [(0, keypair.clone())].into_iter().collect(),
+                    None,
+                )));
+                tx
+            })
+            .collect();
+
+        let req = RequestPrepareProposal {
+            txs: wrappers.iter().map(|tx| tx.to_bytes().into()).collect(),
+            ..Default::default()
+        };
+        let received: Vec<Vec<u8>> = shell
+            .prepare_proposal(req)
+            .txs
+            .into_iter()
+            .map(|tx_bytes| {
+                Tx::try_from(tx_bytes.as_ref())
+                    .expect("Test failed")
+                    .header
+                    .serialize_to_vec()
+            })
+            .collect();
+        let expected: Vec<Vec<u8>> = wrappers
+            .into_iter()
+            .rev()
+            .map(|tx| tx.header.serialize_to_vec())
+            .collect();
+        assert_eq!(received, expected);
+    }
+
     /// Test that if the unsigned wrapper tx hash is known (replay attack), the
     /// transaction is not included in the block
     #[test]
@@ -1040,6 +1421,43 @@ mod test_prepare_proposal {
         assert_eq!(result.txs.len(), 0);
     }
 
+    /// Test that a wrapper tx with a wrong chain id, which would be rejected
+    /// by `ProcessProposal`, is not included in the proposal
+    #[test]
+    fn test_wrong_chain_id_wrapper_tx() {
+        let (shell, _recv, _, _) = test_utils::setup();
+        let keypair = gen_keypair();
+        let mut wrapper_tx =
+            Tx::from_type(TxType::Wrapper(Box::new(WrapperTx::new(
+                Fee {
+                    amount_per_gas_unit: DenominatedAmount::native(1.into()),
+                    token: shell.wl_storage.storage.native_token.clone(),
+                },
+                keypair.ref_to(),
+                Epoch(0),
+                GAS_LIMIT_MULTIPLIER.into(),
+                None,
+            ))));
+        wrapper_tx.header.chain_id = ChainId("wrong_chain_id".to_string());
+        wrapper_tx.set_code(Code::new("wasm_code".as_bytes().to_owned(), None));
+        wrapper_tx
+            .set_data(Data::new("transaction data".as_bytes().to_owned()));
+        wrapper_tx.add_section(Section::Signature(Signature::new(
+            wrapper_tx.sechashes(),
+            [(0, keypair)].into_iter().collect(),
+            None,
+        )));
+
+        let req = RequestPrepareProposal {
+            txs: vec![wrapper_tx.to_bytes().into()],
+            max_tx_bytes: 0,
+            time: None,
+            ..Default::default()
+        };
+        let result = shell.prepare_proposal(req);
+        assert_eq!(result.txs.len(), 0);
+    }
+
     /// Check that a tx requiring more gas than the block limit is not included
     /// in the block
     #[test]
@@ -1123,6 +1541,84 @@ mod test_prepare_proposal {
         assert!(result.txs.is_empty());
     }
 
+    // Check that a wrapper exceeding the max tx bytes parameter is not included
+    // in the block, while a wrapper of exactly the max tx bytes is
+    #[test]
+    fn test_exceeding_max_tx_bytes_wrapper() {
+        let (mut shell, _recv, _, _) = test_utils::setup();
+        let keypair = gen_keypair();
+
+        // Load some tokens to tx signer to pay fees
+        let balance_key = token::balance_key(
+            &shell.wl_storage.storage.native_token,
+            &Address::from(&keypair.ref_to()),
+        );
+        shell
+            .wl_storage
+            .storage
+            .write(&balance_key, Amount::native_whole(1_000).serialize_to_vec())
+            .unwrap();
+
+        let wrapper = |data_len: usize| {
+            let mut wrapper_tx =
+                Tx::from_type(TxType::Wrapper(Box::new(WrapperTx::new(
+                    Fee {
+                        amount_per_gas_unit: DenominatedAmount::native(
+                            1.into(),
+                        ),
+                        token: shell.wl_storage.storage.native_token.clone(),
+                    },
+                    keypair.ref_to(),
+                    Epoch(0),
+                    // Enough gas for the size of the tx
+                    (10 * GAS_LIMIT_MULTIPLIER).into(),
+                    None,
+                ))));
+            wrapper_tx.header.chain_id = shell.chain_id.clone();
+            wrapper_tx
+                .set_code(Code::new("wasm_code".as_bytes().to_owned(), None));
+            wrapper_tx.set_data(Data::new(vec![0; data_len]));
+            wrapper_tx.add_section(Section::Signature(Signature::new(
+                wrapper_tx.sechashes(),
+                [(0, keypair.clone())].into_iter().collect(),
+                None,
+            )));
+            wrapper_tx.to_bytes()
+        };
+
+        // Lower the max tx bytes parameter, so that a tx of its size stays
+        // within the gas limits
+        let max_tx_bytes = wrapper(0).len() as u32 + 1000;
+        shell
+            .wl_storage
+            .storage
+            .write(
+                &namada::ledger::parameters::storage::get_max_tx_bytes_key(),
+                max_tx_bytes.serialize_to_vec(),
+            )
+            .unwrap();
+
+        let just_under = wrapper(1000);
+        assert_eq!(just_under.len(), max_tx_bytes as usize);
+        let req = RequestPrepareProposal {
+            txs: vec![just_under.clone().into()],
+            max_tx_bytes: 0,
+            time: None,
+            ..Default::default()
+        };
+        let result = shell.prepare_proposal(req);
+        assert_eq!(result.txs, vec![TxBytes::from(just_under)]);
+
+        let req = RequestPrepareProposal {
+            txs: vec![wrapper(1001).into()],
+            max_tx_bytes: 0,
+            time: None,
+            ..Default::default()
+        };
+        let result = shell.prepare_proposal(req);
+        assert!(result.txs.is_empty());
+    }
+
     // Check that a wrapper using a token not accepted byt the validator for fee
     // payment is not included in the block
     #[test]
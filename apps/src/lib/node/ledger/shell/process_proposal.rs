@@ -3,11 +3,10 @@
 
 use data_encoding::HEXUPPER;
 use namada::core::hints;
-use namada::core::ledger::storage::WlStorage;
 use namada::ledger::pos::PosQueries;
 use namada::ledger::protocol::get_fee_unshielding_transaction;
 use namada::ledger::storage::TempWlStorage;
-use namada::ledger::storage_api::tx::validate_tx_bytes;
+use namada::ledger::storage_api::tx::{is_tx_whitelisted, validate_tx_bytes};
 use namada::proof_of_stake::storage::find_validator_by_raw_hash;
 use namada::types::internal::TxInQueue;
 use namada::types::transaction::protocol::{
@@ -15,7 +14,10 @@ use namada::types::transaction::protocol::{
 };
 use namada_sdk::eth_bridge::{EthBridgeQueries, SendValsetUpd};
 
-use super::block_alloc::{BlockSpace, EncryptedTxsBins};
+use super::block_alloc::states::{
+    ProposalAllocFailure, ProposalAllocator, ProposedTx,
+};
+use super::block_alloc::{BlockResources, BlockSpace};
 use super::*;
 use crate::facade::tendermint_proto::v0_37::abci::RequestProcessProposal;
 use crate::node::ledger::shell::block_alloc::{AllocFailure, TxBin};
@@ -24,11 +26,10 @@ use crate::node::ledger::shims::abcipp_shim_types::shim::TxBytes;
 
 /// Validation metadata, to keep track of used resources or
 /// transaction numbers, in a block proposal.
-#[derive(Default)]
 pub struct ValidationMeta {
-    /// Space and gas utilized by encrypted txs.
-    pub encrypted_txs_bins: EncryptedTxsBins,
-    /// Vote extension digest counters.
+    /// The block allocator that `PrepareProposal` starts from, driven over
+    /// the proposed txs to check their order and the resources they use.
+    pub alloc: ProposalAllocator,
     /// Space utilized by all txs.
     pub txs_bin: TxBin<BlockSpace>,
     /// Check if the decrypted tx queue has any elements
@@ -37,30 +38,6 @@ pub struct ValidationMeta {
     /// This field will only evaluate to true if a block
     /// proposer didn't include all decrypted txs in a block.
     pub decrypted_queue_has_remaining_txs: bool,
-    /// Check if a block has decrypted txs.
-    pub has_decrypted_txs: bool,
-}
-
-impl<D, H> From<&WlStorage<D, H>> for ValidationMeta
-where
-    D: 'static + DB + for<'iter> DBIter<'iter>,
-    H: 'static + StorageHasher,
-{
-    fn from(wl_storage: &WlStorage<D, H>) -> Self {
-        let max_proposal_bytes =
-            wl_storage.pos_queries().get_max_proposal_bytes().get();
-        let max_block_gas =
-            namada::core::ledger::gas::get_max_block_gas(wl_storage).unwrap();
-        let encrypted_txs_bin =
-            EncryptedTxsBins::new(max_proposal_bytes, max_block_gas);
-        let txs_bin = TxBin::init(max_proposal_bytes);
-        Self {
-            decrypted_queue_has_remaining_txs: false,
-            has_decrypted_txs: false,
-            encrypted_txs_bins: encrypted_txs_bin,
-            txs_bin,
-        }
-    }
 }
 
 impl<D, H> Shell<D, H>
@@ -165,7 +142,13 @@ where
     ) -> (Vec<TxResult>, ValidationMeta) {
         let mut tx_queue_iter = self.wl_storage.storage.tx_queue.iter();
         let mut temp_wl_storage = TempWlStorage::new(&self.wl_storage.storage);
-        let mut metadata = ValidationMeta::from(&self.wl_storage);
+        let mut metadata = ValidationMeta {
+            alloc: self.get_encrypted_txs_allocator().into(),
+            txs_bin: TxBin::init(
+                self.wl_storage.pos_queries().get_max_proposal_bytes().get(),
+            ),
+            decrypted_queue_has_remaining_txs: false,
+        };
         let mut vp_wasm_cache = self.vp_wasm_cache.clone();
         let mut tx_wasm_cache = self.tx_wasm_cache.clone();
 
@@ -344,6 +327,12 @@ where
                     .into(),
             },
             TxType::Protocol(protocol_tx) => {
+                if let Err(e) =
+                    metadata.alloc.try_alloc(ProposedTx::Protocol(tx_bytes))
+                {
+                    return alloc_failure_result(e);
+                }
+
                 // Tx chain id
                 if tx_chain_id != self.chain_id {
                     return TxResult {
@@ -522,7 +511,18 @@ where
                 }
             }
             TxType::Decrypted(tx_header) => {
-                metadata.has_decrypted_txs = true;
+                match metadata.alloc.try_alloc(ProposedTx::Decrypted(tx_bytes))
+                {
+                    // Like in `PrepareProposal`, a decrypted tx larger than
+                    // its bin is still accepted, as the txs of the queue
+                    // can't be skipped
+                    Ok(())
+                    | Err(ProposalAllocFailure::Alloc {
+                        failure: AllocFailure::OverflowsBin { .. },
+                        ..
+                    }) => {}
+                    Err(e) => return alloc_failure_result(e),
+                }
                 match tx_queue_iter.next() {
                     Some(wrapper) => {
                         if wrapper.tx.raw_header_hash() != tx.raw_header_hash()
@@ -545,6 +545,14 @@ where
                                        incorrectly marked as un-decryptable"
                                     .into(),
                             }
+                        } else if tx_bytes != decrypted_tx(wrapper).to_bytes() {
+                            TxResult {
+                                code: ResultCode::InvalidTx.into(),
+                                info: "Process proposal rejected a decrypted \
+                                       transaction that doesn't match its \
+                                       wrapper transaction in the queue"
+                                    .into(),
+                            }
                         } else {
                             match tx.header().expiration {
                                 Some(tx_expiration)
@@ -582,13 +590,15 @@ where
                 // incentivize the proposer to include only
                 // valid transaction and avoid wasting block
                 // resources (ABCI only)
+                let resources =
+                    BlockResources::new(tx_bytes, u64::from(wrapper.gas_limit));
                 let mut tx_gas_meter = TxGasMeter::new(wrapper.gas_limit);
                 if tx_gas_meter.add_wrapper_gas(tx_bytes).is_err() {
                     // Account for the tx's resources even in case of an error.
                     // Ignore any allocation error
                     let _ = metadata
-                        .encrypted_txs_bins
-                        .try_dump(tx_bytes, u64::from(wrapper.gas_limit));
+                        .alloc
+                        .try_alloc(ProposedTx::Encrypted(resources));
 
                     return TxResult {
                         code: ResultCode::TxGasLimit.into(),
@@ -596,26 +606,6 @@ where
                             .to_string(),
                     };
                 }
-
-                // try to allocate space and gas for this encrypted tx
-                if let Err(e) = metadata
-                    .encrypted_txs_bins
-                    .try_dump(tx_bytes, u64::from(wrapper.gas_limit))
-                {
-                    return TxResult {
-                        code: ResultCode::AllocationError.into(),
-                        info: e,
-                    };
-                }
-                // decrypted txs shouldn't show up before wrapper txs
-                if metadata.has_decrypted_txs {
-                    return TxResult {
-                        code: ResultCode::InvalidTx.into(),
-                        info: "Decrypted txs should not be proposed before \
-                               wrapper txs"
-                            .into(),
-                    };
-                }
                 if hints::unlikely(self.encrypted_txs_not_allowed()) {
                     return TxResult {
                         code: ResultCode::AllocationError.into(),
@@ -625,49 +615,22 @@ where
                     };
                 }
 
-                // ChainId check
-                if tx_chain_id != self.chain_id {
-                    return TxResult {
-                        code: ResultCode::InvalidChainId.into(),
-                        info: format!(
-                            "Tx carries a wrong chain id: expected {}, found \
-                             {}",
-                            self.chain_id, tx_chain_id
-                        ),
-                    };
-                }
-
-                // Tx expiration
-                if let Some(exp) = tx_expiration {
-                    if block_time > exp {
-                        return TxResult {
-                            code: ResultCode::ExpiredTx.into(),
-                            info: format!(
-                                "Tx expired at {:#?}, block time: {:#?}",
-                                exp, block_time
-                            ),
-                        };
-                    }
-                }
-
-                // Replay protection checks
+                // try to allocate space and gas for this encrypted tx, in
+                // the same bins as `PrepareProposal`
                 if let Err(e) =
-                    self.replay_protection_checks(&tx, temp_wl_storage)
+                    metadata.alloc.try_alloc(ProposedTx::Encrypted(resources))
                 {
-                    return TxResult {
-                        code: ResultCode::ReplayTx.into(),
-                        info: e.to_string(),
-                    };
+                    return alloc_failure_result(e);
                 }
 
-                // Check that the fee payer has sufficient balance.
-                match self.wrapper_fee_check(
+                match self.check_proposed_wrapper(
+                    &tx,
                     &wrapper,
-                    get_fee_unshielding_transaction(&tx, &wrapper),
+                    block_time,
                     temp_wl_storage,
                     vp_wasm_cache,
                     tx_wasm_cache,
-                    Some(block_proposer),
+                    block_proposer,
                     false,
                 ) {
                     Ok(()) => TxResult {
@@ -675,15 +638,94 @@ where
                         info: "Process proposal accepted this transaction"
                             .into(),
                     },
-                    Err(e) => TxResult {
-                        code: ResultCode::FeeError.into(),
-                        info: e.to_string(),
-                    },
+                    Err(tx_result) => tx_result,
                 }
             }
         }
     }
 
+    /// Checks the validity rules of a wrapper tx which are shared by
+    /// [`Shell::prepare_proposal`] and [`Shell::process_proposal`]: chain id,
+    /// expiration, tx whitelist, replay protection and fees. Using the same
+    /// checks on both sides guarantees that an honest proposer never
+    /// includes a wrapper tx that honest validators would reject.
+    ///
+    /// The allocation of block resources is checked by both sides with the
+    /// same [`BlockAllocator`](super::block_alloc::BlockAllocator) state
+    /// machine, and the decrypted txs are built with [`decrypted_tx`].
+    #[allow(clippy::too_many_arguments)]
+    pub(super) fn check_proposed_wrapper<CA>(
+        &self,
+        tx: &Tx,
+        wrapper: &WrapperTx,
+        block_time: DateTimeUtc,
+        temp_wl_storage: &mut TempWlStorage<D, H>,
+        vp_wasm_cache: &mut VpCache<CA>,
+        tx_wasm_cache: &mut TxCache<CA>,
+        block_proposer: &Address,
+        is_prepare_proposal: bool,
+    ) -> std::result::Result<(), TxResult>
+    where
+        CA: 'static + WasmCacheAccess + Sync,
+    {
+        // ChainId check
+        if tx.header.chain_id != self.chain_id {
+            return Err(TxResult {
+                code: ResultCode::InvalidChainId.into(),
+                info: format!(
+                    "Tx carries a wrong chain id: expected {}, found {}",
+                    self.chain_id, tx.header.chain_id
+                ),
+            });
+        }
+
+        // Tx expiration
+        if let Some(exp) = tx.header.expiration {
+            if block_time > exp {
+                return Err(TxResult {
+                    code: ResultCode::ExpiredTx.into(),
+                    info: format!(
+                        "Tx expired at {:#?}, block time: {:#?}",
+                        exp, block_time
+                    ),
+                });
+            }
+        }
+
+        // Tx whitelist
+        if !is_tx_whitelisted(temp_wl_storage, tx)
+            .expect("Failed to read the tx whitelist param from storage")
+        {
+            return Err(TxResult {
+                code: ResultCode::TxNotWhitelisted.into(),
+                info: "Tx code is not whitelisted".into(),
+            });
+        }
+
+        // Replay protection checks
+        if let Err(e) = self.replay_protection_checks(tx, temp_wl_storage) {
+            return Err(TxResult {
+                code: ResultCode::ReplayTx.into(),
+                info: e.to_string(),
+            });
+        }
+
+        // Check that the fee payer has sufficient balance.
+        self.wrapper_fee_check(
+            wrapper,
+            get_fee_unshielding_transaction(tx, wrapper),
+            temp_wl_storage,
+            vp_wasm_cache,
+            tx_wasm_cache,
+            Some(block_proposer),
+            is_prepare_proposal,
+        )
+        .map_err(|e| TxResult {
+            code: ResultCode::FeeError.into(),
+            info: e.to_string(),
+        })
+    }
+
     pub fn revert_proposal(
         &mut self,
         _req: shim::request::RevertProposal,
@@ -701,6 +743,44 @@ where
     }
 }
 
+/// Build the decrypted tx of a wrapper tx in the queue. `PrepareProposal`
+/// proposes these txs and `ProcessProposal` only accepts the exact same
+/// bytes.
+pub(super) fn decrypted_tx(TxInQueue { tx, .. }: &TxInQueue) -> Tx {
+    let mut tx = tx.clone();
+    tx.update_header(TxType::Decrypted(DecryptedTx::Decrypted));
+    tx
+}
+
+/// The [`TxResult`] of a tx that couldn't be allocated in the proposal
+fn alloc_failure_result(failure: ProposalAllocFailure) -> TxResult {
+    match failure {
+        ProposalAllocFailure::OutOfOrder { kind, after } => TxResult {
+            code: ResultCode::InvalidTx.into(),
+            info: format!(
+                "The {kind} txs should not be proposed after the {after} txs"
+            ),
+        },
+        ProposalAllocFailure::Alloc {
+            kind,
+            failure: AllocFailure::Rejected { .. },
+        } => TxResult {
+            code: ResultCode::AllocationError.into(),
+            info: format!("No more resources left in the block for {kind} txs"),
+        },
+        ProposalAllocFailure::Alloc {
+            kind,
+            failure: AllocFailure::OverflowsBin { .. },
+        } => TxResult {
+            code: ResultCode::AllocationError.into(),
+            info: format!(
+                "The given {kind} tx requires more resources than allotted to \
+                 {kind} txs"
+            ),
+        },
+    }
+}
+
 /// We test the failure cases of [`process_proposal`]. The happy flows
 /// are covered by the e2e tests.
 #[cfg(test)]
@@ -1255,6 +1335,107 @@ mod test_process_proposal {
         );
     }
 
+    /// Test that a decrypted tx that doesn't match the bytes built by
+    /// `PrepareProposal` from the tx queue is rejected by [`process_proposal`]
+    #[test]
+    fn test_decrypted_tx_mismatching_queue() {
+        let (mut shell, _recv, _, _) = test_utils::setup_at_height(3u64);
+        let keypair = gen_keypair();
+        let mut tx = Tx::from_type(TxType::Wrapper(Box::new(WrapperTx::new(
+            Fee {
+                amount_per_gas_unit: DenominatedAmount::native(
+                    Default::default(),
+                ),
+                token: shell.wl_storage.storage.native_token.clone(),
+            },
+            keypair.ref_to(),
+            Epoch(0),
+            GAS_LIMIT_MULTIPLIER.into(),
+            None,
+        ))));
+        tx.header.chain_id = shell.chain_id.clone();
+        tx.set_code(Code::new("wasm_code".as_bytes().to_owned(), None));
+        tx.set_data(Data::new("transaction data".as_bytes().to_owned()));
+        shell.enqueue_tx(tx.clone(), GAS_LIMIT_MULTIPLIER.into());
+
+        // The header still matches the queue, but an extra section was added
+        tx.update_header(TxType::Decrypted(DecryptedTx::Decrypted));
+        tx.add_section(Section::Data(Data::new(
+            "extra data".as_bytes().to_owned(),
+        )));
+        let request = ProcessProposal {
+            txs: vec![tx.to_bytes()],
+        };
+        let response = if let Err(TestError::RejectProposal(mut resp)) =
+            shell.process_proposal(request)
+        {
+            assert_eq!(resp.len(), 1);
+            resp.remove(0)
+        } else {
+            panic!("Test failed")
+        };
+        assert_eq!(response.result.code, u32::from(ResultCode::InvalidTx));
+        assert_eq!(
+            response.result.info,
+            String::from(
+                "Process proposal rejected a decrypted transaction that \
+                 doesn't match its wrapper transaction in the queue"
+            ),
+        );
+    }
+
+    /// Test that a wrapper tx proposed after the decrypted txs, which
+    /// `PrepareProposal` never does, is rejected by [`process_proposal`]
+    #[test]
+    fn test_wrapper_after_decrypted_txs() {
+        let (mut shell, _recv, _, _) = test_utils::setup_at_height(3u64);
+        let keypair = gen_keypair();
+        let mut wrapper =
+            Tx::from_type(TxType::Wrapper(Box::new(WrapperTx::new(
+                Fee {
+                    amount_per_gas_unit: DenominatedAmount::native(
+                        Default::default(),
+                    ),
+                    token: shell.wl_storage.storage.native_token.clone(),
+                },
+                keypair.ref_to(),
+                Epoch(0),
+                GAS_LIMIT_MULTIPLIER.into(),
+                None,
+            ))));
+        wrapper.header.chain_id = shell.chain_id.clone();
+        wrapper.set_code(Code::new("wasm_code".as_bytes().to_owned(), None));
+        wrapper.set_data(Data::new("transaction data".as_bytes().to_owned()));
+        wrapper.add_section(Section::Signature(Signature::new(
+            wrapper.sechashes(),
+            [(0, keypair)].into_iter().collect(),
+            None,
+        )));
+        shell.enqueue_tx(wrapper.clone(), GAS_LIMIT_MULTIPLIER.into());
+        let decrypted =
+            decrypted_tx(shell.wl_storage.storage.tx_queue.get(0).unwrap());
+
+        let request = ProcessProposal {
+            txs: vec![decrypted.to_bytes(), wrapper.to_bytes()],
+        };
+        let response = if let Err(TestError::RejectProposal(mut resp)) =
+            shell.process_proposal(request)
+        {
+            assert_eq!(resp.len(), 2);
+            resp.remove(1)
+        } else {
+            panic!("Test failed")
+        };
+        assert_eq!(response.result.code, u32::from(ResultCode::InvalidTx));
+        assert_eq!(
+            response.result.info,
+            String::from(
+                "The encrypted txs should not be proposed after the decrypted \
+                 txs"
+            ),
+        );
+    }
+
     /// Test that a block containing a tx incorrectly labelled as undecryptable
     /// is rejected by [`process_proposal`]
     #[test]
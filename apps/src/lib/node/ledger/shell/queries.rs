@@ -1,8 +1,10 @@
 //! Shell methods for querying state
 
-use namada::ledger::dry_run_tx;
-use namada::ledger::queries::{RequestCtx, ResponseQuery};
+use namada::ledger::queries::{
+    RequestCtx, ResponseQuery, ALLOC_REJECTIONS_PATH, BLOCK_STATS_PATH,
+};
 use namada::ledger::storage_api::token;
+use namada::ledger::{dry_run_tx, dry_run_tx_bundle};
 use namada::types::address::Address;
 
 use super::*;
@@ -26,11 +28,42 @@ where
             storage_read_past_height_limit: self.storage_read_past_height_limit,
         };
 
+        // Serve the response from the cache, if available
+        let last_height = self.wl_storage.storage.get_last_block_height().0;
+        let cached = self
+            .query_cache
+            .as_ref()
+            .and_then(|cache| cache.get(&query, last_height));
+
         // Invoke the root RPC handler - returns borsh-encoded data on success
-        let result = if query.path == "/shell/dry_run_tx" {
+        let result = if let Some(response) = cached {
+            Ok(response)
+        } else if query.path == "/shell/dry_run_tx" {
             dry_run_tx(ctx, &query)
+        } else if query.path == "/shell/dry_run_tx_bundle" {
+            dry_run_tx_bundle(ctx, &query)
+        } else if query.path == BLOCK_STATS_PATH {
+            // Block stats are only kept in memory by the shell
+            let block_stats: Vec<_> = self.block_stats.iter().collect();
+            Ok(ResponseQuery {
+                data: block_stats.serialize_to_vec(),
+                ..Default::default()
+            })
+        } else if query.path == ALLOC_REJECTIONS_PATH {
+            // The counters are only kept in memory by the shell
+            let alloc_rejections = self.alloc_rejections.lock().unwrap();
+            Ok(ResponseQuery {
+                data: alloc_rejections.serialize_to_vec(),
+                ..Default::default()
+            })
         } else {
-            namada::ledger::queries::handle_path(ctx, &query)
+            let result = namada::ledger::queries::handle_path(ctx, &query);
+            if let (Some(cache), Ok(response)) =
+                (self.query_cache.as_ref(), result.as_ref())
+            {
+                cache.insert(&query, last_height, response);
+            }
+            result
         };
         match result {
             Ok(ResponseQuery { data, info, proof }) => response::Query {
@@ -79,6 +112,57 @@ mod test_queries {
     use crate::node::ledger::shell::test_utils::get_pkh_from_address;
     use crate::node::ledger::shims::abcipp_shim_types::shim::request::FinalizeBlock;
 
+    /// Test that the shell serves the block stats that it keeps in memory
+    #[test]
+    fn test_block_stats_query() {
+        let (mut shell, _recv, _, _oracle_control_recv) =
+            test_utils::setup_at_height(0u64);
+        for height in 1..=2 {
+            shell.record_block_stats(BlockStats {
+                height: BlockHeight(height),
+                bytes_used: height * 100,
+                ..Default::default()
+            });
+        }
+
+        let response = shell.query(request::Query {
+            data: Default::default(),
+            path: BLOCK_STATS_PATH.to_string(),
+            height: Default::default(),
+            prove: false,
+        });
+        assert_eq!(response.code, 0.into());
+        let block_stats =
+            Vec::<BlockStats>::try_from_slice(&response.value).unwrap();
+        assert_eq!(
+            block_stats
+                .iter()
+                .map(|stats| (stats.height, stats.bytes_used))
+                .collect::<Vec<_>>(),
+            vec![(BlockHeight(1), 100), (BlockHeight(2), 200)]
+        );
+    }
+
+    /// Test that the shell serves the block space allocator rejections that
+    /// it keeps in memory
+    #[test]
+    fn test_alloc_rejections_query() {
+        let (shell, _recv, _, _oracle_control_recv) =
+            test_utils::setup_at_height(0u64);
+        shell.alloc_rejections.lock().unwrap().proposals = 3;
+
+        let response = shell.query(request::Query {
+            data: Default::default(),
+            path: ALLOC_REJECTIONS_PATH.to_string(),
+            height: Default::default(),
+            prove: false,
+        });
+        assert_eq!(response.code, 0.into());
+        let alloc_rejections =
+            AllocRejections::try_from_slice(&response.value).unwrap();
+        assert_eq!(alloc_rejections.proposals, 3);
+    }
+
     macro_rules! test_must_send_valset_upd {
         (epoch_assertions: $epoch_assertions:expr $(,)?) => {
             /// Test if [`EthBridgeQueries::must_send_valset_upd`] behaves as
@@ -0,0 +1,179 @@
+//! In-memory cache of the responses to queries at committed block heights.
+//!
+//! The data stored at a committed block height never changes, so responses to
+//! the queries of the configured paths at such a height can be served from
+//! memory instead of reading the storage again. Queries at height `0` (i.e.
+//! the latest height) are never cached, nor are queries beyond the configured
+//! `storage_read_past_height_limit`.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+
+use namada::ledger::queries::{EncodedResponseQuery, RequestQuery};
+
+use crate::config;
+
+/// The key of a cached response
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+struct CacheKey {
+    path: String,
+    data: Vec<u8>,
+    height: u64,
+    prove: bool,
+}
+
+#[derive(Debug, Default)]
+struct Entries {
+    responses: HashMap<CacheKey, EncodedResponseQuery>,
+    /// Insertion order of the keys, used for evicting the oldest responses
+    order: VecDeque<CacheKey>,
+}
+
+/// A bounded cache of query responses
+#[derive(Debug)]
+pub struct QueryCache {
+    paths: Vec<String>,
+    capacity: usize,
+    storage_read_past_height_limit: Option<u64>,
+    entries: Mutex<Entries>,
+}
+
+impl QueryCache {
+    /// Create a new cache from its config
+    pub fn new(
+        config: config::QueryCache,
+        storage_read_past_height_limit: Option<u64>,
+    ) -> Self {
+        Self {
+            paths: config.paths,
+            capacity: config.capacity,
+            storage_read_past_height_limit,
+            entries: Mutex::default(),
+        }
+    }
+
+    /// Get the key of a query that can be cached, given the last committed
+    /// block height, if any.
+    fn cache_key(
+        &self,
+        query: &RequestQuery,
+        last_height: u64,
+    ) -> Option<CacheKey> {
+        let height = query.height.value();
+        if self.capacity == 0
+            || height == 0
+            || height > last_height
+            || self
+                .storage_read_past_height_limit
+                .map(|limit| height + limit < last_height)
+                .unwrap_or_default()
+            || !self.paths.iter().any(|path| query.path.starts_with(path))
+        {
+            return None;
+        }
+        Some(CacheKey {
+            path: query.path.clone(),
+            data: query.data.to_vec(),
+            height,
+            prove: query.prove,
+        })
+    }
+
+    /// Find a cached response to the given query
+    pub fn get(
+        &self,
+        query: &RequestQuery,
+        last_height: u64,
+    ) -> Option<EncodedResponseQuery> {
+        let key = self.cache_key(query, last_height)?;
+        let entries = self.entries.lock().unwrap();
+        entries.responses.get(&key).cloned()
+    }
+
+    /// Cache the response to the given query, if the query is cacheable
+    pub fn insert(
+        &self,
+        query: &RequestQuery,
+        last_height: u64,
+        response: &EncodedResponseQuery,
+    ) {
+        let Some(key) = self.cache_key(query, last_height) else {
+            return;
+        };
+        let mut entries = self.entries.lock().unwrap();
+        if entries.responses.contains_key(&key) {
+            return;
+        }
+        if entries.order.len() >= self.capacity {
+            if let Some(oldest) = entries.order.pop_front() {
+                entries.responses.remove(&oldest);
+            }
+        }
+        entries.order.push_back(key.clone());
+        entries.responses.insert(key, response.clone());
+    }
+}
+
+#[cfg(test)]
+mod test_query_cache {
+    use super::*;
+
+    fn query(path: &str, height: u32) -> RequestQuery {
+        RequestQuery {
+            data: Default::default(),
+            path: path.to_string(),
+            height: height.into(),
+            prove: false,
+        }
+    }
+
+    fn response(data: u8) -> EncodedResponseQuery {
+        EncodedResponseQuery {
+            data: vec![data],
+            ..Default::default()
+        }
+    }
+
+    /// Test that only queries of the configured paths at committed heights
+    /// are cached, and that the oldest responses are evicted first.
+    #[test]
+    fn test_query_cache() {
+        let cache = QueryCache::new(
+            config::QueryCache {
+                paths: vec!["/shell/value".to_string()],
+                capacity: 2,
+            },
+            Some(8),
+        );
+        let last_height = 10;
+
+        // Latest height, uncommitted height, height beyond the past height
+        // limit and other paths are not cached
+        for query in [
+            query("/shell/value/a", 0),
+            query("/shell/value/a", 11),
+            query("/shell/value/a", 1),
+            query("/shell/epoch", 5),
+        ] {
+            cache.insert(&query, last_height, &response(0));
+            assert!(cache.get(&query, last_height).is_none());
+        }
+
+        let first = query("/shell/value/a", 5);
+        cache.insert(&first, last_height, &response(1));
+        assert_eq!(cache.get(&first, last_height).unwrap().data, vec![1]);
+
+        // The same path at another height is a different entry
+        let second = query("/shell/value/a", 6);
+        assert!(cache.get(&second, last_height).is_none());
+        cache.insert(&second, last_height, &response(2));
+        assert_eq!(cache.get(&second, last_height).unwrap().data, vec![2]);
+
+        // Exceeding the capacity evicts the oldest response
+        let third = query("/shell/value/b", 6);
+        cache.insert(&third, last_height, &response(3));
+        assert!(cache.get(&first, last_height).is_none());
+        assert_eq!(cache.get(&second, last_height).unwrap().data, vec![2]);
+        assert_eq!(cache.get(&third, last_height).unwrap().data, vec![3]);
+    }
+}
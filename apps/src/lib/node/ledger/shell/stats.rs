@@ -1,6 +1,11 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fmt::Display;
 
+use namada::ledger::gas::Gas;
+use namada::ledger::queries::BlockStats;
+use namada::types::storage::BlockHeight;
+use namada::types::transaction::TxType;
+
 #[derive(Debug, Default)]
 pub struct InternalStats {
     successful_tx: u64,
@@ -10,6 +15,9 @@ pub struct InternalStats {
     tx_cache_size: (usize, usize),
     tx_executed: HashMap<String, u64>,
     wrapper_txs: u64,
+    txs_by_type: BTreeMap<String, u64>,
+    gas_used_by_type: BTreeMap<String, Gas>,
+    bytes_used: u64,
 }
 
 impl InternalStats {
@@ -55,6 +63,49 @@ impl InternalStats {
     pub fn increment_wrapper_txs(&mut self) {
         self.wrapper_txs += 1;
     }
+
+    pub fn add_bytes_used(&mut self, bytes: usize) {
+        self.bytes_used += bytes as u64;
+    }
+
+    pub fn add_gas_used(&mut self, tx_type: &TxType, gas: Gas) {
+        let tx_type = match tx_type {
+            TxType::Raw => "raw",
+            TxType::Wrapper(_) => "wrapper",
+            TxType::Decrypted(_) => "decrypted",
+            TxType::Protocol(_) => "protocol",
+        };
+        *self.txs_by_type.entry(tx_type.to_string()).or_default() += 1;
+        let gas_used = self
+            .gas_used_by_type
+            .entry(tx_type.to_string())
+            .or_default();
+        *gas_used = gas_used.checked_add(gas).unwrap_or(*gas_used);
+    }
+
+    /// Get the gas and space utilization of the block
+    pub fn block_stats(
+        &self,
+        height: BlockHeight,
+        max_block_gas: Gas,
+        max_proposal_bytes: u64,
+    ) -> BlockStats {
+        let gas_used = self
+            .gas_used_by_type
+            .values()
+            .fold(Gas::default(), |acc, gas| {
+                acc.checked_add(*gas).unwrap_or(acc)
+            });
+        BlockStats {
+            height,
+            txs_by_type: self.txs_by_type.clone(),
+            gas_used_by_type: self.gas_used_by_type.clone(),
+            gas_used,
+            max_block_gas,
+            bytes_used: self.bytes_used,
+            max_proposal_bytes,
+        }
+    }
 }
 
 impl Display for InternalStats {
@@ -12,7 +12,6 @@ use lazy_static::lazy_static;
 use namada::core::types::ethereum_structs;
 use namada::core::types::transaction::ResultCode;
 use namada::eth_bridge::oracle::config::Config as OracleConfig;
-use namada::ledger::dry_run_tx;
 use namada::ledger::events::log::dumb_queries;
 use namada::ledger::queries::{
     EncodedResponseQuery, RequestCtx, RequestQuery, Router, RPC,
@@ -20,6 +19,7 @@ use namada::ledger::queries::{
 use namada::ledger::storage::{
     LastBlock, Sha256Hasher, EPOCH_SWITCH_BLOCKS_DELAY,
 };
+use namada::ledger::{dry_run_tx, dry_run_tx_bundle};
 use namada::proof_of_stake::pos_queries::PosQueries;
 use namada::proof_of_stake::storage::{
     read_consensus_validator_set_addresses_with_stake,
@@ -598,6 +598,8 @@ impl<'a> Client for &'a MockNode {
         };
         if request.path == "/shell/dry_run_tx" {
             dry_run_tx(ctx, &request)
+        } else if request.path == "/shell/dry_run_tx_bundle" {
+            dry_run_tx_bundle(ctx, &request)
         } else {
             rpc.handle(ctx, &request)
         }
@@ -0,0 +1,175 @@
+//! Operator-defined filter of the wrapper txs accepted into the mempool.
+//!
+//! The filter lets node operators reject txs by their size, the hash of their
+//! code or their fee payer without rebuilding the node. It is only applied in
+//! `mempool_validate`, so it doesn't affect the validity of block proposals.
+
+use std::collections::HashSet;
+
+use namada::proto::{Section, Tx};
+use namada::types::address::Address;
+use namada::types::transaction::{ResultCode, TxType};
+use thiserror::Error;
+
+use crate::config;
+
+/// The reason a tx was rejected by the [`TxFilter`]
+#[allow(missing_docs)]
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum Rejection {
+    #[error("Tx of {0} bytes exceeds the node's limit of {1} bytes")]
+    TooLarge(usize, usize),
+    #[error("Tx code is not allowed by the node's tx filter")]
+    CodeNotAllowed,
+    #[error("Txs paid by {0} are not allowed by the node's tx filter")]
+    SourceNotAllowed(Address),
+}
+
+impl Rejection {
+    /// The result code of the mempool response for this rejection
+    pub fn code(&self) -> ResultCode {
+        match self {
+            Self::TooLarge(_, _) => ResultCode::TooLarge,
+            Self::CodeNotAllowed => ResultCode::TxNotWhitelisted,
+            Self::SourceNotAllowed(_) => ResultCode::InvalidTx,
+        }
+    }
+}
+
+/// A filter of wrapper txs
+#[derive(Debug)]
+pub struct TxFilter {
+    max_tx_bytes: Option<usize>,
+    deny_code_hashes: HashSet<String>,
+    allow_code_hashes: HashSet<String>,
+    deny_sources: HashSet<Address>,
+}
+
+impl TxFilter {
+    /// Create a new filter from its config
+    pub fn new(config: config::TxFilter) -> Self {
+        let lowercase = |hashes: Vec<String>| {
+            hashes.into_iter().map(|hash| hash.to_lowercase()).collect()
+        };
+        Self {
+            max_tx_bytes: config.max_tx_bytes,
+            deny_code_hashes: lowercase(config.deny_code_hashes),
+            allow_code_hashes: lowercase(config.allow_code_hashes),
+            deny_sources: config.deny_sources.into_iter().collect(),
+        }
+    }
+
+    /// Check a tx of the given size in bytes against the filter. Txs other
+    /// than wrappers always pass.
+    pub fn check(&self, tx: &Tx, tx_bytes: usize) -> Result<(), Rejection> {
+        let TxType::Wrapper(wrapper) = &tx.header.tx_type else {
+            return Ok(());
+        };
+        if let Some(max_tx_bytes) = self.max_tx_bytes {
+            if tx_bytes > max_tx_bytes {
+                return Err(Rejection::TooLarge(tx_bytes, max_tx_bytes));
+            }
+        }
+        let source = wrapper.fee_payer();
+        if self.deny_sources.contains(&source) {
+            return Err(Rejection::SourceNotAllowed(source));
+        }
+        if !self.deny_code_hashes.is_empty()
+            || !self.allow_code_hashes.is_empty()
+        {
+            let code_hash = tx
+                .get_section(tx.code_sechash())
+                .and_then(|section| Section::code_sec(section.as_ref()))
+                .map(|section| section.code.hash().to_string().to_lowercase());
+            let is_allowed = match code_hash {
+                Some(hash) => {
+                    !self.deny_code_hashes.contains(&hash)
+                        && (self.allow_code_hashes.is_empty()
+                            || self.allow_code_hashes.contains(&hash))
+                }
+                None => self.allow_code_hashes.is_empty(),
+            };
+            if !is_allowed {
+                return Err(Rejection::CodeNotAllowed);
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test_tx_filter {
+    use namada::proto::Code;
+    use namada::types::key::RefTo;
+    use namada::types::storage::Epoch;
+    use namada::types::token::{Amount, DenominatedAmount};
+    use namada::types::transaction::{Fee, WrapperTx};
+
+    use super::*;
+    use crate::node::ledger::shell::test_utils::gen_keypair;
+
+    /// Test that wrapper txs are filtered by their size, code hash and fee
+    /// payer
+    #[test]
+    fn test_tx_filter() {
+        let keypair = gen_keypair();
+        let mut tx = Tx::from_type(TxType::Wrapper(Box::new(WrapperTx::new(
+            Fee {
+                amount_per_gas_unit: DenominatedAmount::native(Amount::zero()),
+                token: namada::types::address::nam(),
+            },
+            keypair.ref_to(),
+            Epoch(0),
+            Default::default(),
+            None,
+        ))));
+        let code = Code::new("wasm_code".as_bytes().to_owned(), None);
+        let code_hash = code.code.hash().to_string();
+        tx.set_code(code);
+        let source = Address::from(&keypair.ref_to());
+
+        let filter =
+            |config: config::TxFilter| TxFilter::new(config).check(&tx, 100);
+
+        // An empty filter accepts any tx
+        assert_eq!(filter(Default::default()), Ok(()));
+
+        assert_eq!(
+            filter(config::TxFilter {
+                max_tx_bytes: Some(99),
+                ..Default::default()
+            }),
+            Err(Rejection::TooLarge(100, 99))
+        );
+        assert_eq!(
+            filter(config::TxFilter {
+                deny_sources: vec![source.clone()],
+                ..Default::default()
+            }),
+            Err(Rejection::SourceNotAllowed(source))
+        );
+
+        // Code hashes are matched regardless of their case
+        assert_eq!(
+            filter(config::TxFilter {
+                deny_code_hashes: vec![code_hash.to_uppercase()],
+                ..Default::default()
+            }),
+            Err(Rejection::CodeNotAllowed)
+        );
+        assert_eq!(
+            filter(config::TxFilter {
+                allow_code_hashes: vec![code_hash.to_lowercase()],
+                ..Default::default()
+            }),
+            Ok(())
+        );
+        assert_eq!(
+            filter(config::TxFilter {
+                allow_code_hashes: vec!["00".repeat(32)],
+                ..Default::default()
+            }),
+            Err(Rejection::CodeNotAllowed)
+        );
+    }
+}
@@ -2,7 +2,9 @@ use std::convert::TryFrom;
 use std::future::Future;
 use std::path::PathBuf;
 use std::pin::Pin;
+use std::sync::Arc;
 use std::task::{Context, Poll};
+use std::time::Duration;
 
 use futures::future::FutureExt;
 use namada::proof_of_stake::storage::find_validator_by_raw_hash;
@@ -11,8 +13,8 @@ use namada::types::hash::Hash;
 use namada::types::key::tm_raw_hash_to_string;
 use namada::types::storage::{BlockHash, BlockHeight};
 use namada::types::transaction::hash_tx;
-use tokio::sync::broadcast;
 use tokio::sync::mpsc::UnboundedSender;
+use tokio::sync::{broadcast, OwnedSemaphorePermit, Semaphore};
 use tower::Service;
 
 use super::abcipp_shim_types::shim::request::{FinalizeBlock, ProcessedTx};
@@ -35,12 +37,18 @@ pub struct AbcippShim {
     service: Shell,
     begin_block_request: Option<request::BeginBlock>,
     delivered_txs: Vec<TxBytes>,
-    shell_recv: std::sync::mpsc::Receiver<(
-        Req,
-        tokio::sync::oneshot::Sender<Result<Resp, BoxError>>,
-    )>,
+    shell_recv: std::sync::mpsc::Receiver<ShellRequest>,
 }
 
+/// A request forwarded to the shell with the channel for its response. The
+/// queries and CheckTx requests also hold a permit for their slot in the
+/// shell's queue until they're processed.
+type ShellRequest = (
+    Req,
+    tokio::sync::oneshot::Sender<Result<Resp, BoxError>>,
+    Option<OwnedSemaphorePermit>,
+);
+
 impl AbcippShim {
     /// Create a shell with a ABCI service that passes messages to and from the
     /// shell.
@@ -54,12 +62,16 @@ impl AbcippShim {
         vp_wasm_compilation_cache: u64,
         tx_wasm_compilation_cache: u64,
     ) -> (Self, AbciService, broadcast::Sender<()>) {
-        // We can use an unbounded channel here, because tower-abci limits the
-        // the number of requests that can come in
-
+        // The consensus requests are always queued, as their number is
+        // bounded by the size of a block. Only the queries and CheckTx
+        // requests are limited by the number of slots in the queue.
         let (shell_send, shell_recv) = std::sync::mpsc::channel();
         let (server_shutdown, _) = broadcast::channel::<()>(1);
         let action_at_height = config.shell.action_at_height.clone();
+        let response_timeout = config
+            .shell
+            .abci_response_timeout_secs
+            .map(Duration::from_secs);
         (
             Self {
                 service: Shell::new(
@@ -77,8 +89,10 @@ impl AbcippShim {
             },
             AbciService {
                 shell_send,
+                queue_slots: Arc::new(Semaphore::new(SHELL_QUEUE_SLOTS)),
                 shutdown: server_shutdown.clone(),
                 action_at_height,
+                response_timeout,
                 suspended: false,
             },
             server_shutdown,
@@ -95,7 +109,12 @@ impl AbcippShim {
     /// Run the shell's blocking loop that receives messages from the
     /// [`AbciService`].
     pub fn run(mut self) {
-        while let Ok((req, resp_sender)) = self.shell_recv.recv() {
+        while let Ok((req, resp_sender, queue_slot)) = self.shell_recv.recv() {
+            // Skip the queries and CheckTx requests whose response is no
+            // longer awaited, e.g. after they timed out, to free their slots
+            if queue_slot.is_some() && resp_sender.is_closed() {
+                continue;
+            }
             let resp = match req {
                 Req::ProcessProposal(proposal) => self
                     .service
@@ -190,6 +209,10 @@ impl AbcippShim {
     }
 }
 
+/// The maximum number of queries and CheckTx requests forwarded to the shell
+/// that may be waiting to be processed
+const SHELL_QUEUE_SLOTS: usize = 100;
+
 /// Indicates how [`AbciService`] should
 /// check whether or not it needs to take
 /// action.
@@ -206,10 +229,10 @@ enum CheckAction {
 #[derive(Debug)]
 pub struct AbciService {
     /// A channel for forwarding requests to the shell
-    shell_send: std::sync::mpsc::Sender<(
-        Req,
-        tokio::sync::oneshot::Sender<Result<Resp, BoxError>>,
-    )>,
+    shell_send: std::sync::mpsc::Sender<ShellRequest>,
+    /// The slots in the shell's queue for the queries and CheckTx requests,
+    /// which are rejected when there's no slot left
+    queue_slots: Arc<Semaphore>,
     /// Indicates if the consensus connection is suspended.
     suspended: bool,
     /// This resolves the non-completing futures returned to tower-abci
@@ -217,6 +240,9 @@ pub struct AbciService {
     shutdown: broadcast::Sender<()>,
     /// An action to be taken at a specified block height.
     action_at_height: Option<ActionAtHeight>,
+    /// The maximum time to wait for the shell's response to a query or a
+    /// CheckTx request, if any
+    response_timeout: Option<Duration>,
 }
 
 impl AbciService {
@@ -291,15 +317,58 @@ impl AbciService {
     /// If we are not taking special action for this request,
     /// forward it normally.
     fn forward_request(&mut self, req: Req) -> <Self as Service<Req>>::Future {
+        // Only the mempool and queries may be rejected or time out, as
+        // failing any of the consensus requests would halt the node
+        let (queue_slot, response_timeout) = match req {
+            Req::Query(_) | Req::CheckTx(_) => {
+                match self.queue_slots.clone().try_acquire_owned() {
+                    Ok(queue_slot) => (Some(queue_slot), self.response_timeout),
+                    Err(_) => {
+                        tracing::error!(
+                            "The shell's request queue is full, the shell may \
+                             be stuck"
+                        );
+                        return async {
+                            Err(BoxError::from(
+                                "The shell's request queue is full",
+                            ))
+                        }
+                        .boxed();
+                    }
+                }
+            }
+            _ => (None, None),
+        };
         let (resp_send, recv) = tokio::sync::oneshot::channel();
-        let result = self.shell_send.send((req, resp_send));
+        // The request is sent right away to preserve the order of requests
+        let result = self
+            .shell_send
+            .send((req, resp_send, queue_slot))
+            .map_err(|err| err.to_string());
 
         async move {
             if let Err(err) = result {
                 // The shell has shut-down
                 return Err(err.into());
             }
-            match recv.await {
+            let resp = match response_timeout {
+                Some(timeout) => {
+                    match tokio::time::timeout(timeout, recv).await {
+                        Ok(resp) => resp,
+                        Err(_) => {
+                            tracing::error!(
+                                "The shell didn't respond to an ABCI request \
+                                 within {timeout:?}"
+                            );
+                            return Err(BoxError::from(format!(
+                                "The shell didn't respond within {timeout:?}"
+                            )));
+                        }
+                    }
+                }
+                None => recv.await,
+            };
+            match resp {
                 Ok(resp) => resp,
                 Err(err) => {
                     tracing::info!("ABCI response channel didn't respond");
@@ -349,7 +418,9 @@ impl Service<Req> for AbciService {
         &mut self,
         _cx: &mut Context<'_>,
     ) -> Poll<Result<(), Self::Error>> {
-        // Nothing to check as the sender's channel is unbounded
+        // Nothing to check as the consensus requests are always queued and the
+        // queries and CheckTx requests are rejected from `call` when the
+        // shell's queue is full
         Poll::Ready(Ok(()))
     }
 
@@ -368,3 +439,94 @@ impl Service<Req> for AbciService {
         }
     }
 }
+
+#[cfg(test)]
+mod test_abcipp_shim {
+    use std::sync::mpsc::{channel, Receiver};
+
+    use namada_sdk::tendermint::abci::request::CheckTxKind;
+
+    use super::*;
+
+    /// Create an ABCI service forwarding its requests to the returned
+    /// receiver
+    fn abci_service(
+        queue_slots: usize,
+        response_timeout: Option<Duration>,
+    ) -> (AbciService, Receiver<ShellRequest>) {
+        let (shell_send, shell_recv) = channel();
+        let (shutdown, _) = broadcast::channel(1);
+        let service = AbciService {
+            shell_send,
+            queue_slots: Arc::new(Semaphore::new(queue_slots)),
+            suspended: false,
+            shutdown,
+            action_at_height: None,
+            response_timeout,
+        };
+        (service, shell_recv)
+    }
+
+    fn check_tx() -> Req {
+        Req::CheckTx(request::CheckTx {
+            tx: Default::default(),
+            kind: CheckTxKind::New,
+        })
+    }
+
+    /// Test that a request fails when the shell's request queue is full
+    #[tokio::test]
+    async fn test_forward_request_queue_full() {
+        let (mut service, _shell_recv) = abci_service(1, None);
+
+        // The first request is queued, as the shell is not responding
+        let _queued = service.call(check_tx());
+        let err = service.call(check_tx()).await.unwrap_err();
+        assert_eq!(err.to_string(), "The shell's request queue is full");
+    }
+
+    /// Test that the consensus requests are still forwarded to the shell when
+    /// the shell's request queue is full
+    #[tokio::test]
+    async fn test_forward_consensus_request_queue_full() {
+        let (mut service, shell_recv) = abci_service(1, None);
+
+        // The queue is filled before the shell starts responding
+        let queued = service.call(check_tx());
+        let err = service.call(check_tx()).await.unwrap_err();
+        assert_eq!(err.to_string(), "The shell's request queue is full");
+        let end_block =
+            service.call(Req::EndBlock(request::EndBlock { height: 1 }));
+        let commit = service.call(Req::Commit);
+
+        std::thread::spawn(move || {
+            while let Ok((_req, resp_send, _queue_slot)) = shell_recv.recv() {
+                let _ = resp_send.send(Ok(Resp::Flush));
+            }
+        });
+        assert!(queued.await.is_ok());
+        assert!(end_block.await.is_ok());
+        assert!(commit.await.is_ok());
+    }
+
+    /// Test that only queries and CheckTx requests time out
+    #[tokio::test]
+    async fn test_forward_request_timeout() {
+        let (mut service, shell_recv) =
+            abci_service(SHELL_QUEUE_SLOTS, Some(Duration::from_millis(10)));
+        // A slow shell, responding after the timeout
+        std::thread::spawn(move || {
+            while let Ok((_req, resp_send, _queue_slot)) = shell_recv.recv() {
+                std::thread::sleep(Duration::from_millis(100));
+                let _ = resp_send.send(Ok(Resp::Flush));
+            }
+        });
+
+        let err = service.call(check_tx()).await.unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("The shell didn't respond within"));
+        let resp = service.call(Req::Flush).await.unwrap();
+        assert!(matches!(resp, Resp::Flush));
+    }
+}
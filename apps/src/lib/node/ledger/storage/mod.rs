@@ -680,6 +680,74 @@ mod tests {
         assert!(result.is_err(), "The bridge pool tree should be pruned");
     }
 
+    /// Test that the block data needed to rebuild the Merkle trees at the
+    /// retained heights isn't pruned and that no proofs are made at the
+    /// pruned heights
+    #[test]
+    fn test_prune_block_data_from_epoch_start() {
+        let db_path =
+            TempDir::new().expect("Unable to create a temporary DB directory");
+        let mut storage = PersistentStorage::open(
+            db_path.path(),
+            ChainId::default(),
+            address::nam(),
+            None,
+            None,
+        );
+        storage.block_data_retain_height = Some(3);
+        let key = ibc_key("key").unwrap();
+
+        let mut roots = HashMap::new();
+        for height in 1..=10_u64 {
+            let height = BlockHeight(height);
+            storage
+                .begin_block(BlockHash::default(), height)
+                .expect("begin_block failed");
+            storage
+                .write(&key, types::encode(&height))
+                .expect("write failed");
+            // The second epoch starts at height 5
+            if height == BlockHeight(5) {
+                storage.block.epoch = storage.block.epoch.next();
+            }
+            if height == BlockHeight(1) || height == BlockHeight(5) {
+                storage.block.pred_epochs.new_epoch(height);
+            }
+            roots.insert(height, storage.merkle_root());
+            let batch = PersistentStorage::batch();
+            storage.commit_block(batch).expect("commit failed");
+        }
+        assert_eq!(storage.get_oldest_block_data_height(), BlockHeight(7));
+
+        // The trees at the retained heights are rebuilt from the start of
+        // their epoch
+        for height in 7..=10_u64 {
+            let height = BlockHeight(height);
+            let tree = storage
+                .get_merkle_tree(height, None)
+                .expect("The tree should be restored");
+            assert_eq!(tree.root().0, roots.get(&height).unwrap().0);
+            let value = types::encode(&height);
+            storage
+                .get_existence_proof(&key, &value, height)
+                .expect("The value should be proven");
+        }
+
+        let missing_key = ibc_key("missing").unwrap();
+        for height in [4_u64, 6] {
+            let height = BlockHeight(height);
+            let value = types::encode(&height);
+            assert!(matches!(
+                storage.get_existence_proof(&key, &value, height),
+                Err(namada::ledger::storage::Error::PrunedBlockData { .. })
+            ));
+            assert!(matches!(
+                storage.get_non_existence_proof(&missing_key, height),
+                Err(namada::ledger::storage::Error::PrunedBlockData { .. })
+            ));
+        }
+    }
+
     /// Test the prefix iterator with RocksDB.
     #[test]
     fn test_persistent_storage_prefix_iter() {
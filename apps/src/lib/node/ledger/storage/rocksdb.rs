@@ -58,14 +58,16 @@ use namada::ledger::storage::merkle_tree::{
 };
 use namada::ledger::storage::types::PrefixIterator;
 use namada::ledger::storage::{
-    types, BlockStateRead, BlockStateWrite, DBIter, DBWriteBatch, Error,
-    MerkleTreeStoresRead, Result, StoreType, DB,
+    address_tx_key, types, BlockStateRead, BlockStateWrite, DBIter,
+    DBWriteBatch, Error, MerkleTreeStoresRead, Result, StoreType, DB,
 };
+use namada::types::address::Address;
 use namada::types::ethereum_events::Uint;
+use namada::types::hash::Hash;
 use namada::types::internal::TxQueue;
 use namada::types::storage::{
     BlockHeight, BlockResults, Epoch, EthEventsQueue, Header, Key, KeySeg,
-    KEY_SEGMENT_SEPARATOR,
+    TxIndex, KEY_SEGMENT_SEPARATOR,
 };
 use namada::types::time::DateTimeUtc;
 use rayon::prelude::*;
@@ -88,6 +90,7 @@ const DIFFS_CF: &str = "diffs";
 const STATE_CF: &str = "state";
 const BLOCK_CF: &str = "block";
 const REPLAY_PROTECTION_CF: &str = "replay_protection";
+const ADDRESS_INDEX_CF: &str = "address_index";
 
 /// RocksDB handle
 #[derive(Debug)]
@@ -190,6 +193,19 @@ pub fn open(
         replay_protection_cf_opts,
     ));
 
+    // for the index of txs by address (insert-intensive)
+    let mut address_index_cf_opts = Options::default();
+    address_index_cf_opts
+        .set_compression_type(rocksdb::DBCompressionType::Zstd);
+    address_index_cf_opts.set_compression_options(0, 0, 0, 1024 * 1024);
+    address_index_cf_opts
+        .set_compaction_style(rocksdb::DBCompactionStyle::Universal);
+    address_index_cf_opts.set_block_based_table_factory(&table_opts);
+    cfs.push(ColumnFamilyDescriptor::new(
+        ADDRESS_INDEX_CF,
+        address_index_cf_opts,
+    ));
+
     rocksdb::DB::open_cf_descriptors(&db_opts, path, cfs)
         .map(RocksDB)
         .map_err(|e| Error::DBError(e.into_string()))
@@ -513,6 +529,23 @@ impl RocksDB {
         batch
             .delete_cf(reprot_cf, replay_protection::last_prefix().to_string());
 
+        // Delete the entries of the txs applied in the last block from the
+        // index of txs by address
+        let address_index_cf = self.get_column_family(ADDRESS_INDEX_CF)?;
+        tracing::info!("Removing the last block's txs from the address index");
+        for (key, value) in self
+            .0
+            .iterator_cf(address_index_cf, IteratorMode::Start)
+            .map(|result| result.map_err(|e| Error::DBError(e.into_string())))
+            .collect::<Result<Vec<_>>>()?
+        {
+            let (height, _hash): (BlockHeight, Hash) =
+                types::decode(value).map_err(Error::CodingError)?;
+            if height == last_block.height {
+                batch.delete_cf(address_index_cf, key);
+            }
+        }
+
         // Execute next step in parallel
         let batch = Mutex::new(batch);
 
@@ -1158,10 +1191,7 @@ impl DB for RocksDB {
         Ok(Some(merkle_tree_stores))
     }
 
-    fn has_replay_protection_entry(
-        &self,
-        hash: &namada::types::hash::Hash,
-    ) -> Result<bool> {
+    fn has_replay_protection_entry(&self, hash: &Hash) -> Result<bool> {
         let replay_protection_cf =
             self.get_column_family(REPLAY_PROTECTION_CF)?;
 
@@ -1450,6 +1480,25 @@ impl DB for RocksDB {
         Ok(())
     }
 
+    fn prune_block_data(
+        &mut self,
+        batch: &mut Self::WriteBatch,
+        from: BlockHeight,
+        to: BlockHeight,
+    ) -> Result<()> {
+        // The height key segments preserve the ordering of the heights, so
+        // the data of the heights in the range is contiguous
+        let diffs_cf = self.get_column_family(DIFFS_CF)?;
+        batch.0.delete_range_cf(diffs_cf, from.raw(), to.raw());
+        let block_cf = self.get_column_family(BLOCK_CF)?;
+        batch.0.delete_range_cf(
+            block_cf,
+            format!("results/{}", from.raw()),
+            format!("results/{}", to.raw()),
+        );
+        Ok(())
+    }
+
     fn read_bridge_pool_signed_nonce(
         &self,
         height: BlockHeight,
@@ -1498,6 +1547,50 @@ impl DB for RocksDB {
 
         Ok(())
     }
+
+    fn batch_write_address_tx(
+        &mut self,
+        batch: &mut Self::WriteBatch,
+        owner: &Address,
+        height: BlockHeight,
+        tx_index: TxIndex,
+        hash: &Hash,
+    ) -> Result<()> {
+        let address_index_cf = self.get_column_family(ADDRESS_INDEX_CF)?;
+        // The entry is keyed by the tx's position, so writing it again when a
+        // block is replayed doesn't duplicate it
+        let key = format!("{owner}/{}", address_tx_key(height, tx_index));
+        batch
+            .0
+            .put_cf(address_index_cf, key, types::encode(&(height, *hash)));
+        Ok(())
+    }
+
+    fn read_schema_version(&self) -> Result<Option<u64>> {
+        let state_cf = self.get_column_family(STATE_CF)?;
+        match self
+            .0
+            .get_cf(state_cf, "schema_version")
+            .map_err(|e| Error::DBError(e.into_string()))?
+        {
+            Some(bytes) => {
+                Ok(Some(types::decode(bytes).map_err(Error::CodingError)?))
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn batch_write_schema_version(
+        &mut self,
+        batch: &mut Self::WriteBatch,
+        version: u64,
+    ) -> Result<()> {
+        let state_cf = self.get_column_family(STATE_CF)?;
+        batch
+            .0
+            .put_cf(state_cf, "schema_version", types::encode(&version));
+        Ok(())
+    }
 }
 
 impl<'iter> DBIter<'iter> for RocksDB {
@@ -1510,6 +1603,34 @@ impl<'iter> DBIter<'iter> for RocksDB {
         iter_subspace_prefix(self, prefix)
     }
 
+    fn iter_prefix_after(
+        &'iter self,
+        prefix: &Key,
+        start_after: Option<&Key>,
+    ) -> PersistentPrefixIterator<'iter> {
+        let subspace_cf = self
+            .get_column_family(SUBSPACE_CF)
+            .expect("{SUBSPACE_CF} column family should exist");
+        let prefix = if prefix.is_empty() {
+            "".to_owned()
+        } else {
+            format!("{prefix}/")
+        };
+        // Seek to the first key after the given one, unless it's before the
+        // prefix
+        let start = match start_after {
+            Some(key) => std::cmp::max(format!("{key}\0"), prefix.clone()),
+            None => prefix.clone(),
+        };
+        let read_opts = make_iter_read_opts(Some(prefix));
+        let iter = self.0.iterator_cf_opt(
+            subspace_cf,
+            read_opts,
+            IteratorMode::From(start.as_bytes(), Direction::Forward),
+        );
+        PersistentPrefixIterator(PrefixIterator::new(iter, "".to_owned()))
+    }
+
     fn iter_results(&'iter self) -> PersistentPrefixIterator<'iter> {
         let db_prefix = "results/".to_owned();
         let prefix = "results".to_owned();
@@ -1550,6 +1671,21 @@ impl<'iter> DBIter<'iter> for RocksDB {
         let stripped_prefix = Some(replay_protection::last_prefix());
         iter_prefix(self, replay_protection_cf, stripped_prefix.as_ref(), None)
     }
+
+    fn iter_address_txs(&'iter self, owner: &Address) -> Self::PrefixIter {
+        let prefix = format!("{owner}/");
+
+        let address_index_cf = self
+            .get_column_family(ADDRESS_INDEX_CF)
+            .expect("{ADDRESS_INDEX_CF} column family should exist");
+        let read_opts = make_iter_read_opts(Some(prefix.clone()));
+        let iter = self.0.iterator_cf_opt(
+            address_index_cf,
+            read_opts,
+            IteratorMode::From(prefix.as_bytes(), Direction::Forward),
+        );
+        PersistentPrefixIterator(PrefixIterator::new(iter, prefix))
+    }
 }
 
 fn iter_subspace_prefix<'iter>(
@@ -1874,6 +2010,88 @@ mod test {
         assert_eq!(latest_value, None);
     }
 
+    /// Test that pruning the data of old blocks keeps the values at the
+    /// following heights readable
+    #[test]
+    fn test_prune_block_data() {
+        let dir = tempdir().unwrap();
+        let mut db = open(dir.path(), None).unwrap();
+
+        let key = Key::parse("test").unwrap();
+        for height in [10, 20, 30] {
+            db.write_subspace_val(
+                BlockHeight(height),
+                &key,
+                vec![height as u8],
+            )
+            .unwrap();
+        }
+        let last_height = BlockHeight(30);
+
+        let mut batch = RocksDB::batch();
+        db.prune_block_data(&mut batch, BlockHeight(1), BlockHeight(20))
+            .unwrap();
+        db.exec_batch(batch.0).unwrap();
+
+        assert!(db.iter_new_diffs(BlockHeight(10), None).next().is_none());
+        assert!(db.iter_new_diffs(BlockHeight(20), None).next().is_some());
+        for (height, expected) in [(20, 20_u8), (25, 20), (30, 30)] {
+            let value = db
+                .read_subspace_val_with_height(
+                    &key,
+                    BlockHeight(height),
+                    last_height,
+                )
+                .expect("read should succeed");
+            assert_eq!(value, Some(vec![expected]));
+        }
+    }
+
+    /// Test that the index of txs by address is iterated from the most recent
+    /// tx of the owner
+    #[test]
+    fn test_address_index() {
+        let dir = tempdir().unwrap();
+        let mut db = open(dir.path(), None).unwrap();
+
+        let owner = gen_established_address("owner");
+        let other = gen_established_address("other");
+        let txs = [
+            (BlockHeight(9), TxIndex(1), Hash([1; 32])),
+            (BlockHeight(10), TxIndex(0), Hash([2; 32])),
+            (BlockHeight(10), TxIndex(2), Hash([3; 32])),
+        ];
+        let mut batch = RocksDB::batch();
+        for (height, tx_index, hash) in &txs {
+            db.batch_write_address_tx(
+                &mut batch, &owner, *height, *tx_index, hash,
+            )
+            .unwrap();
+        }
+        db.batch_write_address_tx(
+            &mut batch,
+            &other,
+            BlockHeight(11),
+            TxIndex(0),
+            &txs[0].2,
+        )
+        .unwrap();
+        // Nothing is indexed until the batch is executed
+        assert!(db.iter_address_txs(&owner).next().is_none());
+        db.exec_batch(batch.0).unwrap();
+
+        let indexed: Vec<(BlockHeight, Hash)> = db
+            .iter_address_txs(&owner)
+            .map(|(_key, value, _gas)| types::decode(value).unwrap())
+            .collect();
+        let expected: Vec<_> = txs
+            .iter()
+            .rev()
+            .map(|(height, _tx_index, hash)| (*height, *hash))
+            .collect();
+        assert_eq!(indexed, expected);
+    }
+
     #[test]
     fn test_prefix_iter() {
         let dir = tempdir().unwrap();
@@ -1909,19 +2127,33 @@ mod test {
             .iter_prefix(Some(&prefix_0))
             .map(|(key, _val, _)| Key::parse(key).unwrap())
             .collect();
-        itertools::assert_equal(keys_0, itered_keys);
+        itertools::assert_equal(keys_0.clone(), itered_keys);
 
         let itered_keys: Vec<Key> = db
             .iter_prefix(Some(&prefix_1))
             .map(|(key, _val, _)| Key::parse(key).unwrap())
             .collect();
-        itertools::assert_equal(keys_1, itered_keys);
+        itertools::assert_equal(keys_1.clone(), itered_keys);
 
         let itered_keys: Vec<Key> = db
             .iter_prefix(None)
             .map(|(key, _val, _)| Key::parse(key).unwrap())
             .collect();
         itertools::assert_equal(all_keys, itered_keys);
+
+        // Iterate after a key under the prefix
+        let itered_keys: Vec<Key> = db
+            .iter_prefix_after(&prefix_0, Some(&keys_0[0]))
+            .map(|(key, _val, _)| Key::parse(key).unwrap())
+            .collect();
+        itertools::assert_equal(keys_0[1..].to_vec(), itered_keys);
+
+        // A key before the prefix doesn't skip any of its keys
+        let itered_keys: Vec<Key> = db
+            .iter_prefix_after(&prefix_1, Some(&keys_0[0]))
+            .map(|(key, _val, _)| Key::parse(key).unwrap())
+            .collect();
+        itertools::assert_equal(keys_1, itered_keys);
     }
 
     #[test]
@@ -1962,6 +2194,17 @@ mod test {
         )
         .unwrap();
 
+        let owner = gen_established_address("owner");
+        let hash_0 = Hash([0; 32]);
+        db.batch_write_address_tx(
+            &mut batch,
+            &owner,
+            height_0,
+            TxIndex(0),
+            &hash_0,
+        )
+        .unwrap();
+
         add_block_to_batch(
             &db,
             &mut batch,
@@ -1994,6 +2237,14 @@ mod test {
         .unwrap();
         db.batch_delete_subspace_val(&mut batch, height_1, &delete_key)
             .unwrap();
+        db.batch_write_address_tx(
+            &mut batch,
+            &owner,
+            height_1,
+            TxIndex(0),
+            &Hash([1; 32]),
+        )
+        .unwrap();
 
         add_block_to_batch(
             &db,
@@ -2031,6 +2282,12 @@ mod test {
                 .unwrap()
                 .unwrap();
         assert_eq!(conversion_state, types::encode(&conversion_state_0));
+        // Check that only the txs of the first block are indexed
+        let indexed: Vec<(BlockHeight, Hash)> = db
+            .iter_address_txs(&owner)
+            .map(|(_key, value, _gas)| types::decode(value).unwrap())
+            .collect();
+        assert_eq!(indexed, vec![(height_0, hash_0)]);
     }
 
     /// A test helper to write a block
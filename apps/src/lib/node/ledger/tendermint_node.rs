@@ -18,7 +18,7 @@ use tokio::sync::oneshot::error::RecvError;
 use tokio::sync::oneshot::{Receiver, Sender};
 
 use crate::cli::namada_version;
-use crate::config;
+use crate::config::{self, TendermintMode};
 use crate::facade::tendermint::node::Id as TendermintNodeId;
 use crate::facade::tendermint::{block, Genesis, Moniker};
 use crate::facade::tendermint_config::{
@@ -54,6 +54,39 @@ pub enum Error {
     CantCreate(String),
     #[error("Couldn't encode {0}")]
     CantEncode(&'static str),
+    #[error("Couldn't read {0}")]
+    CantRead(String),
+    #[error(
+        "The CometBFT private validator state ({current}) is behind the last \
+         signed state recorded by the node ({recorded}), which may happen \
+         after restoring the CometBFT data from a backup. Refusing to start \
+         to prevent double signing. If you are sure that this validator has \
+         not signed any blocks past the current state, remove {record}."
+    )]
+    SignStateBehind {
+        current: SignState,
+        recorded: SignState,
+        record: String,
+    },
+}
+
+/// The last consensus vote signed by a validator, as recorded by CometBFT in
+/// its private validator state file
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct SignState {
+    height: u64,
+    round: i64,
+    step: i64,
+}
+
+impl std::fmt::Display for SignState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "height {}, round {}, step {}",
+            self.height, self.round, self.step
+        )
+    }
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
@@ -84,20 +117,32 @@ pub async fn run(
     config: config::Ledger,
     abort_recv: Receiver<Sender<()>>,
 ) -> Result<()> {
+    let is_validator =
+        matches!(config.shell.tendermint_mode, TendermintMode::Validator);
+    let record = sign_state_record(config.chain_dir());
     let (home_dir_string, tendermint_path) =
-        initalize_config(home_dir, chain_id, genesis_time, config).await?;
+        initalize_config(&home_dir, chain_id, genesis_time, config).await?;
+    if is_validator {
+        check_sign_state(&home_dir, &record)?;
+    }
     let tendermint_node =
         start_node(proxy_app_address, home_dir_string, tendermint_path)?;
 
     tracing::info!("CometBFT node started");
 
-    handle_node_response(tendermint_node, abort_recv).await
+    let res = handle_node_response(tendermint_node, abort_recv).await;
+    if is_validator {
+        if let Err(err) = record_sign_state(&home_dir, &record) {
+            tracing::error!("Failed to record the last signed state: {err}");
+        }
+    }
+    res
 }
 
 /// Setup the tendermint configuration. We return the tendermint path and home
 /// directory
 async fn initalize_config(
-    home_dir: PathBuf,
+    home_dir: &Path,
     chain_id: ChainId,
     genesis_time: DateTimeUtc,
     config: config::Ledger,
@@ -116,9 +161,14 @@ async fn initalize_config(
         panic!("Tendermint failed to initialize with {:#?}", output);
     }
 
-    write_tm_genesis(&home_dir, chain_id, genesis_time).await?;
+    write_tm_genesis(home_dir, chain_id, genesis_time).await?;
 
-    update_tendermint_config(&home_dir, config.cometbft).await?;
+    update_tendermint_config(
+        home_dir,
+        config.cometbft,
+        config.cometbft_mempool_version,
+    )
+    .await?;
     Ok((home_dir_string, tendermint_path))
 }
 
@@ -197,7 +247,10 @@ async fn handle_abort(
     }
 }
 
-pub fn reset(tendermint_dir: impl AsRef<Path>) -> Result<()> {
+pub fn reset(
+    tendermint_dir: impl AsRef<Path>,
+    chain_dir: impl AsRef<Path>,
+) -> Result<()> {
     let tendermint_path = from_env_or_default()?;
     let tendermint_dir = tendermint_dir.as_ref().to_string_lossy();
     // reset all the Tendermint state, if any
@@ -214,6 +267,12 @@ pub fn reset(tendermint_dir: impl AsRef<Path>) -> Result<()> {
         .expect("Failed to reset tendermint node's data");
     std::fs::remove_dir_all(format!("{}/config", tendermint_dir,))
         .expect("Failed to reset tendermint node's config");
+    // the validator state has been reset too, so its record is no longer valid
+    let record = sign_state_record(chain_dir);
+    if record.exists() {
+        std::fs::remove_file(record)
+            .expect("Failed to reset the validator's last signed state");
+    }
     Ok(())
 }
 
@@ -347,6 +406,76 @@ fn write_validator(
     })
 }
 
+/// Read the last signed state from a file in the format of CometBFT's private
+/// validator state, if the file exists
+fn read_sign_state(path: &Path) -> Result<Option<SignState>> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let err = |msg: String| {
+        Error::CantRead(format!(
+            "{} at {}. Caused by {msg}",
+            STATE_FILE,
+            path.to_string_lossy()
+        ))
+    };
+    let data = std::fs::read(path).map_err(|e| err(e.to_string()))?;
+    let state: serde_json::Value =
+        serde_json::from_slice(&data).map_err(|e| err(e.to_string()))?;
+    // CometBFT encodes the height as a string
+    let height = state["height"]
+        .as_str()
+        .and_then(|height| height.parse().ok())
+        .ok_or_else(|| err("invalid height".to_string()))?;
+    let round = state["round"]
+        .as_i64()
+        .ok_or_else(|| err("invalid round".to_string()))?;
+    let step = state["step"]
+        .as_i64()
+        .ok_or_else(|| err("invalid step".to_string()))?;
+    Ok(Some(SignState {
+        height,
+        round,
+        step,
+    }))
+}
+
+/// Check that the CometBFT private validator state is not behind the last
+/// signed state recorded by the node, which would allow CometBFT to sign
+/// conflicting votes. Updates the record if the check passes.
+fn check_sign_state(home_dir: &Path, record: &Path) -> Result<()> {
+    let current = read_sign_state(&validator_state(home_dir))?;
+    if let Some(recorded) = read_sign_state(record)? {
+        let current = current.unwrap_or_default();
+        if current < recorded {
+            return Err(Error::SignStateBehind {
+                current,
+                recorded,
+                record: record.to_string_lossy().to_string(),
+            });
+        }
+    }
+    record_sign_state(home_dir, record)
+}
+
+/// Record the last signed state from the CometBFT private validator state,
+/// unless it is behind the existing record.
+fn record_sign_state(home_dir: &Path, record: &Path) -> Result<()> {
+    let Some(current) = read_sign_state(&validator_state(home_dir))? else {
+        return Ok(());
+    };
+    if matches!(read_sign_state(record)?, Some(recorded) if recorded >= current)
+    {
+        return Ok(());
+    }
+    let state = json!({
+        "height": current.height.to_string(),
+        "round": current.round,
+        "step": current.step,
+    });
+    write_validator(record.to_owned(), STATE_DIR, STATE_FILE, state)
+}
+
 /// Length of a Tendermint Node ID in bytes
 const TENDERMINT_NODE_ID_LENGTH: usize = 20;
 
@@ -372,6 +501,7 @@ pub fn id_from_pk(pk: &common::PublicKey) -> TendermintNodeId {
 async fn update_tendermint_config(
     home_dir: impl AsRef<Path>,
     mut config: TendermintConfig,
+    mempool_version: Option<String>,
 ) -> Result<()> {
     let path = configuration(home_dir);
 
@@ -420,13 +550,41 @@ async fn update_tendermint_config(
         .open(path)
         .await
         .map_err(Error::OpenWriteConfig)?;
-    let config_str =
-        toml::to_string(&config).map_err(Error::ConfigSerializeToml)?;
+    let config_str = config_to_toml(&config, mempool_version.as_deref())?;
     file.write_all(config_str.as_bytes())
         .await
         .map_err(Error::WriteConfig)
 }
 
+/// The mempool version used unless the operator sets one. The prioritized
+/// mempool (v1) orders the txs by the priority returned from `CheckTx`, so
+/// that the wrapper txs paying the highest fees are broadcast and proposed
+/// first. The FIFO mempool (v0) ignores it. The prioritized mempool is
+/// deprecated in CometBFT v0.37 and removed in v0.38, so this default has to
+/// be revisited when upgrading from v0.37.
+const DEFAULT_MEMPOOL_VERSION: &str = "v1";
+
+/// Serialize the CometBFT config to TOML, with the given mempool version or
+/// the prioritized mempool by default. The mempool version is not part of the
+/// typed config, so it's added to the serialized one.
+fn config_to_toml(
+    config: &TendermintConfig,
+    mempool_version: Option<&str>,
+) -> Result<String> {
+    let mut config =
+        toml::Value::try_from(config).map_err(Error::ConfigSerializeToml)?;
+    if let Some(mempool) = config
+        .get_mut("mempool")
+        .and_then(toml::Value::as_table_mut)
+    {
+        mempool.insert(
+            "version".to_owned(),
+            mempool_version.unwrap_or(DEFAULT_MEMPOOL_VERSION).into(),
+        );
+    }
+    toml::to_string(&config).map_err(Error::ConfigSerializeToml)
+}
+
 async fn write_tm_genesis(
     home_dir: impl AsRef<Path>,
     chain_id: ChainId,
@@ -514,6 +672,13 @@ fn validator_state(home_dir: impl AsRef<Path>) -> PathBuf {
         .join("priv_validator_state.json")
 }
 
+/// The record of the last signed state is kept in the chain directory,
+/// outside of CometBFT's home, so that it is not affected by restoring the
+/// CometBFT home or its data from a backup
+fn sign_state_record(chain_dir: impl AsRef<Path>) -> PathBuf {
+    chain_dir.as_ref().join("last_sign_state.json")
+}
+
 fn configuration(home_dir: impl AsRef<Path>) -> PathBuf {
     home_dir.as_ref().join("config").join("config.toml")
 }
@@ -530,3 +695,126 @@ const STATE_FILE: &str = "private validator state file";
 const STATE_DIR: &str = "private validator state directory";
 
 const GENESIS_FILE: &str = "CometBFT genesis file";
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::config::DEFAULT_COMETBFT_CONFIG;
+
+    /// Write a CometBFT private validator state with the given last signed
+    /// state
+    fn write_state(home_dir: &Path, height: u64, round: i64, step: i64) {
+        let state = json!({
+            "height": height.to_string(),
+            "round": round,
+            "step": step,
+        });
+        write_validator(
+            validator_state(home_dir),
+            STATE_DIR,
+            STATE_FILE,
+            state,
+        )
+        .unwrap();
+    }
+
+    fn sign_state(height: u64, round: i64, step: i64) -> Option<SignState> {
+        Some(SignState {
+            height,
+            round,
+            step,
+        })
+    }
+
+    /// Test that the serialized CometBFT config uses the prioritized mempool,
+    /// unless the operator sets another mempool version
+    #[test]
+    fn test_config_mempool_version() {
+        let config = TendermintConfig::parse_toml(DEFAULT_COMETBFT_CONFIG)
+            .expect("Test failed");
+        let config_str = config_to_toml(&config, None).expect("Test failed");
+
+        let value: toml::Value = toml::from_str(&config_str).unwrap();
+        assert_eq!(
+            value["mempool"]["version"],
+            toml::Value::String("v1".to_owned())
+        );
+        let parsed =
+            TendermintConfig::parse_toml(&config_str).expect("Test failed");
+        assert_eq!(parsed.mempool.size, config.mempool.size);
+
+        let config_str =
+            config_to_toml(&config, Some("v0")).expect("Test failed");
+        let value: toml::Value = toml::from_str(&config_str).unwrap();
+        assert_eq!(
+            value["mempool"]["version"],
+            toml::Value::String("v0".to_owned())
+        );
+    }
+
+    /// Test that the node refuses to start with a validator state that is
+    /// behind the recorded one and that the record follows the validator
+    /// state otherwise
+    #[test]
+    fn test_check_sign_state() {
+        let home_dir = tempfile::tempdir().unwrap();
+        let chain_dir = tempfile::tempdir().unwrap();
+        let home_dir = home_dir.path();
+        let record = sign_state_record(chain_dir.path());
+
+        // Without a record, the current state gets recorded
+        write_state(home_dir, 10, 0, 3);
+        check_sign_state(home_dir, &record).unwrap();
+        assert_eq!(read_sign_state(&record).unwrap(), sign_state(10, 0, 3));
+
+        // An equal state passes
+        check_sign_state(home_dir, &record).unwrap();
+        assert_eq!(read_sign_state(&record).unwrap(), sign_state(10, 0, 3));
+
+        // A state ahead passes and updates the record
+        write_state(home_dir, 10, 1, 1);
+        check_sign_state(home_dir, &record).unwrap();
+        assert_eq!(read_sign_state(&record).unwrap(), sign_state(10, 1, 1));
+
+        // A state behind is rejected and the record is kept
+        write_state(home_dir, 9, 2, 3);
+        assert!(matches!(
+            check_sign_state(home_dir, &record),
+            Err(Error::SignStateBehind { current, recorded, .. })
+                if Some(current) == sign_state(9, 2, 3)
+                    && Some(recorded) == sign_state(10, 1, 1)
+        ));
+        assert_eq!(read_sign_state(&record).unwrap(), sign_state(10, 1, 1));
+    }
+
+    /// Test that the record is only updated by a validator state ahead of it
+    #[test]
+    fn test_record_sign_state() {
+        let home_dir = tempfile::tempdir().unwrap();
+        let chain_dir = tempfile::tempdir().unwrap();
+        let home_dir = home_dir.path();
+        let record = sign_state_record(chain_dir.path());
+
+        // Nothing is recorded without a validator state
+        record_sign_state(home_dir, &record).unwrap();
+        assert_eq!(read_sign_state(&record).unwrap(), None);
+
+        write_state(home_dir, 5, 0, 2);
+        record_sign_state(home_dir, &record).unwrap();
+        assert_eq!(read_sign_state(&record).unwrap(), sign_state(5, 0, 2));
+
+        // Equal state
+        record_sign_state(home_dir, &record).unwrap();
+        assert_eq!(read_sign_state(&record).unwrap(), sign_state(5, 0, 2));
+
+        // State behind
+        write_state(home_dir, 5, 0, 1);
+        record_sign_state(home_dir, &record).unwrap();
+        assert_eq!(read_sign_state(&record).unwrap(), sign_state(5, 0, 2));
+
+        // State ahead
+        write_state(home_dir, 6, 0, 1);
+        record_sign_state(home_dir, &record).unwrap();
+        assert_eq!(read_sign_state(&record).unwrap(), sign_state(6, 0, 1));
+    }
+}
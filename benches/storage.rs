@@ -0,0 +1,118 @@
+use std::time::{Duration, Instant};
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use namada::core::types::storage::Key;
+use namada::ledger::storage_api;
+use namada_apps::bench_utils::BenchShell;
+
+/// The numbers of keys in the state sizes that are being benchmarked
+const STATE_SIZES: [u64; 3] = [100, 1_000, 10_000];
+
+/// The length of the values written to storage in bytes
+const VALUE_LEN: usize = 32;
+
+/// Generate the given number of keys under the prefix
+fn generate_keys(prefix: &Key, size: u64) -> Vec<Key> {
+    (0..size)
+        .map(|ix| prefix.push(&ix.to_string()).unwrap())
+        .collect()
+}
+
+/// Generate random bytes for a value
+fn generate_value() -> Vec<u8> {
+    (0..VALUE_LEN).map(|_| rand::random()).collect()
+}
+
+// Benchmarks the commit of a block that modifies the given number of keys,
+// which includes the update of the merkle tree and the write to DB
+fn storage_commit(c: &mut Criterion) {
+    let mut group = c.benchmark_group("storage_commit");
+    let mut shell = BenchShell::default();
+
+    for size in STATE_SIZES {
+        let prefix = Key::parse(format!("bench/commit/{size}")).unwrap();
+        let keys = generate_keys(&prefix, size);
+
+        group.bench_function(format!("keys: {size}"), |b| {
+            b.iter_custom(|iters| {
+                let mut elapsed = Duration::ZERO;
+                for _ in 0..iters {
+                    for key in &keys {
+                        shell
+                            .wl_storage
+                            .write_log
+                            .protocol_write(key, generate_value())
+                            .unwrap();
+                    }
+                    let start = Instant::now();
+                    shell.wl_storage.commit_block().unwrap();
+                    elapsed += start.elapsed();
+                }
+                elapsed
+            })
+        });
+    }
+
+    group.finish();
+}
+
+// Benchmarks the update of the given number of keys in the merkle tree and the
+// recomputation of its root
+fn merkle_root(c: &mut Criterion) {
+    let mut group = c.benchmark_group("merkle_root");
+    let mut shell = BenchShell::default();
+
+    for size in STATE_SIZES {
+        let prefix = Key::parse(format!("bench/merkle/{size}")).unwrap();
+        let keys = generate_keys(&prefix, size);
+
+        group.bench_function(format!("keys: {size}"), |b| {
+            b.iter_batched(
+                || keys.iter().map(|_| generate_value()).collect::<Vec<_>>(),
+                |values| {
+                    let tree = &mut shell.wl_storage.storage.block.tree;
+                    for (key, value) in keys.iter().zip(values) {
+                        tree.update(key, value).unwrap();
+                    }
+                    tree.root()
+                },
+                criterion::BatchSize::SmallInput,
+            )
+        });
+    }
+
+    group.finish();
+}
+
+// Benchmarks the iteration over a prefix with the given number of committed
+// keys
+fn storage_iter_prefix(c: &mut Criterion) {
+    let mut group = c.benchmark_group("storage_iter_prefix");
+    let mut shell = BenchShell::default();
+
+    for size in STATE_SIZES {
+        let prefix = Key::parse(format!("bench/iter/{size}")).unwrap();
+        for key in generate_keys(&prefix, size) {
+            shell
+                .wl_storage
+                .write_log
+                .protocol_write(&key, generate_value())
+                .unwrap();
+        }
+        shell.wl_storage.commit_block().unwrap();
+
+        group.bench_function(format!("keys: {size}"), |b| {
+            b.iter(|| {
+                let iter =
+                    storage_api::iter_prefix_bytes(&shell.wl_storage, &prefix)
+                        .unwrap();
+                assert_eq!(iter.count() as u64, size);
+            })
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(storage, storage_commit, merkle_root, storage_iter_prefix);
+criterion_main!(storage);
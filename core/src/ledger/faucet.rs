@@ -0,0 +1,51 @@
+//! Testnet faucet library code.
+//!
+//! The faucet is an internal account that allows anyone to withdraw up to
+//! [`read_withdrawal_limit`] of any token in a single tx, without the need for
+//! a signature. The faucet is only enabled on chains that set the withdrawal
+//! limit at genesis.
+
+use crate::ledger::storage_api::{self, StorageRead, StorageWrite};
+use crate::types::address::{Address, InternalAddress};
+use crate::types::storage::{DbKeySeg, Key};
+
+/// The faucet internal address
+pub const ADDRESS: Address = Address::Internal(InternalAddress::Faucet);
+
+/// The storage sub-key of the faucet's withdrawal limit
+const WITHDRAWAL_LIMIT_KEY: &str = "withdrawal_limit";
+
+/// Obtain a storage key for the faucet's withdrawal limit
+pub fn withdrawal_limit_key() -> Key {
+    Key {
+        segments: vec![
+            DbKeySeg::AddressSeg(ADDRESS),
+            DbKeySeg::StringSeg(WITHDRAWAL_LIMIT_KEY.to_string()),
+        ],
+    }
+}
+
+/// Check if key is inside the faucet address space
+pub fn is_faucet_key(key: &Key) -> bool {
+    matches!(&key.segments[0], DbKeySeg::AddressSeg(addr) if addr == &ADDRESS)
+}
+
+/// Read the maximum number of whole tokens that can be withdrawn from the
+/// faucet in a single tx. Returns `None` when the faucet is not enabled.
+pub fn read_withdrawal_limit<S>(storage: &S) -> storage_api::Result<Option<u64>>
+where
+    S: StorageRead,
+{
+    storage.read(&withdrawal_limit_key())
+}
+
+/// Enable the faucet with the given withdrawal limit in whole tokens
+pub fn write_withdrawal_limit<S>(
+    storage: &mut S,
+    limit: u64,
+) -> storage_api::Result<()>
+where
+    S: StorageWrite,
+{
+    storage.write(&withdrawal_limit_key(), limit)
+}
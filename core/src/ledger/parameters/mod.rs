@@ -14,6 +14,7 @@ use crate::types::address::{Address, InternalAddress};
 use crate::types::chain::ProposalBytes;
 use crate::types::dec::Dec;
 use crate::types::hash::Hash;
+use crate::types::storage::{BlockHash, BlockHeight};
 use crate::types::time::DurationSecs;
 use crate::types::token;
 
@@ -67,6 +68,19 @@ pub struct Parameters {
     pub minimum_gas_price: BTreeMap<Address, token::Amount>,
 }
 
+/// A chain upgrade scheduled by governance. Nodes running an app version
+/// older than the plan's halt before processing the block at the upgrade
+/// height, so that operators can switch to the upgraded binary.
+#[derive(
+    Clone, Debug, PartialEq, Eq, BorshSerialize, BorshDeserialize, BorshSchema,
+)]
+pub struct UpgradePlan {
+    /// The height of the first block to be processed by the upgraded nodes
+    pub height: BlockHeight,
+    /// The app version of the upgraded nodes
+    pub app_version: u64,
+}
+
 /// Epoch duration. A new epoch begins as soon as both the `min_num_of_blocks`
 /// and `min_duration` have passed since the beginning of the current epoch.
 #[derive(
@@ -390,6 +404,46 @@ where
     Ok(gas_cost_table.get(token).map(|amount| amount.to_owned()))
 }
 
+/// Read the randomness beacon value of the current block, if any. The value is
+/// derived from the block hashes, so it is deterministic and can be verified
+/// by anyone, but it can be biased by the block proposer. Applications must
+/// not use it when that is a concern.
+pub fn read_block_randomness<S>(
+    storage: &S,
+) -> storage_api::Result<Option<Hash>>
+where
+    S: StorageRead,
+{
+    storage.read(&storage::get_block_randomness_key())
+}
+
+/// Update the randomness beacon value for a new block, by hashing the
+/// previous value with the new block's hash and height
+pub fn update_block_randomness<S>(
+    storage: &mut S,
+    block_hash: &BlockHash,
+    height: BlockHeight,
+) -> storage_api::Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    let prev = read_block_randomness(storage)?.unwrap_or_default();
+    let randomness = Hash::sha256(
+        [&prev.0[..], &block_hash.0[..], &height.0.to_be_bytes()[..]].concat(),
+    );
+    storage.write(&storage::get_block_randomness_key(), randomness)
+}
+
+/// Read the chain upgrade scheduled by governance, if any
+pub fn read_upgrade_plan<S>(
+    storage: &S,
+) -> storage_api::Result<Option<UpgradePlan>>
+where
+    S: StorageRead,
+{
+    storage.read(&storage::get_upgrade_plan_key())
+}
+
 /// Read all the parameters from storage. Returns the parameters and gas
 /// cost.
 pub fn read<S>(storage: &S) -> storage_api::Result<Parameters>
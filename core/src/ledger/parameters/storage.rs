@@ -44,6 +44,13 @@ struct Keys {
     fee_unshielding_gas_limit: &'static str,
     fee_unshielding_descriptions_limit: &'static str,
     max_signatures_per_transaction: &'static str,
+    // ========================================
+    // Protocol values
+    // ========================================
+    /// Sub-key for storing the randomness beacon value of the current block
+    block_randomness: &'static str,
+    /// Sub-key for storing the chain upgrade scheduled by governance, if any
+    upgrade_plan: &'static str,
 }
 
 /// Returns if the key is a parameter key.
@@ -188,3 +195,13 @@ pub fn get_gas_cost_key() -> Key {
 pub fn get_max_signatures_per_transaction_key() -> Key {
     get_max_signatures_per_transaction_key_at_addr(ADDRESS)
 }
+
+/// Storage key used for the randomness beacon value
+pub fn get_block_randomness_key() -> Key {
+    get_block_randomness_key_at_addr(ADDRESS)
+}
+
+/// Storage key used for the chain upgrade plan
+pub fn get_upgrade_plan_key() -> Key {
+    get_upgrade_plan_key_at_addr(ADDRESS)
+}
@@ -14,18 +14,20 @@ use super::merkle_tree::{
     base_tree_key_prefix, subtree_key_prefix, MerkleTreeStoresRead, StoreType,
 };
 use super::{
-    BlockStateRead, BlockStateWrite, DBIter, DBWriteBatch, Error, Result, DB,
+    address_tx_key, BlockStateRead, BlockStateWrite, DBIter, DBWriteBatch,
+    Error, Result, DB,
 };
 use crate::ledger::masp_conversions::ConversionState;
 use crate::ledger::replay_protection;
 use crate::ledger::storage::types::{self, KVBytes, PrefixIterator};
+use crate::types::address::Address;
 use crate::types::ethereum_events::Uint;
 use crate::types::ethereum_structs;
 use crate::types::hash::Hash;
 use crate::types::internal::TxQueue;
 use crate::types::storage::{
     BlockHeight, BlockResults, Epoch, EthEventsQueue, Header, Key, KeySeg,
-    KEY_SEGMENT_SEPARATOR,
+    TxIndex, KEY_SEGMENT_SEPARATOR,
 };
 use crate::types::time::DateTimeUtc;
 
@@ -42,10 +44,13 @@ pub struct MockDB(
 // safe.
 unsafe impl Sync for MockDB {}
 
-/// An in-memory write batch is not needed as it just updates values in memory.
-/// It's here to satisfy the storage interface.
+/// An in-memory write batch. Most of the batch writes update the values in
+/// memory directly, the others are held in here until the batch is executed.
 #[derive(Debug, Default)]
-pub struct MockDBWriteBatch;
+pub struct MockDBWriteBatch {
+    /// The pending writes of the batch
+    writes: BTreeMap<String, Vec<u8>>,
+}
 
 impl DB for MockDB {
     /// There is no cache for MockDB
@@ -485,7 +490,12 @@ impl DB for MockDB {
         value: impl AsRef<[u8]>,
     ) -> Result<i64> {
         // batch_write are directly committed
-        self.batch_write_subspace_val(&mut MockDBWriteBatch, height, key, value)
+        self.batch_write_subspace_val(
+            &mut MockDBWriteBatch::default(),
+            height,
+            key,
+            value,
+        )
     }
 
     fn delete_subspace_val(
@@ -494,16 +504,21 @@ impl DB for MockDB {
         key: &Key,
     ) -> Result<i64> {
         // batch_delete are directly committed
-        self.batch_delete_subspace_val(&mut MockDBWriteBatch, height, key)
+        self.batch_delete_subspace_val(
+            &mut MockDBWriteBatch::default(),
+            height,
+            key,
+        )
     }
 
     fn batch() -> Self::WriteBatch {
-        MockDBWriteBatch
+        MockDBWriteBatch::default()
     }
 
-    fn exec_batch(&mut self, _batch: Self::WriteBatch) -> Result<()> {
-        // Nothing to do - in MockDB, batch writes are committed directly from
-        // `batch_write_subspace_val` and `batch_delete_subspace_val`.
+    fn exec_batch(&mut self, batch: Self::WriteBatch) -> Result<()> {
+        // Most of the batch writes, e.g. from `batch_write_subspace_val` and
+        // `batch_delete_subspace_val`, are committed directly
+        self.0.borrow_mut().extend(batch.writes);
         Ok(())
     }
 
@@ -588,6 +603,27 @@ impl DB for MockDB {
         Ok(())
     }
 
+    fn prune_block_data(
+        &mut self,
+        _batch: &mut Self::WriteBatch,
+        from: BlockHeight,
+        to: BlockHeight,
+    ) -> Result<()> {
+        // The height key segments preserve the ordering of the heights
+        let in_range = |height: &str| {
+            from.raw().as_str() <= height && height < to.raw().as_str()
+        };
+        self.0.borrow_mut().retain(|key, _| {
+            let segments: Vec<&str> = key.splitn(3, '/').collect();
+            match segments[..] {
+                [height, "old" | "new", _] => !in_range(height),
+                ["results", height] => !in_range(height),
+                _ => true,
+            }
+        });
+        Ok(())
+    }
+
     fn read_bridge_pool_signed_nonce(
         &self,
         _height: BlockHeight,
@@ -626,6 +662,42 @@ impl DB for MockDB {
 
         Ok(())
     }
+
+    fn batch_write_address_tx(
+        &mut self,
+        batch: &mut Self::WriteBatch,
+        owner: &Address,
+        height: BlockHeight,
+        tx_index: TxIndex,
+        hash: &Hash,
+    ) -> Result<()> {
+        let key = format!(
+            "address_index/{owner}/{}",
+            address_tx_key(height, tx_index)
+        );
+        batch.writes.insert(key, types::encode(&(height, *hash)));
+        Ok(())
+    }
+
+    fn read_schema_version(&self) -> Result<Option<u64>> {
+        match self.0.borrow().get("schema_version") {
+            Some(bytes) => {
+                Ok(Some(types::decode(bytes).map_err(Error::CodingError)?))
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn batch_write_schema_version(
+        &mut self,
+        batch: &mut Self::WriteBatch,
+        version: u64,
+    ) -> Result<()> {
+        batch
+            .writes
+            .insert("schema_version".into(), types::encode(&version));
+        Ok(())
+    }
 }
 
 impl<'iter> DBIter<'iter> for MockDB {
@@ -651,6 +723,27 @@ impl<'iter> DBIter<'iter> for MockDB {
         MockPrefixIterator::new(MockIterator { prefix, iter }, stripped_prefix)
     }
 
+    fn iter_prefix_after(
+        &'iter self,
+        prefix: &Key,
+        start_after: Option<&Key>,
+    ) -> MockPrefixIterator {
+        let stripped_prefix = "subspace/".to_owned();
+        let prefix = if prefix == &Key::default() {
+            stripped_prefix.clone()
+        } else {
+            format!("{stripped_prefix}{prefix}/")
+        };
+        let mut db = self.0.borrow().clone();
+        let iter = match start_after {
+            // Skip to the first key after the given one
+            Some(key) => db.split_off(&format!("{stripped_prefix}{key}\0")),
+            None => db,
+        }
+        .into_iter();
+        MockPrefixIterator::new(MockIterator { prefix, iter }, stripped_prefix)
+    }
+
     fn iter_results(&'iter self) -> MockPrefixIterator {
         let stripped_prefix = "results/".to_owned();
         let prefix = "results".to_owned();
@@ -707,6 +800,13 @@ impl<'iter> DBIter<'iter> for MockDB {
         let iter = self.0.borrow().clone().into_iter();
         MockPrefixIterator::new(MockIterator { prefix, iter }, stripped_prefix)
     }
+
+    fn iter_address_txs(&'iter self, owner: &Address) -> MockPrefixIterator {
+        let stripped_prefix = format!("address_index/{owner}/");
+        let prefix = stripped_prefix.clone();
+        let iter = self.0.borrow().clone().into_iter();
+        MockPrefixIterator::new(MockIterator { prefix, iter }, stripped_prefix)
+    }
 }
 
 /// A prefix iterator base for the [`MockPrefixIterator`].
@@ -112,6 +112,12 @@ where
     pub eth_events_queue: EthEventsQueue,
     /// How many block heights in the past can the storage be queried
     pub storage_read_past_height_limit: Option<u64>,
+    /// How many block heights in the past are the subspace diffs and the
+    /// block results retained for. When not set, nothing is pruned.
+    pub block_data_retain_height: Option<u64>,
+    /// The version of the storage schema. It's persisted on every commit and
+    /// bumped when the storage migrations are applied.
+    pub schema_version: u64,
 }
 
 /// Last committed block
@@ -146,6 +152,10 @@ pub struct BlockStorage<H: StorageHasher> {
     pub epoch: Epoch,
     /// Results of applying transactions
     pub results: BlockResults,
+    /// The applied txs to be added to the index of txs by the addresses
+    /// whose validity predicates they triggered. They are written together
+    /// with the rest of the block on commit.
+    pub address_txs: Vec<(Address, TxIndex, Hash)>,
     /// Predecessor block epochs
     pub pred_epochs: Epochs,
 }
@@ -171,6 +181,14 @@ pub enum Error {
     NoMerkleTree { height: BlockHeight },
     #[error("Code hash error: {0}")]
     InvalidCodeHash(HashError),
+    #[error(
+        "The data of the block at the height {height} has been pruned, the \
+         oldest retained height is {oldest}"
+    )]
+    PrunedBlockData {
+        height: BlockHeight,
+        oldest: BlockHeight,
+    },
 }
 
 /// The block's state as stored in the database.
@@ -357,6 +375,15 @@ pub trait DB: std::fmt::Debug {
         pruned_epoch: Epoch,
     ) -> Result<()>;
 
+    /// Prune the subspace diffs and the block results at the heights in the
+    /// range `from..to`
+    fn prune_block_data(
+        &mut self,
+        batch: &mut Self::WriteBatch,
+        from: BlockHeight,
+        to: BlockHeight,
+    ) -> Result<()>;
+
     /// Read the signed nonce of Bridge Pool
     fn read_bridge_pool_signed_nonce(
         &self,
@@ -377,6 +404,28 @@ pub trait DB: std::fmt::Debug {
         batch: &mut Self::WriteBatch,
         key: &Key,
     ) -> Result<()>;
+
+    /// Batch write an entry of the index of the applied txs by the addresses
+    /// whose validity predicates they triggered. The index is kept per node
+    /// and is not a part of the ledger's state.
+    fn batch_write_address_tx(
+        &mut self,
+        batch: &mut Self::WriteBatch,
+        owner: &Address,
+        height: BlockHeight,
+        tx_index: TxIndex,
+        hash: &Hash,
+    ) -> Result<()>;
+
+    /// Read the version of the storage schema, if it has been written
+    fn read_schema_version(&self) -> Result<Option<u64>>;
+
+    /// Batch write the version of the storage schema
+    fn batch_write_schema_version(
+        &mut self,
+        batch: &mut Self::WriteBatch,
+        version: u64,
+    ) -> Result<()>;
 }
 
 /// A database prefix iterator.
@@ -392,6 +441,16 @@ pub trait DBIter<'iter> {
     /// ordered by the storage keys.
     fn iter_prefix(&'iter self, prefix: Option<&Key>) -> Self::PrefixIter;
 
+    /// WARNING: This only works for values that have been committed to DB.
+    ///
+    /// Read account subspace key value pairs with the given prefix from the DB,
+    /// ordered by the storage keys, starting after the given key, if any.
+    fn iter_prefix_after(
+        &'iter self,
+        prefix: &Key,
+        start_after: Option<&Key>,
+    ) -> Self::PrefixIter;
+
     /// Read results subspace key value pairs from the DB
     fn iter_results(&'iter self) -> Self::PrefixIter;
 
@@ -411,6 +470,18 @@ pub trait DBIter<'iter> {
 
     /// Read replay protection storage from the last block
     fn iter_replay_protection(&'iter self) -> Self::PrefixIter;
+
+    /// Read the index of the applied txs that triggered the validity predicate
+    /// of the given owner, from the most recent. The values are the encoded
+    /// heights and hashes of the txs.
+    fn iter_address_txs(&'iter self, owner: &Address) -> Self::PrefixIter;
+}
+
+/// The key of an entry in the index of the applied txs by address, relative to
+/// the owner. The height and the index of the tx are inverted, so that the
+/// keys of the most recent txs come first.
+pub fn address_tx_key(height: BlockHeight, tx_index: TxIndex) -> String {
+    format!("{:016x}/{:08x}", u64::MAX - height.0, u32::MAX - tx_index.0)
 }
 
 /// Atomic batch write.
@@ -436,6 +507,7 @@ where
             epoch: Epoch::default(),
             pred_epochs: Epochs::default(),
             results: BlockResults::default(),
+            address_txs: Vec::new(),
         };
         Storage::<D, H> {
             db: D::open(db_path, cache),
@@ -458,6 +530,8 @@ where
             ethereum_height: None,
             eth_events_queue: EthEventsQueue::default(),
             storage_read_past_height_limit,
+            block_data_retain_height: None,
+            schema_version: 0,
         }
     }
 
@@ -500,6 +574,10 @@ where
             self.tx_queue = tx_queue;
             self.ethereum_height = ethereum_height;
             self.eth_events_queue = eth_events_queue;
+            // A chain committed before the schema version was introduced is
+            // at the initial version
+            self.schema_version =
+                self.db.read_schema_version()?.unwrap_or_default();
             tracing::debug!("Loaded storage from DB");
         } else {
             tracing::info!("No state could be found");
@@ -560,6 +638,19 @@ where
         };
         self.db
             .add_block_to_batch(state, &mut batch, is_full_commit)?;
+        self.db
+            .batch_write_schema_version(&mut batch, self.schema_version)?;
+        for (owner, tx_index, hash) in
+            std::mem::take(&mut self.block.address_txs)
+        {
+            self.db.batch_write_address_tx(
+                &mut batch,
+                &owner,
+                self.block.height,
+                tx_index,
+                &hash,
+            )?;
+        }
         let header = self
             .header
             .take()
@@ -573,6 +664,8 @@ where
         if is_full_commit {
             // prune old merkle tree stores
             self.prune_merkle_tree_stores(&mut batch)?;
+            // prune the diffs and results of blocks that can no longer be read
+            self.prune_block_data(&mut batch)?;
         }
         self.db.exec_batch(batch)
     }
@@ -620,6 +713,10 @@ where
         if height == BlockHeight(0) || height >= self.get_last_block_height() {
             self.read(key)
         } else {
+            let oldest = self.get_oldest_block_data_height();
+            if height < oldest {
+                return Err(Error::PrunedBlockData { height, oldest });
+            }
             match self.db.read_subspace_val_with_height(
                 key,
                 height,
@@ -784,6 +881,8 @@ where
     /// Rebuild Merkle tree with diffs in the DB.
     /// Base tree and the specified `store_type` subtree is rebuilt.
     /// If `store_type` isn't given, full Merkle tree is restored.
+    /// Fails with `Error::PrunedBlockData` at the heights whose diffs may have
+    /// been pruned.
     pub fn get_merkle_tree(
         &self,
         height: BlockHeight,
@@ -795,6 +894,10 @@ where
         } else {
             height
         };
+        let oldest = self.get_oldest_block_data_height();
+        if oldest > BlockHeight(1) && height < oldest {
+            return Err(Error::PrunedBlockData { height, oldest });
+        }
 
         let epoch = self
             .block
@@ -1194,6 +1297,31 @@ where
         Ok(())
     }
 
+    // Prune the subspace diffs and the block results at the heights before the
+    // first height of the epoch of the oldest height whose data is retained.
+    // Values at a height are read from the diffs of the successor heights and
+    // the Merkle tree at a height is rebuilt from the stores at the first
+    // height of its epoch and the diffs after it, so reads and proofs at the
+    // heights before the oldest one fail with `Error::PrunedBlockData`.
+    // Without `block_data_retain_height`, nothing gets pruned. Use after
+    // updating self.last_block in the commit.
+    fn prune_block_data(&mut self, batch: &mut D::WriteBatch) -> Result<()> {
+        let oldest_height = self.get_oldest_block_data_height();
+        let prune_height = self
+            .block
+            .pred_epochs
+            .get_epoch(oldest_height)
+            .and_then(|epoch| {
+                self.block.pred_epochs.get_start_height_of_epoch(epoch)
+            })
+            .map_or(oldest_height, |height| height.min(oldest_height));
+        if prune_height <= BlockHeight(1) {
+            return Ok(());
+        }
+        self.db
+            .prune_block_data(batch, BlockHeight(1), prune_height)
+    }
+
     /// Get the height of the last committed block or 0 if no block has been
     /// committed yet. The first block is at height 1.
     pub fn get_last_block_height(&self) -> BlockHeight {
@@ -1203,6 +1331,17 @@ where
             .unwrap_or_default()
     }
 
+    /// Get the oldest height whose subspace diffs and block results are
+    /// retained, as limited by `block_data_retain_height`
+    pub fn get_oldest_block_data_height(&self) -> BlockHeight {
+        match self.block_data_retain_height {
+            Some(retain) if retain < self.get_last_block_height().0 => {
+                (self.get_last_block_height().0 - retain).into()
+            }
+            _ => BlockHeight(1),
+        }
+    }
+
     /// Get the oldest epoch where we can read a value
     pub fn get_oldest_epoch(&self) -> Epoch {
         let oldest_height = match self.storage_read_past_height_limit {
@@ -1324,6 +1463,7 @@ pub mod testing {
                 epoch: Epoch::default(),
                 pred_epochs: Epochs::default(),
                 results: BlockResults::default(),
+                address_txs: Vec::new(),
             };
             Self {
                 db: MockDB::default(),
@@ -1346,6 +1486,8 @@ pub mod testing {
                 ethereum_height: None,
                 eth_events_queue: EthEventsQueue::default(),
                 storage_read_past_height_limit: Some(1000),
+                block_data_retain_height: None,
+                schema_version: 0,
             }
         }
     }
@@ -1584,4 +1726,58 @@ mod tests {
             assert_eq!(wl_storage.storage.block.epoch, epoch_before.next());
         }
     }
+
+    /// Test that the values at the heights whose block data has been pruned
+    /// can no longer be read
+    #[test]
+    fn test_read_pruned_block_data() {
+        let mut storage = TestStorage::default();
+        storage.last_block = Some(LastBlock {
+            height: BlockHeight(10),
+            hash: BlockHash::default(),
+            time: DateTimeUtc::now(),
+        });
+        let key = Key::parse("key").unwrap();
+
+        // Without the retain height, nothing is pruned
+        assert_eq!(storage.get_oldest_block_data_height(), BlockHeight(1));
+        assert!(storage.read_with_height(&key, BlockHeight(1)).is_ok());
+
+        storage.block_data_retain_height = Some(3);
+        assert_eq!(storage.get_oldest_block_data_height(), BlockHeight(7));
+        assert!(matches!(
+            storage.read_with_height(&key, BlockHeight(6)),
+            Err(Error::PrunedBlockData {
+                height: BlockHeight(6),
+                oldest: BlockHeight(7),
+            })
+        ));
+        assert!(storage.read_with_height(&key, BlockHeight(7)).is_ok());
+
+        // A retain height beyond the last height doesn't prune anything
+        storage.block_data_retain_height = Some(10);
+        assert_eq!(storage.get_oldest_block_data_height(), BlockHeight(1));
+    }
+
+    /// Test that the schema version is only written when the batch of a
+    /// committed block is executed
+    #[test]
+    fn test_commit_schema_version() {
+        let mut storage = TestStorage::default();
+        let mut batch = TestStorage::batch();
+        storage
+            .db
+            .batch_write_schema_version(&mut batch, 1)
+            .unwrap();
+        assert_eq!(storage.db.read_schema_version().unwrap(), None);
+        storage.exec_batch(batch).unwrap();
+        assert_eq!(storage.db.read_schema_version().unwrap(), Some(1));
+
+        storage.schema_version = 2;
+        storage
+            .begin_block(BlockHash::default(), BlockHeight(1))
+            .unwrap();
+        storage.commit_block(TestStorage::batch()).unwrap();
+        assert_eq!(storage.db.read_schema_version().unwrap(), Some(2));
+    }
 }
@@ -3,6 +3,8 @@
 
 use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 
+use borsh::BorshDeserialize;
+use borsh_ext::BorshSerializeExt;
 use itertools::Itertools;
 use thiserror::Error;
 
@@ -13,6 +15,9 @@ use crate::ledger::gas::{
 use crate::ledger::replay_protection::{all_key, last_key};
 use crate::ledger::storage::traits::StorageHasher;
 use crate::ledger::storage::Storage;
+use crate::ledger::storage_api::account::{
+    storage_usage_key, storage_usage_owner,
+};
 use crate::types::address::{Address, EstablishedAddressGen, InternalAddress};
 use crate::types::hash::Hash;
 use crate::types::ibc::IbcEvent;
@@ -20,6 +25,7 @@ use crate::types::storage;
 use crate::types::token::{
     is_any_minted_balance_key, is_any_minter_key, is_any_token_balance_key,
 };
+use crate::types::transaction::TxEvent;
 
 #[allow(missing_docs)]
 #[derive(Error, Debug)]
@@ -101,6 +107,8 @@ pub struct WriteLog {
     tx_precommit_write_log: HashMap<storage::Key, StorageModification>,
     /// The IBC events for the current transaction
     ibc_events: BTreeSet<IbcEvent>,
+    /// The custom events emitted by the current transaction
+    events: Vec<TxEvent>,
     /// Storage modifications for the replay protection storage, always
     /// committed regardless of the result of the transaction
     replay_protection: HashMap<Hash, ReProtStorageModification>,
@@ -130,6 +138,7 @@ impl Default for WriteLog {
             tx_write_log: HashMap::with_capacity(100),
             tx_precommit_write_log: HashMap::with_capacity(100),
             ibc_events: BTreeSet::new(),
+            events: Vec::new(),
             replay_protection: HashMap::with_capacity(1_000),
         }
     }
@@ -383,6 +392,18 @@ impl WriteLog {
         len as u64 * MEMORY_ACCESS_GAS_PER_BYTE
     }
 
+    /// Add a custom event of the current transaction and return the gas cost.
+    pub fn emit_event(&mut self, event: TxEvent) -> u64 {
+        let len = event
+            .attributes
+            .iter()
+            .fold(event.event_type.len(), |acc, (k, v)| {
+                acc + k.len() + v.len()
+            });
+        self.events.push(event);
+        len as u64 * MEMORY_ACCESS_GAS_PER_BYTE
+    }
+
     /// Get the storage keys changed and accounts keys initialized in the
     /// current transaction. The account keys point to the validity predicates
     /// of the newly created accounts. The keys in the precommit are not
@@ -442,6 +463,11 @@ impl WriteLog {
         std::mem::take(&mut self.ibc_events)
     }
 
+    /// Take the custom events of the current transaction
+    pub fn take_events(&mut self) -> Vec<TxEvent> {
+        std::mem::take(&mut self.events)
+    }
+
     /// Get the IBC event of the current transaction
     pub fn get_ibc_events(&self) -> &BTreeSet<IbcEvent> {
         &self.ibc_events
@@ -476,6 +502,7 @@ impl WriteLog {
 
         self.block_write_log.extend(tx_precommit_write_log);
         self.take_ibc_events();
+        self.take_events();
     }
 
     /// Drop the current transaction's write log and precommit when it's
@@ -484,6 +511,7 @@ impl WriteLog {
     pub fn drop_tx(&mut self) {
         self.tx_precommit_write_log.clear();
         self.tx_write_log.clear();
+        self.events.clear();
     }
 
     /// Drop the current transaction's write log but keep the precommit one.
@@ -507,26 +535,68 @@ impl WriteLog {
             + for<'iter> ledger::storage::DBIter<'iter>,
         H: StorageHasher,
     {
+        // The changes of the storage usage of the accounts in this block
+        let mut usage_diffs: BTreeMap<Address, i64> = BTreeMap::new();
         for (key, entry) in self.block_write_log.iter() {
-            match entry {
-                StorageModification::Write { value } => {
-                    storage
-                        .batch_write_subspace_val(batch, key, value.clone())
-                        .map_err(Error::StorageError)?;
-                }
-                StorageModification::Delete => {
-                    storage
-                        .batch_delete_subspace_val(batch, key)
-                        .map_err(Error::StorageError)?;
-                }
-                StorageModification::InitAccount { vp_code_hash } => {
-                    storage
-                        .batch_write_subspace_val(batch, key, *vp_code_hash)
-                        .map_err(Error::StorageError)?;
-                }
+            let owner = storage_usage_owner(key);
+            let existed = match owner {
+                Some(_) => storage.has_key(key).map_err(Error::StorageError)?.0,
+                None => false,
+            };
+            let size_diff = match entry {
+                StorageModification::Write { value } => storage
+                    .batch_write_subspace_val(batch, key, value.clone())
+                    .map_err(Error::StorageError)?,
+                StorageModification::Delete => -storage
+                    .batch_delete_subspace_val(batch, key)
+                    .map_err(Error::StorageError)?,
+                StorageModification::InitAccount { vp_code_hash } => storage
+                    .batch_write_subspace_val(batch, key, *vp_code_hash)
+                    .map_err(Error::StorageError)?,
                 // temporary value isn't persisted
-                StorageModification::Temp { .. } => {}
+                StorageModification::Temp { .. } => continue,
+            };
+            if let Some(owner) = owner {
+                let exists = !matches!(entry, StorageModification::Delete);
+                let key_diff = match (existed, exists) {
+                    (false, true) => key.len() as i64,
+                    (true, false) => -(key.len() as i64),
+                    _ => 0,
+                };
+                *usage_diffs.entry(owner.clone()).or_default() +=
+                    size_diff + key_diff;
+            }
+        }
+        for (owner, diff) in usage_diffs {
+            if diff == 0 {
+                continue;
             }
+            let usage_key = storage_usage_key(&owner);
+            // The counter may have been recounted in this block by a storage
+            // migration, which takes precedence over the committed one
+            let usage = match self.block_write_log.get(&usage_key) {
+                Some(StorageModification::Write { value }) => {
+                    Some(value.clone())
+                }
+                Some(StorageModification::Delete) => None,
+                _ => storage.read(&usage_key).map_err(Error::StorageError)?.0,
+            };
+            let usage = match usage {
+                Some(bytes) => u64::try_from_slice(&bytes).map_err(|e| {
+                    Error::StorageError(
+                        ledger::storage::Error::BorshCodingError(e),
+                    )
+                })?,
+                None => 0,
+            };
+            let usage = usage.saturating_add_signed(diff);
+            storage
+                .batch_write_subspace_val(
+                    batch,
+                    &usage_key,
+                    usage.serialize_to_vec(),
+                )
+                .map_err(Error::StorageError)?;
         }
 
         for (hash, entry) in self.replay_protection.iter() {
@@ -730,6 +800,7 @@ mod tests {
 
     use super::*;
     use crate::types::hash::Hash;
+    use crate::types::storage::KeySeg;
     use crate::types::{address, storage};
 
     #[test]
@@ -950,6 +1021,82 @@ mod tests {
         assert_eq!(value, None);
     }
 
+    #[test]
+    fn test_emit_event() {
+        let mut write_log = WriteLog::default();
+        let event = |event_type: &str| TxEvent {
+            event_type: event_type.to_string(),
+            attributes: [("key".to_string(), "value".to_string())].into(),
+        };
+
+        // the events are kept in the order of their emission
+        let gas = write_log.emit_event(event("second"));
+        assert!(gas > 0);
+        write_log.emit_event(event("first"));
+        assert_eq!(
+            write_log.take_events(),
+            vec![event("second"), event("first")]
+        );
+        assert!(write_log.take_events().is_empty());
+
+        // the events of a dropped tx are discarded
+        write_log.emit_event(event("dropped"));
+        write_log.drop_tx();
+        assert!(write_log.take_events().is_empty());
+    }
+
+    #[test]
+    fn test_commit_storage_usage() {
+        let mut storage =
+            crate::ledger::storage::testing::TestStorage::default();
+        let mut write_log = WriteLog::default();
+        let mut batch = crate::ledger::storage::testing::TestStorage::batch();
+        let owner = address::testing::established_address_1();
+        let usage = |storage: &crate::ledger::storage::testing::TestStorage| {
+            let (value, _) = storage
+                .read(&storage_usage_key(&owner))
+                .expect("read failed");
+            value.map_or(0, |bytes| u64::try_from_slice(&bytes).unwrap())
+        };
+
+        let key1 = storage::Key::from(owner.to_db_key())
+            .push(&"key1".to_owned())
+            .unwrap();
+        let key2 = storage::Key::from(owner.to_db_key())
+            .push(&"key2".to_owned())
+            .unwrap();
+        let balance_key =
+            crate::types::token::balance_key(&address::nam(), &owner);
+        let other_key = storage::Key::parse("other").unwrap();
+
+        // keys and values of the account and its balances are counted
+        let val1 = "value1".as_bytes().to_vec();
+        write_log.write(&key1, val1.clone()).unwrap();
+        write_log.write(&key2, val1.clone()).unwrap();
+        write_log.write(&balance_key, val1.clone()).unwrap();
+        write_log.write(&other_key, val1.clone()).unwrap();
+        write_log.commit_tx();
+        write_log
+            .commit_block(&mut storage, &mut batch)
+            .expect("commit failed");
+        let expected = (key1.len() + key2.len() + balance_key.len()) as u64
+            + 3 * val1.len() as u64;
+        assert_eq!(usage(&storage), expected);
+
+        // an update only counts the change of the value's size, a delete
+        // removes both the key and the value
+        let val2 = "val2".as_bytes().to_vec();
+        write_log.write(&key1, val2.clone()).unwrap();
+        write_log.delete(&key2).unwrap();
+        write_log.commit_tx();
+        write_log
+            .commit_block(&mut storage, &mut batch)
+            .expect("commit failed");
+        let expected = (key1.len() + balance_key.len()) as u64
+            + (val1.len() + val2.len()) as u64;
+        assert_eq!(usage(&storage), expected);
+    }
+
     #[test]
     fn test_replay_protection_commit() {
         let mut storage =
@@ -1,10 +1,11 @@
 //! Cryptographic signature keys storage API
 
 use super::*;
+use crate::ledger::parameters;
 use crate::types::account::AccountPublicKeysMap;
 use crate::types::address::Address;
 use crate::types::key::*;
-use crate::types::storage::Key;
+use crate::types::storage::{DbKeySeg, Key, KeySeg};
 
 /// Init the subspace of a new account
 pub fn init_account_storage<S>(
@@ -105,3 +106,52 @@ where
     }
     Ok(())
 }
+
+/// The storage key segment of the per-account storage usage counters
+pub const STORAGE_USAGE_KEY_SEGMENT: &str = "storage_usage";
+
+/// Get the key prefix of the per-account storage usage counters
+pub fn storage_usage_prefix() -> Key {
+    Key::from(parameters::ADDRESS.to_db_key())
+        .push(&STORAGE_USAGE_KEY_SEGMENT.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Get the key of the counter of the bytes held in storage by an account.
+/// The counters live under the parameters address, whose VP rejects writes
+/// to unknown keys, so they can only be updated by the protocol when a block
+/// is committed.
+pub fn storage_usage_key(owner: &Address) -> Key {
+    storage_usage_prefix()
+        .push(&owner.to_db_key())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Get the account whose storage usage is charged for the given key, if any.
+/// These are the keys in the sub-space of a non-internal account and the
+/// multitoken balances that it owns.
+pub fn storage_usage_owner(key: &Key) -> Option<&Address> {
+    if let Some([_token, owner]) =
+        crate::types::token::is_any_token_balance_key(key)
+    {
+        return Some(owner);
+    }
+    match key.segments.first() {
+        Some(DbKeySeg::AddressSeg(addr))
+            if !matches!(addr, Address::Internal(_)) =>
+        {
+            Some(addr)
+        }
+        _ => None,
+    }
+}
+
+/// Get the number of bytes held in storage by an account, counting both the
+/// keys and their values. The counter is updated when a block is committed,
+/// so it doesn't include the changes of the current block.
+pub fn storage_usage<S>(storage: &S, owner: &Address) -> Result<u64>
+where
+    S: StorageRead,
+{
+    Ok(storage.read(&storage_usage_key(owner))?.unwrap_or_default())
+}
@@ -179,10 +179,59 @@ where
         }
     };
 
-    let total_supply = read_total_supply(&*storage, source)?;
+    let total_supply = read_total_supply(&*storage, token)?;
     let new_total_supply =
         total_supply.checked_sub(amount_to_burn).unwrap_or_default();
 
     let total_supply_key = token::minted_balance_key(token);
     storage.write(&total_supply_key, new_total_supply)
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::ledger::storage::testing::TestWlStorage;
+    use crate::types::address::testing::{
+        established_address_1, established_address_2, established_address_3,
+    };
+
+    /// Test that burning tokens decreases the total supply of the token by
+    /// the burned amount
+    #[test]
+    fn test_burn_total_supply() {
+        let mut storage = TestWlStorage::default();
+        let token = established_address_1();
+        let alice = established_address_2();
+        let bob = established_address_3();
+
+        credit_tokens(&mut storage, &token, &alice, Amount::from_u64(100))
+            .unwrap();
+        credit_tokens(&mut storage, &token, &bob, Amount::from_u64(50))
+            .unwrap();
+        assert_eq!(
+            read_total_supply(&storage, &token).unwrap(),
+            Amount::from_u64(150)
+        );
+
+        burn(&mut storage, &token, &alice, Amount::from_u64(30)).unwrap();
+        assert_eq!(
+            read_balance(&storage, &token, &alice).unwrap(),
+            Amount::from_u64(70)
+        );
+        assert_eq!(
+            read_total_supply(&storage, &token).unwrap(),
+            Amount::from_u64(120)
+        );
+
+        // Burning more than the balance only burns the balance
+        burn(&mut storage, &token, &bob, Amount::from_u64(80)).unwrap();
+        assert_eq!(
+            read_balance(&storage, &token, &bob).unwrap(),
+            Amount::zero()
+        );
+        assert_eq!(
+            read_total_supply(&storage, &token).unwrap(),
+            Amount::from_u64(70)
+        );
+    }
+}
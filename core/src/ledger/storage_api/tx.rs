@@ -1,8 +1,11 @@
 //! Tx storage_api functions
 
 use super::StorageRead;
-use crate::ledger::parameters::storage::get_max_tx_bytes_key;
+use crate::ledger::parameters::storage::{
+    get_max_tx_bytes_key, get_tx_whitelist_storage_key,
+};
 use crate::ledger::storage_api;
+use crate::proto::{Section, Tx};
 
 /// Validate the size of a tx.
 pub fn validate_tx_bytes<S>(
@@ -17,3 +20,24 @@ where
         .expect("The max tx bytes param should be present in storage");
     Ok(tx_size <= max_tx_bytes as usize)
 }
+
+/// Check that the code of a tx is allowed by the tx whitelist parameter. Any
+/// code is allowed when the whitelist is empty.
+pub fn is_tx_whitelisted<S>(storage: &S, tx: &Tx) -> storage_api::Result<bool>
+where
+    S: StorageRead,
+{
+    let whitelist: Vec<String> = storage
+        .read(&get_tx_whitelist_storage_key())?
+        .unwrap_or_default();
+    if whitelist.is_empty() {
+        return Ok(true);
+    }
+    let code_hash = tx
+        .get_section(tx.code_sechash())
+        .and_then(|section| Section::code_sec(section.as_ref()))
+        .map(|section| section.code.hash());
+    Ok(code_hash.map_or(false, |hash| {
+        whitelist.contains(&hash.to_string().to_lowercase())
+    }))
+}
@@ -1,6 +1,8 @@
 //! Transaction environment contains functions that can be called from
 //! inside a tx.
 
+use std::collections::BTreeMap;
+
 use borsh::BorshSerialize;
 
 use crate::ledger::storage_api::{self, StorageRead, StorageWrite};
@@ -58,6 +60,15 @@ pub trait TxEnv: StorageRead + StorageWrite {
         event: &IbcEvent,
     ) -> Result<(), storage_api::Error>;
 
+    /// Emit a custom event with the given type and attributes, e.g. to signal
+    /// an outcome of the transaction to indexers. On multiple calls, the
+    /// events are added in the order of their emission.
+    fn emit_event(
+        &mut self,
+        event_type: impl AsRef<str>,
+        attributes: BTreeMap<String, String>,
+    ) -> Result<(), storage_api::Error>;
+
     /// Request to charge the provided amount of gas for the current transaction
     fn charge_gas(&mut self, used_gas: u64) -> Result<(), storage_api::Error>;
 
@@ -1275,6 +1275,16 @@ pub struct PrefixValue {
     pub value: Vec<u8>,
 }
 
+/// A page of a storage prefix iterator.
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize, BorshSchema)]
+pub struct PrefixPage {
+    /// The key-value pairs of this page
+    pub values: Vec<PrefixValue>,
+    /// The key of the last value of this page, after which the next page
+    /// starts, if there are any values left
+    pub last_key: Option<Key>,
+}
+
 /// Container of all Ethereum event queues.
 #[derive(Default, Debug, BorshSerialize, BorshDeserialize)]
 pub struct EthEventsQueue {
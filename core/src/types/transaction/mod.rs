@@ -16,7 +16,7 @@ pub mod protocol;
 /// wrapper txs with encrypted payloads
 pub mod wrapper;
 
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet};
 use std::fmt::{self, Display};
 use std::str::FromStr;
 
@@ -86,6 +86,8 @@ pub enum ResultCode {
     TooLarge = 14,
     /// Decrypted tx is expired
     ExpiredDecryptedTx = 15,
+    /// Tx code is not in the tx whitelist
+    TxNotWhitelisted = 16,
     // =========================================================================
     // WARN: These codes shouldn't be changed between version!
 }
@@ -102,7 +104,7 @@ impl ResultCode {
             InvalidTx | InvalidSig | InvalidOrder | ExtraTxs
             | Undecryptable | AllocationError | ReplayTx | InvalidChainId
             | ExpiredTx | TxGasLimit | FeeError | InvalidVoteExtension
-            | TooLarge => false,
+            | TooLarge | TxNotWhitelisted => false,
         }
     }
 
@@ -187,6 +189,9 @@ pub struct TxResult {
     pub ibc_events: BTreeSet<IbcEvent>,
     /// Ethereum bridge events emitted by the transaction
     pub eth_bridge_events: BTreeSet<EthBridgeEvent>,
+    /// Custom events emitted by the transaction's code, in the order of
+    /// their emission
+    pub events: Vec<TxEvent>,
 }
 
 impl TxResult {
@@ -196,6 +201,25 @@ impl TxResult {
     }
 }
 
+/// A custom event emitted by a transaction's code, e.g. to signal the
+/// outcome of a dapp's operation to indexers
+#[derive(
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    Serialize,
+    Deserialize,
+)]
+pub struct TxEvent {
+    /// The type of the event
+    pub event_type: String,
+    /// The attributes of the event
+    pub attributes: BTreeMap<String, String>,
+}
+
 /// Result of checking a transaction with validity predicates
 // TODO derive BorshSchema after <https://github.com/near/borsh-rs/issues/82>
 #[derive(
@@ -1143,7 +1143,7 @@ mod tests {
         init_balance(&mut wl_storage, &pending_transfers);
         wl_storage
             .storage
-            .commit_block(MockDBWriteBatch)
+            .commit_block(MockDBWriteBatch::default())
             .expect("Test failed");
         // pending transfers time out
         wl_storage.storage.block.height += 10 + 1;
@@ -1169,7 +1169,7 @@ mod tests {
             .expect("Test failed");
         wl_storage
             .storage
-            .commit_block(MockDBWriteBatch)
+            .commit_block(MockDBWriteBatch::default())
             .expect("Test failed");
         wl_storage.storage.block.height += 1;
 
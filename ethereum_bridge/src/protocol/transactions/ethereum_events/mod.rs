@@ -710,7 +710,10 @@ mod tests {
         let prev_keys = vote_tallies::Keys::from(&event);
 
         // commit then update the epoch
-        wl_storage.storage.commit_block(MockDBWriteBatch).unwrap();
+        wl_storage
+            .storage
+            .commit_block(MockDBWriteBatch::default())
+            .unwrap();
         let unbonding_len =
             namada_proof_of_stake::storage::read_pos_params(&wl_storage)
                 .expect("Test failed")
@@ -847,7 +850,10 @@ mod tests {
         });
 
         // commit then update the epoch
-        wl_storage.storage.commit_block(MockDBWriteBatch).unwrap();
+        wl_storage
+            .storage
+            .commit_block(MockDBWriteBatch::default())
+            .unwrap();
         let unbonding_len =
             namada_proof_of_stake::storage::read_pos_params(&wl_storage)
                 .expect("Test failed")
@@ -1268,6 +1268,15 @@ pub struct QueryAccount<C: NamadaTypes = SdkTypes> {
     pub owner: C::Address,
 }
 
+/// Query the storage usage of an account
+#[derive(Clone, Debug)]
+pub struct QueryStorageUsage<C: NamadaTypes = SdkTypes> {
+    /// Common query args
+    pub query: Query<C>,
+    /// Address of an owner
+    pub owner: C::Address,
+}
+
 /// Query token balance(s)
 #[derive(Clone, Debug)]
 pub struct QueryBalance<C: NamadaTypes = SdkTypes> {
@@ -1755,6 +1764,9 @@ pub struct SignTx<C: NamadaTypes = SdkTypes> {
     pub tx_data: C::Data,
     /// The account address
     pub owner: C::Address,
+    /// The public keys of the owner's account, in the account's order. When
+    /// given, the tx is signed without querying the account from a node.
+    pub account_public_keys: Vec<C::PublicKey>,
 }
 
 /// Query PoS commission rate
@@ -1839,6 +1851,9 @@ pub struct Tx<C: NamadaTypes = SdkTypes> {
     pub force: bool,
     /// Do not wait for the transaction to be added to the blockchain
     pub broadcast_only: bool,
+    /// The maximum time to wait for the transaction to be accepted and
+    /// applied. A default is used when not set.
+    pub wait_timeout: Option<StdDuration>,
     /// The address of the ledger node as host:port
     pub ledger_address: C::TendermintAddress,
     /// If any new account is initialized by the tx, use the given alias to
@@ -1915,6 +1930,14 @@ pub trait TxBuilder<C: NamadaTypes>: Sized {
             ..x
         })
     }
+    /// The maximum time to wait for the transaction to be accepted and
+    /// applied
+    fn wait_timeout(self, wait_timeout: StdDuration) -> Self {
+        self.tx(|x| Tx {
+            wait_timeout: Some(wait_timeout),
+            ..x
+        })
+    }
     /// The address of the ledger node as host:port
     fn ledger_address(self, ledger_address: C::TendermintAddress) -> Self {
         self.tx(|x| Tx {
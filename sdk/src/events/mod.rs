@@ -10,7 +10,8 @@ use std::str::FromStr;
 use borsh::{BorshDeserialize, BorshSerialize};
 use namada_core::types::ethereum_structs::{BpTransferStatus, EthBridgeEvent};
 use namada_core::types::ibc::IbcEvent;
-use namada_core::types::transaction::TxType;
+use namada_core::types::storage::{BlockHeight, Epoch};
+use namada_core::types::transaction::{TxEvent, TxType};
 use serde_json::Value;
 
 // use crate::ledger::governance::utils::ProposalEvent;
@@ -86,6 +87,11 @@ pub enum EventType {
     PgfPayment,
     /// Ethereum Bridge event
     EthereumBridge,
+    /// A new epoch has begun
+    NewEpoch,
+    /// A custom event emitted by a transaction's code, with the type given by
+    /// the code in its `kind` attribute
+    Custom,
 }
 
 impl Display for EventType {
@@ -97,6 +103,8 @@ impl Display for EventType {
             EventType::Proposal => write!(f, "proposal"),
             EventType::PgfPayment => write!(f, "pgf_payment"),
             EventType::EthereumBridge => write!(f, "ethereum_bridge"),
+            EventType::NewEpoch => write!(f, "new_epoch"),
+            EventType::Custom => write!(f, "custom"),
         }?;
         Ok(())
     }
@@ -118,6 +126,8 @@ impl FromStr for EventType {
                 Ok(EventType::Ibc("write_acknowledgement".to_string()))
             }
             "ethereum_bridge" => Ok(EventType::EthereumBridge),
+            "new_epoch" => Ok(EventType::NewEpoch),
+            "custom" => Ok(EventType::Custom),
             _ => Err(EventError::InvalidEventType),
         }
     }
@@ -166,6 +176,19 @@ impl Event {
         event
     }
 
+    /// Creates a new event signalling that a new epoch has begun in the block
+    /// at the given height
+    pub fn new_epoch_event(epoch: Epoch, height: BlockHeight) -> Self {
+        let mut event = Event {
+            event_type: EventType::NewEpoch,
+            level: EventLevel::Block,
+            attributes: HashMap::new(),
+        };
+        event["epoch"] = epoch.to_string();
+        event["height"] = height.to_string();
+        event
+    }
+
     /// Check if the events keys contains a given string
     pub fn contains_key(&self, key: &str) -> bool {
         self.attributes.contains_key(key)
@@ -205,6 +228,19 @@ impl From<IbcEvent> for Event {
     }
 }
 
+impl From<TxEvent> for Event {
+    fn from(tx_event: TxEvent) -> Self {
+        let mut event = Self {
+            event_type: EventType::Custom,
+            level: EventLevel::Tx,
+            attributes: tx_event.attributes.into_iter().collect(),
+        };
+        // The type given by the tx code cannot be spoofed by its attributes
+        event["kind"] = tx_event.event_type;
+        event
+    }
+}
+
 /// Convert our custom event into the necessary tendermint proto type
 impl From<Event> for crate::tendermint_proto::v0_37::abci::Event {
     fn from(event: Event) -> Self {
@@ -126,6 +126,7 @@ pub trait Namada: Sized + MaybeSync + MaybeSend {
             output_folder: None,
             force: false,
             broadcast_only: false,
+            wait_timeout: None,
             ledger_address: (),
             initialized_account_alias: None,
             wallet_alias_force: false,
@@ -646,6 +647,7 @@ where
                 output_folder: None,
                 force: false,
                 broadcast_only: false,
+                wait_timeout: None,
                 ledger_address: (),
                 initialized_account_alias: None,
                 wallet_alias_force: false,
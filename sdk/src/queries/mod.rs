@@ -6,8 +6,11 @@ use namada_core::ledger::storage::traits::StorageHasher;
 use namada_core::ledger::storage::{DBIter, DB};
 use namada_core::ledger::storage_api;
 use namada_core::types::storage::BlockHeight;
-pub use shell::Shell;
 use shell::SHELL;
+pub use shell::{
+    AddressTx, AllocRejections, BlockStats, Shell, ALLOC_REJECTIONS_PATH,
+    BLOCK_STATS_PATH,
+};
 pub use types::{
     EncodedResponseQuery, Error, RequestCtx, RequestQuery, ResponseQuery,
     Router,
@@ -1,21 +1,23 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 
 pub(super) mod eth_bridge;
 
-use borsh::BorshDeserialize;
+use borsh::{BorshDeserialize, BorshSerialize};
 use borsh_ext::BorshSerializeExt;
+use itertools::Itertools;
 use masp_primitives::asset_type::AssetType;
 use masp_primitives::merkle_tree::MerklePath;
 use masp_primitives::sapling::Node;
 use namada_core::hints;
+use namada_core::ledger::gas::Gas;
 use namada_core::ledger::storage::traits::StorageHasher;
-use namada_core::ledger::storage::{DBIter, LastBlock, DB};
+use namada_core::ledger::storage::{DBIter, LastBlock, Storage, DB};
 use namada_core::ledger::storage_api::{self, ResultExt, StorageRead};
 use namada_core::types::account::{Account, AccountPublicKeysMap};
 use namada_core::types::address::Address;
 use namada_core::types::hash::Hash;
 use namada_core::types::storage::{
-    self, BlockHeight, BlockResults, Epoch, KeySeg, PrefixValue,
+    self, BlockHeight, BlockResults, Epoch, KeySeg, PrefixPage, PrefixValue,
 };
 use namada_core::types::token::MaspDenom;
 #[cfg(any(test, feature = "async-client"))]
@@ -45,6 +47,72 @@ type Conversion = (
     MerklePath<Node>,
 );
 
+/// Gas and space utilization statistics of a committed block
+#[derive(Clone, Debug, Default, BorshSerialize, BorshDeserialize)]
+pub struct BlockStats {
+    /// Height of the block
+    pub height: BlockHeight,
+    /// Number of txs in the block, by tx type
+    pub txs_by_type: BTreeMap<String, u64>,
+    /// Gas used by the block's txs, by tx type
+    pub gas_used_by_type: BTreeMap<String, Gas>,
+    /// Total gas used by the block's txs
+    pub gas_used: Gas,
+    /// The max block gas parameter
+    pub max_block_gas: Gas,
+    /// Space used by the block's txs, in bytes
+    pub bytes_used: u64,
+    /// The max proposal bytes parameter
+    pub max_proposal_bytes: u64,
+}
+
+/// The query path of the gas and space utilization of the recently committed
+/// blocks. The [`BlockStats`] are only kept in memory by the ledger shell,
+/// which serves this path outside of the queries router.
+pub const BLOCK_STATS_PATH: &str = "/shell/block_stats";
+
+/// Counters of the txs that were dropped from the block proposals built by a
+/// node because the block space allocator rejected them
+#[derive(
+    Clone, Debug, Default, PartialEq, Eq, BorshSerialize, BorshDeserialize,
+)]
+pub struct AllocRejections {
+    /// Number of block proposals built by the node
+    pub proposals: u64,
+    /// Number of block proposals from which at least one tx was dropped
+    pub constrained_proposals: u64,
+    /// Number of txs dropped because their bin was full, by bin
+    pub rejected: BTreeMap<String, u64>,
+    /// Number of txs dropped because they're larger than their bin, by bin
+    pub overflowed: BTreeMap<String, u64>,
+    /// Resource left in a bin when a tx was last rejected from it, by bin
+    pub bin_resource_left: BTreeMap<String, u64>,
+    /// Total size of the dropped txs, in bytes
+    pub bytes_dropped: u64,
+}
+
+/// The query path of the [`AllocRejections`] of a node. They're only kept in
+/// memory by the ledger shell, which serves this path outside of the queries
+/// router.
+pub const ALLOC_REJECTIONS_PATH: &str = "/shell/alloc_rejections";
+
+impl AllocRejections {
+    /// The total number of dropped txs
+    pub fn dropped_txs(&self) -> u64 {
+        self.rejected.values().chain(self.overflowed.values()).sum()
+    }
+}
+
+/// A tx that triggered the validity predicate of an address, as recorded in
+/// the node's index of the applied txs
+#[derive(Clone, Debug, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub struct AddressTx {
+    /// Height of the block in which the tx was applied
+    pub height: BlockHeight,
+    /// Hash of the tx
+    pub hash: Hash,
+}
+
 router! {SHELL,
     // Shell provides storage read access, block metadata and can dry-run a tx
 
@@ -70,10 +138,23 @@ router! {SHELL,
     // Dry run a transaction
     ( "dry_run_tx" ) -> TxResult = (with_options dry_run_tx),
 
+    // Dry run an ordered bundle of dependent transactions
+    ( "dry_run_tx_bundle" ) -> Vec<TxResult> = (with_options dry_run_tx_bundle),
+
+    // The applied txs that touched an address, from the most recent
+    ( "txs" / "by_address" / [owner: Address] / [offset: u64] / [limit: u64] )
+        -> Vec<AddressTx> = txs_by_address,
+
     // Raw storage access - prefix iterator
     ( "prefix" / [storage_key: storage::Key] )
         -> Vec<PrefixValue> = (with_options storage_prefix),
 
+    // Raw storage access - paginated prefix iterator. The page starts after
+    // the key borsh-encoded in the request data, if any, which is the
+    // `last_key` of the previous page
+    ( "prefix_page" / [limit: u64] / [storage_key: storage::Key] )
+        -> PrefixPage = (with_options storage_prefix_page),
+
     // Raw storage access - is given storage key present?
     ( "has_key" / [storage_key: storage::Key] )
         -> bool = storage_has_key,
@@ -103,6 +184,9 @@ router! {SHELL,
     // Query public key revealad
     ( "revealed" / [owner: Address] ) -> bool = revealed,
 
+    // Query the number of bytes stored in an account's subspace
+    ( "storage_usage" / [owner: Address] ) -> u64 = storage_usage,
+
     // IBC UpdateClient event
     ( "ibc_client_update" / [client_id: ClientId] / [consensus_height: BlockHeight] ) -> Option<Event> = ibc_client_update,
 
@@ -123,6 +207,41 @@ where
     unimplemented!("Dry running tx requires \"wasm-runtime\" feature.")
 }
 
+fn dry_run_tx_bundle<D, H, V, T>(
+    _ctx: RequestCtx<'_, D, H, V, T>,
+    _request: &RequestQuery,
+) -> storage_api::Result<EncodedResponseQuery>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    unimplemented!("Dry running txs requires \"wasm-runtime\" feature.")
+}
+
+fn txs_by_address<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    owner: Address,
+    offset: u64,
+    limit: u64,
+) -> storage_api::Result<Vec<AddressTx>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    ctx.wl_storage
+        .storage
+        .db
+        .iter_address_txs(&owner)
+        .skip(offset as usize)
+        .take(limit as usize)
+        .map(|(_key, value, _gas)| {
+            let (height, hash) = <(BlockHeight, Hash)>::try_from_slice(&value)
+                .into_storage_result()?;
+            Ok(AddressTx { height, hash })
+        })
+        .collect()
+}
+
 /// Query to read block results from storage
 pub fn read_results<D, H, V, T>(
     ctx: RequestCtx<'_, D, H, V, T>,
@@ -131,6 +250,15 @@ where
     D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
     H: 'static + StorageHasher + Sync,
 {
+    // The results of all the blocks are returned, so they can't be served
+    // once any of them have been pruned
+    let oldest = ctx.wl_storage.storage.get_oldest_block_data_height();
+    if oldest > BlockHeight(1) {
+        return Err(storage_api::Error::new_alloc(format!(
+            "The results of the blocks before the height {oldest} have been \
+             pruned from this node"
+        )));
+    }
     let (iter, _gas) = ctx.wl_storage.storage.iter_results();
     let mut results = vec![
         BlockResults::default();
@@ -268,15 +396,12 @@ where
     Ok(ctx.wl_storage.storage.last_block.clone())
 }
 
-/// Returns data with `vec![]` when the storage key is not found. For all
-/// borsh-encoded types, it is safe to check `data.is_empty()` to see if the
-/// value was found, except for unit - see `fn query_storage_value` in
-/// `apps/src/lib/client/rpc.rs` for unit type handling via `storage_has_key`.
-fn storage_value<D, H, V, T>(
-    ctx: RequestCtx<'_, D, H, V, T>,
+/// Get the height of a storage query, which can't be further in the past than
+/// the configured limit
+fn checked_queried_height<D, H, V, T>(
+    ctx: &RequestCtx<'_, D, H, V, T>,
     request: &RequestQuery,
-    storage_key: storage::Key,
-) -> storage_api::Result<EncodedResponseQuery>
+) -> storage_api::Result<BlockHeight>
 where
     D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
     H: 'static + StorageHasher + Sync,
@@ -305,6 +430,23 @@ where
             )));
         }
     }
+    Ok(queried_height)
+}
+
+/// Returns data with `vec![]` when the storage key is not found. For all
+/// borsh-encoded types, it is safe to check `data.is_empty()` to see if the
+/// value was found, except for unit - see `fn query_storage_value` in
+/// `apps/src/lib/client/rpc.rs` for unit type handling via `storage_has_key`.
+fn storage_value<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    request: &RequestQuery,
+    storage_key: storage::Key,
+) -> storage_api::Result<EncodedResponseQuery>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    let queried_height = checked_queried_height(&ctx, request)?;
 
     match ctx
         .wl_storage
@@ -368,36 +510,79 @@ where
         })
         .collect();
     let data = data?;
-    let proof = if request.prove {
-        let queried_height = {
-            let last_committed_height =
-                ctx.wl_storage.storage.get_last_block_height();
-
-            let height: BlockHeight = request.height.into();
-            let is_last_height_query = height.0 == 0;
+    let proof = prefix_values_proof(&ctx, request, &data)?;
+    let data = data.serialize_to_vec();
+    Ok(EncodedResponseQuery {
+        data,
+        proof,
+        ..Default::default()
+    })
+}
 
-            if hints::likely(is_last_height_query) {
-                last_committed_height
-            } else {
-                height
-            }
-        };
-        let mut ops = vec![];
-        for PrefixValue { key, value } in &data {
-            let mut proof = ctx
-                .wl_storage
-                .storage
-                .get_existence_proof(key, value, queried_height)
-                .into_storage_result()?;
-            ops.append(&mut proof.ops);
-        }
-        // ops is not empty in this case
-        let proof = ProofOps { ops };
-        Some(proof)
+fn storage_prefix_page<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    request: &RequestQuery,
+    limit: u64,
+    storage_key: storage::Key,
+) -> storage_api::Result<EncodedResponseQuery>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    let queried_height = checked_queried_height(&ctx, request)?;
+    let start_after = (!request.data.is_empty())
+        .then(|| storage::Key::try_from_slice(&request.data))
+        .transpose()
+        .into_storage_result()?;
+
+    let storage = &ctx.wl_storage.storage;
+    let last_committed_height = storage.get_last_block_height();
+    let keys = storage
+        .db
+        .iter_prefix_after(&storage_key, start_after.as_ref());
+    let mut iter: Box<
+        dyn Iterator<Item = storage_api::Result<PrefixValue>> + '_,
+    > = if queried_height >= last_committed_height {
+        Box::new(keys.map(|(key, value, _gas)| {
+            let key = storage::Key::parse(key).into_storage_result()?;
+            Ok(PrefixValue { key, value })
+        }))
     } else {
-        None
+        // The keys deleted since the queried height are only found in the
+        // old diffs of the blocks that deleted them
+        let start_after = start_after
+            .as_ref()
+            .map(ToString::to_string)
+            .unwrap_or_default();
+        let mut deleted_keys = BTreeSet::new();
+        for height in (queried_height.0 + 1)..=last_committed_height.0 {
+            deleted_keys.extend(
+                storage
+                    .db
+                    .iter_old_diffs(BlockHeight(height), Some(&storage_key))
+                    .map(|(key, _value, _gas)| key)
+                    .filter(|key| key > &start_after),
+            );
+        }
+        Box::new(
+            keys.map(|(key, _value, _gas)| key)
+                .merge(deleted_keys)
+                .dedup()
+                .filter_map(move |key| {
+                    read_prefix_value(storage, key, queried_height).transpose()
+                }),
+        )
     };
-    let data = data.serialize_to_vec();
+    let values: Vec<PrefixValue> = iter
+        .by_ref()
+        .take(limit as usize)
+        .collect::<storage_api::Result<_>>()?;
+    let last_key = match iter.next().transpose()? {
+        Some(_) => values.last().map(|value| value.key.clone()),
+        None => None,
+    };
+    let proof = prefix_values_proof(&ctx, request, &values)?;
+    let data = PrefixPage { values, last_key }.serialize_to_vec();
     Ok(EncodedResponseQuery {
         data,
         proof,
@@ -405,6 +590,62 @@ where
     })
 }
 
+/// Read the value of a storage key found by a prefix iterator at the given
+/// height, if it had any
+fn read_prefix_value<D, H>(
+    storage: &Storage<D, H>,
+    key: String,
+    height: BlockHeight,
+) -> storage_api::Result<Option<PrefixValue>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter>,
+    H: 'static + StorageHasher,
+{
+    let key = storage::Key::parse(key).into_storage_result()?;
+    let (value, _gas) = storage
+        .read_with_height(&key, height)
+        .into_storage_result()?;
+    Ok(value.map(|value| PrefixValue { key, value }))
+}
+
+/// Get the merkle proof of existence of the given prefix values, if requested
+fn prefix_values_proof<D, H, V, T>(
+    ctx: &RequestCtx<'_, D, H, V, T>,
+    request: &RequestQuery,
+    data: &[PrefixValue],
+) -> storage_api::Result<Option<ProofOps>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    if !request.prove {
+        return Ok(None);
+    }
+    let queried_height = {
+        let last_committed_height =
+            ctx.wl_storage.storage.get_last_block_height();
+
+        let height: BlockHeight = request.height.into();
+        let is_last_height_query = height.0 == 0;
+
+        if hints::likely(is_last_height_query) {
+            last_committed_height
+        } else {
+            height
+        }
+    };
+    let mut ops = vec![];
+    for PrefixValue { key, value } in data {
+        let mut proof = ctx
+            .wl_storage
+            .storage
+            .get_existence_proof(key, value, queried_height)
+            .into_storage_result()?;
+        ops.append(&mut proof.ops);
+    }
+    Ok(Some(ProofOps { ops }))
+}
+
 fn storage_has_key<D, H, V, T>(
     ctx: RequestCtx<'_, D, H, V, T>,
     storage_key: storage::Key,
@@ -541,10 +782,29 @@ where
     Ok(!public_keys.is_empty())
 }
 
+fn storage_usage<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    owner: Address,
+) -> storage_api::Result<u64>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    storage_api::account::storage_usage(ctx.wl_storage, &owner)
+}
+
 #[cfg(test)]
 mod test {
+    use borsh_ext::BorshSerializeExt;
+    use namada_core::ledger::storage::mockdb::MockDBWriteBatch;
+    use namada_core::ledger::storage::{DBIter, LastBlock, DB};
+    use namada_core::types::address::Address;
+    use namada_core::types::hash::Hash;
+    use namada_core::types::storage::{BlockHash, BlockHeight, Key, TxIndex};
+    use namada_core::types::time::DateTimeUtc;
     use namada_core::types::{address, token};
 
+    use crate::queries::testing::TestClient;
     use crate::queries::RPC;
 
     #[test]
@@ -561,10 +821,136 @@ mod test {
         let path = RPC.shell().dry_run_tx_path();
         assert_eq!("/shell/dry_run_tx", path);
 
+        // Test the `dry_run_tx_bundle` path
+        let path = RPC.shell().dry_run_tx_bundle_path();
+        assert_eq!("/shell/dry_run_tx_bundle", path);
+
+        // Test the `txs_by_address` path
+        let path = RPC.shell().txs_by_address_path(&owner, &10, &20);
+        assert_eq!(format!("/shell/txs/by_address/{owner}/10/20"), path);
+
         let path = RPC.shell().storage_prefix_path(&key);
         assert_eq!(format!("/shell/prefix/{}", key), path);
 
+        // Test the `storage_prefix_page` path
+        let path = RPC.shell().storage_prefix_page_path(&20, &key);
+        assert_eq!(format!("/shell/prefix_page/20/{key}"), path);
+
         let path = RPC.shell().storage_has_key_path(&key);
         assert_eq!(format!("/shell/has_key/{}", key), path);
+
+        let path = RPC.shell().storage_usage_path(&owner);
+        assert_eq!(format!("/shell/storage_usage/{}", owner), path);
+    }
+
+    /// Test that the block results can't be read once some of them have been
+    /// pruned
+    #[tokio::test]
+    async fn test_read_pruned_results() {
+        let mut client = TestClient::new(RPC);
+        client.wl_storage.storage.last_block = Some(LastBlock {
+            height: BlockHeight(10),
+            hash: BlockHash::default(),
+            time: DateTimeUtc::now(),
+        });
+        assert!(RPC.shell().read_results(&client).await.is_ok());
+
+        client.wl_storage.storage.block_data_retain_height = Some(3);
+        assert!(RPC.shell().read_results(&client).await.is_err());
+    }
+
+    /// Test that the applied txs are found by any of the addresses they
+    /// touched, most recent first and paginated
+    #[tokio::test]
+    async fn test_txs_by_address() {
+        let mut client = TestClient::new(RPC);
+        let alice = address::testing::established_address_1();
+        let bob = address::testing::established_address_2();
+        let hashes: Vec<_> = (0..3_u8).map(|i| Hash([i; 32])).collect();
+        let blocks = [
+            vec![
+                (TxIndex(0), hashes[0], vec![&alice, &bob]),
+                (TxIndex(1), hashes[1], vec![&bob]),
+            ],
+            vec![(TxIndex(0), hashes[2], vec![&alice])],
+        ];
+        // The index is written when the block is committed
+        for (height, txs) in (1..).zip(blocks) {
+            let storage = &mut client.wl_storage.storage;
+            storage.block.height = BlockHeight(height);
+            for (tx_index, hash, owners) in txs {
+                for owner in owners {
+                    storage.block.address_txs.push((
+                        owner.clone(),
+                        tx_index,
+                        hash,
+                    ));
+                }
+            }
+            if height == 1 {
+                // Nothing is indexed before the block is committed
+                assert!(storage.db.iter_address_txs(&bob).next().is_none());
+            }
+            storage.commit_block(MockDBWriteBatch::default()).unwrap();
+        }
+
+        let tx_hashes = |owner: &Address, offset: u64, limit: u64| {
+            let client = &client;
+            let owner = owner.clone();
+            async move {
+                RPC.shell()
+                    .txs_by_address(client, &owner, &offset, &limit)
+                    .await
+                    .unwrap()
+                    .into_iter()
+                    .map(|tx| tx.hash)
+                    .collect::<Vec<_>>()
+            }
+        };
+        assert_eq!(tx_hashes(&alice, 0, 10).await, vec![hashes[2], hashes[0]]);
+        assert_eq!(tx_hashes(&bob, 0, 10).await, vec![hashes[1], hashes[0]]);
+        assert_eq!(tx_hashes(&bob, 1, 10).await, vec![hashes[0]]);
+        assert_eq!(tx_hashes(&bob, 0, 1).await, vec![hashes[1]]);
+        let carol = address::testing::established_address_3();
+        assert!(tx_hashes(&carol, 0, 10).await.is_empty());
+    }
+
+    /// Test that the values under a prefix can be read page by page
+    #[tokio::test]
+    async fn test_storage_prefix_page() {
+        let mut client = TestClient::new(RPC);
+        let prefix = Key::parse("prefix").unwrap();
+        for ix in 0..5_u8 {
+            let key = prefix.push(&ix.to_string()).unwrap();
+            client.wl_storage.storage.write(&key, vec![ix]).unwrap();
+        }
+
+        let mut start_after: Option<Key> = None;
+        let mut values = vec![];
+        let mut pages = 0;
+        loop {
+            let page = RPC
+                .shell()
+                .storage_prefix_page(
+                    &client,
+                    start_after.as_ref().map(|key| key.serialize_to_vec()),
+                    None,
+                    false,
+                    &2,
+                    &prefix,
+                )
+                .await
+                .unwrap()
+                .data;
+            assert!(page.values.len() <= 2);
+            values.extend(page.values.into_iter().map(|val| val.value));
+            pages += 1;
+            match page.last_key {
+                Some(last_key) => start_after = Some(last_key),
+                None => break,
+            }
+        }
+        assert_eq!(pages, 3);
+        assert_eq!(values, (0..5_u8).map(|ix| vec![ix]).collect::<Vec<_>>());
     }
 }
@@ -884,7 +884,7 @@ mod test_ethbridge_router {
         client
             .wl_storage
             .storage
-            .commit_block(MockDBWriteBatch)
+            .commit_block(MockDBWriteBatch::default())
             .expect("Test failed");
 
         // check the response
@@ -944,7 +944,7 @@ mod test_ethbridge_router {
         client
             .wl_storage
             .storage
-            .commit_block(MockDBWriteBatch)
+            .commit_block(MockDBWriteBatch::default())
             .expect("Test failed");
 
         // check the response
@@ -997,7 +997,7 @@ mod test_ethbridge_router {
         client
             .wl_storage
             .storage
-            .commit_block(MockDBWriteBatch)
+            .commit_block(MockDBWriteBatch::default())
             .expect("Test failed");
 
         // check the response
@@ -1038,7 +1038,7 @@ mod test_ethbridge_router {
         client
             .wl_storage
             .storage
-            .commit_block(MockDBWriteBatch)
+            .commit_block(MockDBWriteBatch::default())
             .expect("Test failed");
 
         // check the response
@@ -1322,7 +1322,7 @@ mod test_ethbridge_router {
         client
             .wl_storage
             .storage
-            .commit_block(MockDBWriteBatch)
+            .commit_block(MockDBWriteBatch::default())
             .expect("Test failed");
         client.wl_storage.storage.block.height += 1;
 
@@ -1350,7 +1350,7 @@ mod test_ethbridge_router {
         client
             .wl_storage
             .storage
-            .commit_block(MockDBWriteBatch)
+            .commit_block(MockDBWriteBatch::default())
             .expect("Test failed");
         client.wl_storage.storage.block.height += 1;
 
@@ -1514,7 +1514,7 @@ mod test_ethbridge_router {
         client
             .wl_storage
             .storage
-            .commit_block(MockDBWriteBatch)
+            .commit_block(MockDBWriteBatch::default())
             .expect("Test failed");
         client.wl_storage.storage.block.height += 1;
 
@@ -1533,7 +1533,7 @@ mod test_ethbridge_router {
         client
             .wl_storage
             .storage
-            .commit_block(MockDBWriteBatch)
+            .commit_block(MockDBWriteBatch::default())
             .expect("Test failed");
         client.wl_storage.storage.block.height += 1;
         let resp = RPC
@@ -1792,7 +1792,7 @@ mod test_ethbridge_router {
             client
                 .wl_storage
                 .storage
-                .commit_block(MockDBWriteBatch)
+                .commit_block(MockDBWriteBatch::default())
                 .expect("Test failed");
         }
 
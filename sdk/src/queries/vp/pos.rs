@@ -9,7 +9,7 @@ use namada_core::ledger::storage_api::collections::lazy_map;
 use namada_core::ledger::storage_api::OptionExt;
 use namada_core::types::address::Address;
 use namada_core::types::key::common;
-use namada_core::types::storage::Epoch;
+use namada_core::types::storage::{BlockHeight, Epoch};
 use namada_core::types::token;
 use namada_proof_of_stake::parameters::PosParams;
 use namada_proof_of_stake::queries::{
@@ -71,6 +71,9 @@ router! {POS,
         ( "consensus" / [epoch: opt Epoch] )
             -> BTreeSet<WeightedValidator> = consensus_validator_set,
 
+        ( "consensus_at_height" / [height: BlockHeight] )
+            -> BTreeSet<WeightedValidator> = consensus_validator_set_at_height,
+
         ( "below_capacity" / [epoch: opt Epoch] )
             -> BTreeSet<WeightedValidator> = below_capacity_validator_set,
 
@@ -364,6 +367,35 @@ where
     read_consensus_validator_set_addresses_with_stake(ctx.wl_storage, epoch)
 }
 
+/// Get all the validator in the consensus set with their bonded stake, in the
+/// epoch of the given block height. The height must not be above the last
+/// committed block height.
+fn consensus_validator_set_at_height<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    height: BlockHeight,
+) -> storage_api::Result<BTreeSet<WeightedValidator>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    let last_height = ctx.wl_storage.storage.get_last_block_height();
+    if height > last_height {
+        return Err(storage_api::Error::new_alloc(format!(
+            "Cannot query the consensus validator set at block height \
+             {height}, which is above the last committed block height \
+             {last_height}"
+        )));
+    }
+    let epoch = ctx
+        .wl_storage
+        .storage
+        .block
+        .pred_epochs
+        .get_epoch(height)
+        .ok_or_err_msg("Cannot find the epoch of the given block height")?;
+    read_consensus_validator_set_addresses_with_stake(ctx.wl_storage, epoch)
+}
+
 /// Get all the validator in the below-capacity set with their bonded stake.
 fn below_capacity_validator_set<D, H, V, T>(
     ctx: RequestCtx<'_, D, H, V, T>,
@@ -753,3 +785,89 @@ fn enrich_bonds_and_unbonds(
         total_withdrawable,
     }
 }
+
+#[cfg(test)]
+mod test {
+    use namada_core::ledger::storage::LastBlock;
+    use namada_core::types::address::testing::established_address_1;
+    use namada_core::types::dec::Dec;
+    use namada_core::types::key::testing::common_sk_from_simple_seed;
+    use namada_core::types::key::RefTo;
+    use namada_core::types::storage::BlockHash;
+    use namada_core::types::time::DateTimeUtc;
+    use namada_proof_of_stake::test_utils::test_init_genesis;
+    use namada_proof_of_stake::types::GenesisValidator;
+    use namada_proof_of_stake::OwnedPosParams;
+
+    use super::*;
+    use crate::queries::testing::TestClient;
+    use crate::queries::RPC;
+
+    /// Test that the consensus validator set can be queried at past and
+    /// current block heights, but not above the last committed height
+    #[tokio::test]
+    async fn test_consensus_validator_set_at_height() {
+        let mut client = TestClient::new(RPC);
+        let validator = GenesisValidator {
+            address: established_address_1(),
+            tokens: token::Amount::native_whole(1),
+            consensus_key: common_sk_from_simple_seed(0).ref_to(),
+            protocol_key: common_sk_from_simple_seed(1).ref_to(),
+            eth_cold_key: common_sk_from_simple_seed(2).ref_to(),
+            eth_hot_key: common_sk_from_simple_seed(3).ref_to(),
+            commission_rate: Dec::new(1, 1).unwrap(),
+            max_commission_rate_change: Dec::new(1, 1).unwrap(),
+            metadata: Default::default(),
+        };
+        test_init_genesis(
+            &mut client.wl_storage,
+            OwnedPosParams::default(),
+            [validator.clone()].into_iter(),
+            Epoch(0),
+        )
+        .unwrap();
+        // The 2nd epoch starts at height 2 and the last committed height is 3
+        client
+            .wl_storage
+            .storage
+            .block
+            .pred_epochs
+            .new_epoch(BlockHeight(2));
+        client.wl_storage.storage.last_epoch = Epoch(1);
+        client.wl_storage.storage.last_block = Some(LastBlock {
+            height: BlockHeight(3),
+            hash: BlockHash::default(),
+            time: DateTimeUtc::now(),
+        });
+        let expected = BTreeSet::from([WeightedValidator {
+            bonded_stake: validator.tokens,
+            address: validator.address,
+        }]);
+
+        // Past height
+        let set = RPC
+            .vp()
+            .pos()
+            .consensus_validator_set_at_height(&client, &BlockHeight(1))
+            .await
+            .unwrap();
+        assert_eq!(set, expected);
+
+        // Current height
+        let set = RPC
+            .vp()
+            .pos()
+            .consensus_validator_set_at_height(&client, &BlockHeight(3))
+            .await
+            .unwrap();
+        assert_eq!(set, expected);
+
+        // Future height
+        let result = RPC
+            .vp()
+            .pos()
+            .consensus_validator_set_at_height(&client, &BlockHeight(4))
+            .await;
+        assert!(result.is_err());
+    }
+}
@@ -6,6 +6,7 @@ use std::ops::ControlFlow;
 use std::str::FromStr;
 
 use borsh::BorshDeserialize;
+use borsh_ext::BorshSerializeExt;
 use masp_primitives::asset_type::AssetType;
 use masp_primitives::merkle_tree::MerklePath;
 use masp_primitives::sapling::Node;
@@ -21,7 +22,7 @@ use namada_core::types::address::{Address, InternalAddress};
 use namada_core::types::hash::Hash;
 use namada_core::types::key::common;
 use namada_core::types::storage::{
-    BlockHeight, BlockResults, Epoch, Key, PrefixValue,
+    BlockHeight, BlockResults, Epoch, Key, PrefixPage, PrefixValue,
 };
 use namada_core::types::token::{
     Amount, DenominatedAmount, Denomination, MaspDenom,
@@ -31,18 +32,22 @@ use namada_core::types::{storage, token};
 use namada_proof_of_stake::parameters::PosParams;
 use namada_proof_of_stake::types::{
     BondsAndUnbondsDetails, CommissionPair, ValidatorMetaData, ValidatorState,
+    WeightedValidator,
 };
 use serde::Serialize;
 
 use crate::args::InputAmount;
-use crate::control_flow::time;
+use crate::control_flow::time::{self, SleepStrategy};
 use crate::error::{EncodingError, Error, QueryError, TxError};
 use crate::events::Event;
 use crate::internal_macros::echo_error;
 use crate::io::Io;
 use crate::proto::Tx;
 use crate::queries::vp::pos::EnrichedBondsAndUnbondsDetails;
-use crate::queries::{Client, RPC};
+use crate::queries::{
+    AddressTx, AllocRejections, BlockStats, Client, ALLOC_REJECTIONS_PATH,
+    BLOCK_STATS_PATH, RPC,
+};
 use crate::tendermint::block::Height;
 use crate::tendermint::merkle::proof::ProofOps;
 use crate::tendermint_rpc::error::Error as TError;
@@ -50,19 +55,27 @@ use crate::tendermint_rpc::query::Query;
 use crate::tendermint_rpc::Order;
 use crate::{display_line, edisplay_line, error, Namada};
 
+/// The maximum time to sleep between consecutive queries of a tx status
+const TX_STATUS_MAX_BACKOFF: time::Duration = time::Duration::from_secs(4);
+
 /// Query the status of a given transaction.
 ///
-/// If a response is not delivered until `deadline`, we exit the cli with an
-/// error.
+/// The queries are retried with an exponential backoff, capped at
+/// [`TX_STATUS_MAX_BACKOFF`]. If a response is not delivered until
+/// `deadline`, we exit the cli with an error.
 pub async fn query_tx_status(
     context: &impl Namada,
     status: TxEventQuery<'_>,
     deadline: time::Instant,
 ) -> Result<Event, Error> {
     time::Sleep {
-        strategy: time::LinearBackoff {
-            delta: time::Duration::from_secs(1),
-        },
+        strategy: time::ExponentialBackoff {
+            base: 2,
+            as_duration: |exp: u64| {
+                time::Duration::from_millis(exp.saturating_mul(250))
+            },
+        }
+        .map(|backoff| backoff.min(TX_STATUS_MAX_BACKOFF)),
     }
     .timeout(deadline, || async {
         tracing::debug!(query = ?status, "Querying tx status");
@@ -173,6 +186,14 @@ pub async fn get_token_balance<C: crate::queries::Client + Sync>(
     )
 }
 
+/// Query the total supply of the given token.
+pub async fn get_token_total_supply<C: crate::queries::Client + Sync>(
+    client: &C,
+    token: &Address,
+) -> Result<token::Amount, error::Error> {
+    convert_response::<C, _>(RPC.vp().token().total_supply(client, token).await)
+}
+
 /// Check if the given address is a known validator.
 pub async fn is_validator<C: crate::queries::Client + Sync>(
     client: &C,
@@ -411,6 +432,26 @@ where
     })
 }
 
+/// Query a page of at most `limit` raw storage values with a matching prefix,
+/// starting after the `start_after` key, if any. The returned page carries
+/// the last key of the page when there are more values to query, to be used
+/// as the `start_after` key of the next page.
+pub async fn query_storage_prefix_page<C: crate::queries::Client + Sync>(
+    client: &C,
+    key: &storage::Key,
+    start_after: Option<&storage::Key>,
+    limit: u64,
+    prove: bool,
+) -> Result<(PrefixPage, Option<ProofOps>), error::Error> {
+    let data = start_after.map(|key| key.serialize_to_vec());
+    let response = convert_response::<C, _>(
+        RPC.shell()
+            .storage_prefix_page(client, data, None, prove, &limit, key)
+            .await,
+    )?;
+    Ok((response.data, response.proof))
+}
+
 /// Query to check if the given storage key exists.
 pub async fn query_has_storage_key<C: crate::queries::Client + Sync>(
     client: &C,
@@ -509,6 +550,63 @@ pub async fn dry_run_tx<N: Namada>(
     Ok(result)
 }
 
+/// Query the gas and space utilization of the recently committed blocks
+pub async fn get_block_stats<C: crate::queries::Client + Sync>(
+    client: &C,
+) -> Result<Vec<BlockStats>, error::Error> {
+    let data = convert_response::<C, _>(
+        client.simple_request(BLOCK_STATS_PATH.to_owned()).await,
+    )?;
+    Vec::<BlockStats>::try_from_slice(&data)
+        .map_err(|err| Error::from(EncodingError::Decoding(err.to_string())))
+}
+
+/// Query a page of the applied txs that touched the given address, from the
+/// most recent
+pub async fn get_txs_by_address<C: crate::queries::Client + Sync>(
+    client: &C,
+    owner: &Address,
+    offset: u64,
+    limit: u64,
+) -> Result<Vec<AddressTx>, error::Error> {
+    convert_response::<C, _>(
+        RPC.shell()
+            .txs_by_address(client, owner, &offset, &limit)
+            .await,
+    )
+}
+
+/// Query the counters of the txs that were dropped from the node's block
+/// proposals for lack of block space
+pub async fn get_alloc_rejections<C: crate::queries::Client + Sync>(
+    client: &C,
+) -> Result<AllocRejections, error::Error> {
+    let data = convert_response::<C, _>(
+        client
+            .simple_request(ALLOC_REJECTIONS_PATH.to_owned())
+            .await,
+    )?;
+    AllocRejections::try_from_slice(&data)
+        .map_err(|err| Error::from(EncodingError::Decoding(err.to_string())))
+}
+
+/// Dry run an ordered bundle of transactions, each applied on top of the
+/// changes of the preceding accepted transactions
+pub async fn dry_run_tx_bundle<N: Namada>(
+    context: &N,
+    txs_bytes: Vec<Vec<u8>>,
+) -> Result<Vec<TxResult>, Error> {
+    let (data, height, prove) =
+        (Some(txs_bytes.serialize_to_vec()), None, false);
+    let results = convert_response::<N::Client, _>(
+        RPC.shell()
+            .dry_run_tx_bundle(context.client(), data, height, prove)
+            .await,
+    )?
+    .data;
+    Ok(results)
+}
+
 /// Data needed for broadcasting a tx and
 /// monitoring its progress on chain
 ///
@@ -757,6 +855,33 @@ pub async fn get_validator_stake<C: crate::queries::Client + Sync>(
     .map(|t| t.unwrap_or_default())
 }
 
+/// Get the consensus validator set with the validators' stake at the given
+/// epoch, or at the current epoch when `None`
+pub async fn get_consensus_validator_set<C: crate::queries::Client + Sync>(
+    client: &C,
+    epoch: Option<Epoch>,
+) -> Result<BTreeSet<WeightedValidator>, error::Error> {
+    convert_response::<C, _>(
+        RPC.vp().pos().consensus_validator_set(client, &epoch).await,
+    )
+}
+
+/// Get the consensus validator set with the validators' stake in the epoch of
+/// the given block height
+pub async fn get_consensus_validator_set_at_height<
+    C: crate::queries::Client + Sync,
+>(
+    client: &C,
+    height: BlockHeight,
+) -> Result<BTreeSet<WeightedValidator>, error::Error> {
+    convert_response::<C, _>(
+        RPC.vp()
+            .pos()
+            .consensus_validator_set_at_height(client, &height)
+            .await,
+    )
+}
+
 /// Query and return a validator's state
 pub async fn get_validator_state<C: crate::queries::Client + Sync>(
     client: &C,
@@ -897,6 +1022,14 @@ pub async fn is_public_key_revealed<C: crate::queries::Client + Sync>(
     convert_response::<C, bool>(RPC.shell().revealed(client, owner).await)
 }
 
+/// Query the number of bytes stored in an account's substorage
+pub async fn get_storage_usage<C: crate::queries::Client + Sync>(
+    client: &C,
+    owner: &Address,
+) -> Result<u64, error::Error> {
+    convert_response::<C, u64>(RPC.shell().storage_usage(client, owner).await)
+}
+
 /// Query an account substorage at a specific index
 pub async fn get_public_key_at<C: crate::queries::Client + Sync>(
     client: &C,
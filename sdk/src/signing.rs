@@ -338,7 +338,7 @@ pub async fn aux_signing_data(
             1u8,
         ),
         Some(owner @ Address::Internal(internal)) => match internal {
-            InternalAddress::Masp => (None, 0u8),
+            InternalAddress::Masp | InternalAddress::Faucet => (None, 0u8),
             _ => {
                 return Err(Error::from(TxError::InvalidAccount(
                     owner.encode(),
@@ -24,6 +24,7 @@ use namada_core::ibc::core::channel::types::timeout::TimeoutHeight;
 use namada_core::ibc::core::client::types::Height as IbcHeight;
 use namada_core::ibc::core::host::types::identifiers::{ChannelId, PortId};
 use namada_core::ibc::primitives::{Msg, Timestamp as IbcTimestamp};
+use namada_core::ledger::faucet;
 use namada_core::ledger::governance::cli::onchain::{
     DefaultProposal, OnChainProposal, PgfFundingProposal, PgfStewardProposal,
     ProposalVote,
@@ -119,7 +120,8 @@ pub const TX_UPDATE_STEWARD_COMMISSION: &str =
 pub const TX_REDELEGATE_WASM: &str = "tx_redelegate.wasm";
 
 /// Default timeout in seconds for requests to the `/accepted`
-/// and `/applied` ABCI query endpoints.
+/// and `/applied` ABCI query endpoints, used when the tx args don't set
+/// a `wait_timeout`.
 const DEFAULT_NAMADA_EVENTS_MAX_WAIT_TIME_SECONDS: u64 = 60;
 
 /// Capture the result of running a transaction
@@ -237,7 +239,7 @@ pub async fn process_tx(
                 .await
                 .map(ProcessTxResponse::Broadcast)
         } else {
-            match submit_tx(context, to_broadcast).await {
+            match submit_tx(context, to_broadcast, args.wait_timeout).await {
                 Ok(resp) => {
                     if let InnerTxResult::Success(result) =
                         resp.inner_tx_result()
@@ -362,10 +364,12 @@ pub async fn broadcast_tx(
 /// 2. The tx with encrypted payload has been included on the blockchain
 /// 3. The decrypted payload of the tx has been included on the blockchain.
 ///
-/// In the case of errors in any of those stages, an error message is returned
+/// In the case of errors in any of those stages, an error message is returned.
+/// The tx is awaited for up to `wait_timeout`, or a default of 60 seconds.
 pub async fn submit_tx(
     context: &impl Namada,
     to_broadcast: TxBroadcastData,
+    wait_timeout: Option<Duration>,
 ) -> Result<TxResponse> {
     let (_, wrapper_hash, decrypted_hash) = match &to_broadcast {
         TxBroadcastData::Live {
@@ -380,9 +384,9 @@ pub async fn submit_tx(
     broadcast_tx(context, &to_broadcast).await?;
 
     let deadline = time::Instant::now()
-        + time::Duration::from_secs(
+        + wait_timeout.unwrap_or(time::Duration::from_secs(
             DEFAULT_NAMADA_EVENTS_MAX_WAIT_TIME_SECONDS,
-        );
+        ));
 
     tracing::debug!(
         transaction = ?to_broadcast,
@@ -2290,7 +2294,13 @@ pub async fn build_transfer<N: Namada>(
     context: &N,
     args: &mut args::TxTransfer,
 ) -> Result<(Tx, SigningTxData, Option<Epoch>)> {
-    let default_signer = Some(args.source.effective_address());
+    // Withdrawals from the faucet don't need its signature, so the receiver
+    // signs the tx and pays its fees instead
+    let default_signer = if args.source.effective_address() == faucet::ADDRESS {
+        Some(args.target.effective_address())
+    } else {
+        Some(args.source.effective_address())
+    };
     let signing_data = signing::aux_signing_data(
         context,
         &args.tx,
@@ -13,18 +13,28 @@ pub mod storage;
 pub mod vp_host_fns;
 
 #[cfg(feature = "wasm-runtime")]
-pub use dry_run_tx::dry_run_tx;
+pub use dry_run_tx::{dry_run_tx, dry_run_tx_bundle};
 pub use namada_core::ledger::{
-    gas, parameters, replay_protection, storage_api, tx_env, vp_env,
+    faucet, gas, parameters, replay_protection, storage_api, tx_env, vp_env,
 };
 
 #[cfg(feature = "wasm-runtime")]
 mod dry_run_tx {
-    use namada_core::ledger::storage::{DBIter, StorageHasher, DB};
+    use borsh::BorshDeserialize;
+    use borsh_ext::BorshSerializeExt;
+    use namada_core::ledger::gas::{Gas, GasMetering, TxGasMeter};
+    use namada_core::ledger::storage::{
+        DBIter, StorageHasher, TempWlStorage, DB,
+    };
     use namada_core::ledger::storage_api::ResultExt;
+    use namada_core::proto::Tx;
+    use namada_core::types::transaction::{DecryptedTx, TxResult};
     use namada_sdk::queries::{EncodedResponseQuery, RequestCtx, RequestQuery};
 
     use super::{protocol, storage_api};
+    use crate::ledger::protocol::ShellParams;
+    use crate::types::storage::TxIndex;
+    use crate::types::transaction::TxType;
     use crate::vm::wasm::{TxCache, VpCache};
     use crate::vm::WasmCacheAccess;
 
@@ -38,20 +48,84 @@ mod dry_run_tx {
         H: 'static + StorageHasher + Sync,
         CA: 'static + WasmCacheAccess + Sync,
     {
-        use borsh_ext::BorshSerializeExt;
-        use namada_core::ledger::gas::{Gas, GasMetering, TxGasMeter};
-        use namada_core::ledger::storage::TempWlStorage;
-        use namada_core::proto::Tx;
-        use namada_core::types::transaction::DecryptedTx;
+        let mut temp_wl_storage = TempWlStorage::new(&ctx.wl_storage.storage);
+        let data = dry_run_tx_in(
+            &request.data,
+            &TxIndex(0),
+            &mut temp_wl_storage,
+            &mut ctx.vp_wasm_cache,
+            &mut ctx.tx_wasm_cache,
+        )?;
+        // NOTE: the keys changed by the wrapper transaction (if any) are not
+        // returned from this function
+        let data = data.serialize_to_vec();
+        Ok(EncodedResponseQuery {
+            data,
+            proof: None,
+            info: Default::default(),
+        })
+    }
+
+    /// Dry run an ordered bundle of transactions, given as a borsh-encoded
+    /// vector of serialized transactions. Every transaction is applied on top
+    /// of the changes of the preceding accepted transactions of the bundle,
+    /// which are never committed to the storage.
+    pub fn dry_run_tx_bundle<D, H, CA>(
+        mut ctx: RequestCtx<'_, D, H, VpCache<CA>, TxCache<CA>>,
+        request: &RequestQuery,
+    ) -> storage_api::Result<EncodedResponseQuery>
+    where
+        D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+        H: 'static + StorageHasher + Sync,
+        CA: 'static + WasmCacheAccess + Sync,
+    {
+        let txs = Vec::<Vec<u8>>::try_from_slice(&request.data)
+            .into_storage_result()?;
 
-        use crate::ledger::protocol::ShellParams;
-        use crate::types::storage::TxIndex;
-        use crate::types::transaction::TxType;
+        let mut temp_wl_storage = TempWlStorage::new(&ctx.wl_storage.storage);
+        let mut results = Vec::with_capacity(txs.len());
+        for (index, tx_bytes) in txs.iter().enumerate() {
+            let result = dry_run_tx_in(
+                tx_bytes,
+                &TxIndex(index as u32),
+                &mut temp_wl_storage,
+                &mut ctx.vp_wasm_cache,
+                &mut ctx.tx_wasm_cache,
+            )?;
+            // Only the changes of accepted txs are visible to the following
+            // txs of the bundle, as it would happen in a block
+            if result.is_accepted() {
+                temp_wl_storage.write_log.commit_tx();
+            } else {
+                temp_wl_storage.write_log.drop_tx();
+            }
+            results.push(result);
+        }
+        Ok(EncodedResponseQuery {
+            data: results.serialize_to_vec(),
+            proof: None,
+            info: Default::default(),
+        })
+    }
 
-        let mut tx = Tx::try_from(&request.data[..]).into_storage_result()?;
+    /// Dry run a serialized transaction on top of the given temporary storage.
+    /// The changes of the wrapper tx (if any) are committed to the temporary
+    /// write log, while the changes of the inner tx are left uncommitted.
+    fn dry_run_tx_in<D, H, CA>(
+        tx_bytes: &[u8],
+        tx_index: &TxIndex,
+        temp_wl_storage: &mut TempWlStorage<'_, D, H>,
+        vp_wasm_cache: &mut VpCache<CA>,
+        tx_wasm_cache: &mut TxCache<CA>,
+    ) -> storage_api::Result<TxResult>
+    where
+        D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+        H: 'static + StorageHasher + Sync,
+        CA: 'static + WasmCacheAccess + Sync,
+    {
+        let mut tx = Tx::try_from(tx_bytes).into_storage_result()?;
         tx.validate_tx().into_storage_result()?;
 
-        let mut temp_wl_storage = TempWlStorage::new(&ctx.wl_storage.storage);
         let mut cumulated_gas = Gas::default();
 
         // Wrapper dry run to allow estimating the gas cost of a transaction
@@ -63,12 +137,12 @@ mod dry_run_tx {
                     tx.clone(),
                     &wrapper,
                     None,
-                    &request.data,
+                    tx_bytes,
                     ShellParams::new(
                         &mut tx_gas_meter,
-                        &mut temp_wl_storage,
-                        &mut ctx.vp_wasm_cache,
-                        &mut ctx.tx_wasm_cache,
+                        temp_wl_storage,
+                        vp_wasm_cache,
+                        tx_wasm_cache,
                     ),
                     None,
                 )
@@ -84,9 +158,11 @@ mod dry_run_tx {
                 // If dry run only the inner tx, use the max block gas as the
                 // gas limit
                 TxGasMeter::new(
-                    namada_core::ledger::gas::get_max_block_gas(ctx.wl_storage)
-                        .unwrap()
-                        .into(),
+                    namada_core::ledger::gas::get_max_block_gas(
+                        &*temp_wl_storage,
+                    )
+                    .unwrap()
+                    .into(),
                 )
             }
             TxType::Raw => {
@@ -96,21 +172,23 @@ mod dry_run_tx {
                 // If dry run only the inner tx, use the max block gas as the
                 // gas limit
                 TxGasMeter::new(
-                    namada_core::ledger::gas::get_max_block_gas(ctx.wl_storage)
-                        .unwrap()
-                        .into(),
+                    namada_core::ledger::gas::get_max_block_gas(
+                        &*temp_wl_storage,
+                    )
+                    .unwrap()
+                    .into(),
                 )
             }
         };
 
         let mut data = protocol::apply_wasm_tx(
             tx,
-            &TxIndex(0),
+            tx_index,
             ShellParams::new(
                 &mut tx_gas_meter,
-                &mut temp_wl_storage,
-                &mut ctx.vp_wasm_cache,
-                &mut ctx.tx_wasm_cache,
+                temp_wl_storage,
+                vp_wasm_cache,
+                tx_wasm_cache,
             ),
         )
         .into_storage_result()?;
@@ -121,14 +199,7 @@ mod dry_run_tx {
             ))?;
         // Account gas for both inner and wrapper (if available)
         data.gas_used = cumulated_gas;
-        // NOTE: the keys changed by the wrapper transaction (if any) are not
-        // returned from this function
-        let data = data.serialize_to_vec();
-        Ok(EncodedResponseQuery {
-            data,
-            proof: None,
-            info: Default::default(),
-        })
+        Ok(data)
     }
 }
 
@@ -146,6 +217,7 @@ mod test {
     use namada_sdk::queries::{
         EncodedResponseQuery, RequestCtx, RequestQuery, Router, RPC,
     };
+    use namada_test_utils::tx_data::TxWriteData;
     use namada_test_utils::TestWasms;
     use tempfile::TempDir;
     use tendermint_rpc::{Error as RpcError, Response};
@@ -255,6 +327,8 @@ mod test {
             // really permit error types other than [`std::io::Error`]
             if request.path == "/shell/dry_run_tx" {
                 super::dry_run_tx(ctx, &request)
+            } else if request.path == "/shell/dry_run_tx_bundle" {
+                super::dry_run_tx_bundle(ctx, &request)
             } else {
                 self.rpc.handle(ctx, &request)
             }
@@ -302,11 +376,79 @@ mod test {
         let tx_bytes = outer_tx.to_bytes();
         let result = RPC
             .shell()
-            .dry_run_tx(&client, Some(tx_bytes), None, false)
+            .dry_run_tx(&client, Some(tx_bytes.clone()), None, false)
             .await
             .unwrap();
         assert!(result.data.is_accepted());
 
+        // Request dry run of a bundle of txs, in which a tx reads the key
+        // written by the previous one
+        let bundle_key = Key::parse("dry_run_bundle").unwrap();
+        let store_wasm = |client: &mut TestClient<_>, code: &[u8]| {
+            let hash = Hash::sha256(code);
+            client
+                .wl_storage
+                .storage
+                .write(&Key::wasm_code(&hash), code)
+                .unwrap();
+            client
+                .wl_storage
+                .storage
+                .write(
+                    &Key::wasm_code_len(&hash),
+                    (code.len() as u64).serialize_to_vec(),
+                )
+                .unwrap();
+            hash
+        };
+        let chain_id = client.wl_storage.storage.chain_id.clone();
+        let bundle_tx = |code_hash: Hash, data: Vec<u8>| {
+            let mut tx =
+                Tx::from_type(TxType::Decrypted(DecryptedTx::Decrypted));
+            tx.header.chain_id = chain_id.clone();
+            tx.set_code(Code::from_hash(code_hash, None));
+            tx.set_data(Data::new(data));
+            tx.to_bytes()
+        };
+        let write_hash =
+            store_wasm(&mut client, &TestWasms::TxWriteStorageKey.read_bytes());
+        let read_hash =
+            store_wasm(&mut client, &TestWasms::TxReadStorageKey.read_bytes());
+        let write_tx = bundle_tx(
+            write_hash,
+            TxWriteData {
+                key: bundle_key.clone(),
+                value: b"written by the first tx".to_vec(),
+            }
+            .serialize_to_vec(),
+        );
+        let read_tx = bundle_tx(read_hash, bundle_key.serialize_to_vec());
+
+        let result = RPC
+            .shell()
+            .dry_run_tx_bundle(
+                &client,
+                Some(vec![write_tx, read_tx.clone()].serialize_to_vec()),
+                None,
+                false,
+            )
+            .await
+            .unwrap();
+        assert_eq!(result.data.len(), 2);
+        assert!(result.data.iter().all(|result| result.is_accepted()));
+
+        // Without the first tx, the key doesn't exist and the read fails
+        let result = RPC
+            .shell()
+            .dry_run_tx_bundle(
+                &client,
+                Some(vec![read_tx].serialize_to_vec()),
+                None,
+                false,
+            )
+            .await;
+        assert!(result.is_err());
+
         // Request storage value for a balance key ...
         let token_addr = address::testing::established_address_1();
         let owner = address::testing::established_address_2();
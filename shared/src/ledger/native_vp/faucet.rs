@@ -0,0 +1,199 @@
+//! Native VP for the testnet faucet
+
+use std::collections::BTreeSet;
+
+use namada_core::ledger::faucet;
+use namada_core::ledger::storage_api::governance;
+use namada_core::ledger::storage_api::token::read_denom;
+use thiserror::Error;
+
+use crate::ledger::native_vp::{self, Ctx, NativeVp};
+use crate::ledger::storage;
+use crate::ledger::vp_env::VpEnv;
+use crate::proto::Tx;
+use crate::types::address::Address;
+use crate::types::storage::Key;
+use crate::types::token::{self, Amount};
+use crate::vm::WasmCacheAccess;
+
+#[allow(missing_docs)]
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error("Native VP error: {0}")]
+    NativeVpError(#[from] native_vp::Error),
+}
+
+/// Faucet functions result
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Faucet VP
+pub struct FaucetVp<'a, DB, H, CA>
+where
+    DB: storage::DB + for<'iter> storage::DBIter<'iter>,
+    H: storage::StorageHasher,
+    CA: WasmCacheAccess,
+{
+    /// Context to interact with the host structures.
+    pub ctx: Ctx<'a, DB, H, CA>,
+}
+
+impl<'a, DB, H, CA> NativeVp for FaucetVp<'a, DB, H, CA>
+where
+    DB: 'static + storage::DB + for<'iter> storage::DBIter<'iter>,
+    H: 'static + storage::StorageHasher,
+    CA: 'static + WasmCacheAccess,
+{
+    type Error = Error;
+
+    fn validate_tx(
+        &self,
+        tx_data: &Tx,
+        keys_changed: &BTreeSet<Key>,
+        _verifiers: &BTreeSet<Address>,
+    ) -> Result<bool> {
+        for key in keys_changed {
+            let is_valid = if let Some([token, owner]) =
+                token::is_any_token_balance_key(key)
+            {
+                if owner != &faucet::ADDRESS {
+                    // balance changes of other accounts
+                    continue;
+                }
+                let pre: Amount = self.ctx.read_pre(key)?.unwrap_or_default();
+                let post: Amount = self.ctx.read_post(key)?.unwrap_or_default();
+                if post >= pre {
+                    // credit is permissive
+                    continue;
+                }
+                self.is_valid_withdrawal(token, pre - post)?
+            } else if faucet::is_faucet_key(key) {
+                // The withdrawal limit can only be changed by governance
+                match tx_data.data() {
+                    Some(data) => {
+                        governance::is_proposal_accepted(&self.ctx.pre(), &data)
+                            .unwrap_or(false)
+                    }
+                    None => false,
+                }
+            } else {
+                true
+            };
+            if !is_valid {
+                tracing::debug!("key {} modification failed faucet vp", key);
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+}
+
+impl<'a, DB, H, CA> FaucetVp<'a, DB, H, CA>
+where
+    DB: 'static + storage::DB + for<'iter> storage::DBIter<'iter>,
+    H: 'static + storage::StorageHasher,
+    CA: 'static + WasmCacheAccess,
+{
+    /// Check that the faucet is enabled and that the withdrawn amount of the
+    /// given token is within its withdrawal limit
+    fn is_valid_withdrawal(
+        &self,
+        token: &Address,
+        withdrawn: Amount,
+    ) -> Result<bool> {
+        let Some(limit) = faucet::read_withdrawal_limit(&self.ctx.pre())?
+        else {
+            tracing::debug!("The faucet is not enabled on this chain");
+            return Ok(false);
+        };
+        let Some(denom) = read_denom(&self.ctx.pre(), token)? else {
+            return Ok(false);
+        };
+        Ok(Amount::from_uint(limit, denom)
+            .map(|limit| withdrawn <= limit)
+            .unwrap_or(false))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use borsh_ext::BorshSerializeExt;
+    use namada_core::ledger::gas::TxGasMeter;
+
+    use super::*;
+    use crate::core::ledger::storage::testing::TestWlStorage;
+    use crate::core::types::address::nam;
+    use crate::core::types::address::testing::established_address_1;
+    use crate::ledger::gas::VpGasMeter;
+    use crate::proto::{Code, Data};
+    use crate::types::storage::TxIndex;
+    use crate::types::token::balance_key;
+    use crate::types::transaction::TxType;
+    use crate::vm::wasm::compilation_cache::common::testing::cache as wasm_cache;
+
+    /// Run the faucet VP on a withdrawal of the given amount of NAM from a
+    /// faucet with a balance of 1000 NAM
+    fn validate_withdrawal(limit: Option<u64>, withdrawn: u64) -> bool {
+        let mut wl_storage = TestWlStorage::default();
+        if let Some(limit) = limit {
+            faucet::write_withdrawal_limit(&mut wl_storage, limit)
+                .expect("write failed");
+            wl_storage.commit_block().expect("commit failed");
+        }
+        let faucet_key = balance_key(&nam(), &faucet::ADDRESS);
+        wl_storage
+            .storage
+            .write(&faucet_key, Amount::native_whole(1000).serialize_to_vec())
+            .expect("write failed");
+
+        let receiver_key = balance_key(&nam(), &established_address_1());
+        wl_storage
+            .write_log
+            .write(
+                &faucet_key,
+                Amount::native_whole(1000 - withdrawn).serialize_to_vec(),
+            )
+            .expect("write failed");
+        wl_storage
+            .write_log
+            .write(
+                &receiver_key,
+                Amount::native_whole(withdrawn).serialize_to_vec(),
+            )
+            .expect("write failed");
+        let keys_changed = BTreeSet::from([faucet_key, receiver_key]);
+        let verifiers = BTreeSet::new();
+
+        let mut tx = Tx::from_type(TxType::Raw);
+        tx.header.chain_id = wl_storage.storage.chain_id.clone();
+        tx.set_code(Code::new(vec![], None));
+        tx.set_data(Data::new(vec![]));
+        let tx_index = TxIndex::default();
+        let gas_meter = VpGasMeter::new_from_tx_meter(
+            &TxGasMeter::new_from_sub_limit(u64::MAX.into()),
+        );
+        let (vp_wasm_cache, _vp_cache_dir) = wasm_cache();
+        let ctx = Ctx::new(
+            &faucet::ADDRESS,
+            &wl_storage.storage,
+            &wl_storage.write_log,
+            &tx,
+            &tx_index,
+            gas_meter,
+            &keys_changed,
+            &verifiers,
+            vp_wasm_cache,
+        );
+        let vp = FaucetVp { ctx };
+        vp.validate_tx(&tx, &keys_changed, &verifiers)
+            .expect("validation failed")
+    }
+
+    /// Test that anyone can withdraw up to the limit from an enabled faucet
+    #[test]
+    fn test_faucet_withdrawal() {
+        assert!(validate_withdrawal(Some(100), 100));
+        assert!(!validate_withdrawal(Some(100), 101));
+        // The faucet is disabled without a withdrawal limit
+        assert!(!validate_withdrawal(None, 1));
+    }
+}
@@ -2,6 +2,7 @@
 //! as the PoS and IBC modules.
 
 pub mod ethereum_bridge;
+pub mod faucet;
 pub mod ibc;
 pub mod masp;
 pub mod multitoken;
@@ -126,9 +126,16 @@ where
                     _ => Ok(false),
                 }
             }
+            Address::Established(_)
+                if token != &self.ctx.storage.native_token =>
+            {
+                // Tokens with their own account may be minted and burned by a
+                // wasm transaction, if the token's VP accepts it
+                Ok(verifiers.contains(token))
+            }
             _ => {
-                // ERC20 and other tokens should not be minted by a wasm
-                // transaction
+                // The native token, ERC20 and other tokens should not be
+                // minted by a wasm transaction
                 Ok(false)
             }
         }
@@ -351,6 +358,58 @@ mod tests {
         );
     }
 
+    /// Test that a token with its own account can only be minted with the
+    /// token as a verifier
+    #[test]
+    fn test_mint_by_token_vp() {
+        let mut wl_storage = TestWlStorage::default();
+        let mut keys_changed = BTreeSet::new();
+
+        let token = established_address_2();
+
+        // mint 100
+        let target = established_address_1();
+        let target_key = balance_key(&token, &target);
+        let amount = Amount::native_whole(100);
+        wl_storage
+            .write_log
+            .write(&target_key, amount.serialize_to_vec())
+            .expect("write failed");
+        keys_changed.insert(target_key);
+        let minted_key = minted_balance_key(&token);
+        wl_storage
+            .write_log
+            .write(&minted_key, amount.serialize_to_vec())
+            .expect("write failed");
+        keys_changed.insert(minted_key);
+
+        let tx_index = TxIndex::default();
+        let tx = dummy_tx(&wl_storage);
+        let (vp_wasm_cache, _vp_cache_dir) = wasm_cache();
+        let validate = |verifiers: &BTreeSet<Address>| {
+            let gas_meter = VpGasMeter::new_from_tx_meter(
+                &TxGasMeter::new_from_sub_limit(u64::MAX.into()),
+            );
+            let ctx = Ctx::new(
+                &ADDRESS,
+                &wl_storage.storage,
+                &wl_storage.write_log,
+                &tx,
+                &tx_index,
+                gas_meter,
+                &keys_changed,
+                verifiers,
+                vp_wasm_cache.clone(),
+            );
+            let vp = MultitokenVp { ctx };
+            vp.validate_tx(&tx, &keys_changed, verifiers)
+                .expect("validation failed")
+        };
+
+        assert!(!validate(&BTreeSet::new()));
+        assert!(validate(&BTreeSet::from([token])));
+    }
+
     #[test]
     fn test_invalid_mint() {
         let mut wl_storage = TestWlStorage::default();
@@ -21,6 +21,7 @@ use crate::ledger::governance::GovernanceVp;
 use crate::ledger::native_vp::ethereum_bridge::bridge_pool_vp::BridgePoolVp;
 use crate::ledger::native_vp::ethereum_bridge::nut::NonUsableTokens;
 use crate::ledger::native_vp::ethereum_bridge::vp::EthBridge;
+use crate::ledger::native_vp::faucet::FaucetVp;
 use crate::ledger::native_vp::ibc::Ibc;
 use crate::ledger::native_vp::masp::MaspVp;
 use crate::ledger::native_vp::multitoken::MultitokenVp;
@@ -93,6 +94,8 @@ pub enum Error {
     NutNativeVpError(native_vp::ethereum_bridge::nut::Error),
     #[error("MASP native VP error: {0}")]
     MaspNativeVpError(native_vp::masp::Error),
+    #[error("Faucet native VP error: {0}")]
+    FaucetNativeVpError(native_vp::faucet::Error),
     #[error("Access to an internal address {0:?} is forbidden")]
     AccessForbidden(InternalAddress),
 }
@@ -195,6 +198,7 @@ where
                 initialized_accounts: vec![],
                 ibc_events: BTreeSet::default(),
                 eth_bridge_events: BTreeSet::default(),
+                events: vec![],
             })
         }
         TxType::Decrypted(DecryptedTx::Undecryptable) => {
@@ -604,6 +608,7 @@ where
     let initialized_accounts = write_log.get_initialized_accounts();
     let changed_keys = write_log.get_keys();
     let ibc_events = write_log.take_ibc_events();
+    let events = write_log.take_events();
 
     Ok(TxResult {
         gas_used,
@@ -612,6 +617,7 @@ where
         initialized_accounts,
         ibc_events,
         eth_bridge_events: BTreeSet::default(),
+        events,
     })
 }
 
@@ -1010,6 +1016,16 @@ where
                                 gas_meter = masp.ctx.gas_meter.into_inner();
                                 (result, masp.ctx.sentinel.into_inner())
                             }
+                            InternalAddress::Faucet => {
+                                let faucet = FaucetVp { ctx };
+                                let result = faucet
+                                    .validate_tx(tx, &keys_changed, &verifiers)
+                                    .map_err(Error::FaucetNativeVpError);
+                                // Take the gas meter and the sentinel back out
+                                // of the context
+                                gas_meter = faucet.ctx.gas_meter.into_inner();
+                                (result, faucet.ctx.sentinel.into_inner())
+                            }
                         };
 
                     accepted.map_err(|err| {
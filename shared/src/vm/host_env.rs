@@ -8,13 +8,14 @@ use borsh::BorshDeserialize;
 use borsh_ext::BorshSerializeExt;
 use masp_primitives::transaction::Transaction;
 use namada_core::ledger::gas::{
-    GasMetering, TxGasMeter, MEMORY_ACCESS_GAS_PER_BYTE,
+    GasMetering, TxGasMeter, HASH_GAS_PER_BYTE, MEMORY_ACCESS_GAS_PER_BYTE,
+    VERIFY_TX_SIG_GAS,
 };
 use namada_core::ledger::masp_utils;
 use namada_core::types::address::ESTABLISHED_ADDRESS_BYTES_LEN;
 use namada_core::types::internal::KeyVal;
 use namada_core::types::storage::TX_INDEX_LENGTH;
-use namada_core::types::transaction::TxSentinel;
+use namada_core::types::transaction::{TxEvent, TxSentinel};
 use namada_core::types::validity_predicate::VpSentinel;
 use thiserror::Error;
 
@@ -27,15 +28,17 @@ use crate::ledger::gas::{self, VpGasMeter};
 use crate::ledger::storage::write_log::{self, WriteLog};
 use crate::ledger::storage::{self, Storage, StorageHasher};
 use crate::ledger::storage_api::{self, ResultExt};
-use crate::ledger::vp_host_fns;
+use crate::ledger::{parameters, vp_host_fns};
 use crate::proto::Tx;
 use crate::types::address::{self, Address};
 use crate::types::hash::Hash;
 use crate::types::ibc::{IbcEvent, IbcShieldedTransfer};
 use crate::types::internal::HostEnvResult;
+use crate::types::key::ed25519;
 use crate::types::storage::{BlockHeight, Epoch, Key, TxIndex};
 use crate::types::token::{
-    is_any_minted_balance_key, is_any_minter_key, is_any_token_balance_key,
+    self, is_any_minted_balance_key, is_any_minter_key,
+    is_any_token_balance_key,
 };
 use crate::vm::memory::VmMemory;
 use crate::vm::prefix_iter::{PrefixIteratorId, PrefixIterators};
@@ -73,6 +76,8 @@ pub enum TxRuntimeError {
     MissingTxData,
     #[error("IBC: {0}")]
     Ibc(#[from] namada_core::ledger::ibc::Error),
+    #[error("Token error: {0}")]
+    Token(storage_api::Error),
 }
 
 type TxResult<T> = std::result::Result<T, TxRuntimeError>;
@@ -996,6 +1001,33 @@ where
     tx_charge_gas(env, gas)
 }
 
+/// Emitting a custom event function exposed to the wasm VM Tx environment.
+/// The given event will be added to the write log and included in the result
+/// of the transaction.
+pub fn tx_emit_event<MEM, DB, H, CA>(
+    env: &TxVmEnv<MEM, DB, H, CA>,
+    event_ptr: u64,
+    event_len: u64,
+) -> TxResult<()>
+where
+    MEM: VmMemory,
+    DB: storage::DB + for<'iter> storage::DBIter<'iter>,
+    H: StorageHasher,
+    CA: WasmCacheAccess,
+{
+    let (event, gas) = env
+        .memory
+        .read_bytes(event_ptr, event_len as _)
+        .map_err(|e| TxRuntimeError::MemoryError(Box::new(e)))?;
+    tx_charge_gas(env, gas)?;
+    let event: TxEvent = BorshDeserialize::try_from_slice(&event)
+        .map_err(TxRuntimeError::EncodingError)?;
+    tracing::debug!("tx_emit_event {}", event.event_type);
+    let write_log = unsafe { env.ctx.write_log.get() };
+    let gas = write_log.emit_event(event);
+    tx_charge_gas(env, gas)
+}
+
 /// Getting an IBC event function exposed to the wasm VM Tx environment.
 pub fn tx_get_ibc_events<MEM, DB, H, CA>(
     env: &TxVmEnv<MEM, DB, H, CA>,
@@ -1425,6 +1457,120 @@ where
     Ok(())
 }
 
+/// Mint an amount of a token to the target address, as a function exposed to
+/// the wasm VM Tx environment. The token is added to the verifiers, so the
+/// mint has to be accepted by the token's validity predicate.
+#[allow(clippy::too_many_arguments)]
+pub fn tx_mint_tokens<MEM, DB, H, CA>(
+    env: &TxVmEnv<MEM, DB, H, CA>,
+    token_ptr: u64,
+    token_len: u64,
+    target_ptr: u64,
+    target_len: u64,
+    amount_ptr: u64,
+    amount_len: u64,
+) -> TxResult<()>
+where
+    MEM: VmMemory,
+    DB: storage::DB + for<'iter> storage::DBIter<'iter>,
+    H: StorageHasher,
+    CA: WasmCacheAccess,
+{
+    let (token, target, amount) = tx_read_token_args(
+        env, token_ptr, token_len, target_ptr, target_len, amount_ptr,
+        amount_len,
+    )?;
+
+    tracing::debug!("tx_mint_tokens {} of {} to {}", amount, token, target);
+
+    let mut ctx = env.ctx.clone();
+    storage_api::token::credit_tokens(&mut ctx, &token, &target, amount)
+        .map_err(TxRuntimeError::Token)?;
+
+    let verifiers = unsafe { env.ctx.verifiers.get() };
+    verifiers.insert(token);
+    Ok(())
+}
+
+/// Burn an amount of a token from the source address, as a function exposed
+/// to the wasm VM Tx environment. The token is added to the verifiers, so the
+/// burn has to be accepted by the token's validity predicate.
+#[allow(clippy::too_many_arguments)]
+pub fn tx_burn_tokens<MEM, DB, H, CA>(
+    env: &TxVmEnv<MEM, DB, H, CA>,
+    token_ptr: u64,
+    token_len: u64,
+    source_ptr: u64,
+    source_len: u64,
+    amount_ptr: u64,
+    amount_len: u64,
+) -> TxResult<()>
+where
+    MEM: VmMemory,
+    DB: storage::DB + for<'iter> storage::DBIter<'iter>,
+    H: StorageHasher,
+    CA: WasmCacheAccess,
+{
+    let (token, source, amount) = tx_read_token_args(
+        env, token_ptr, token_len, source_ptr, source_len, amount_ptr,
+        amount_len,
+    )?;
+
+    tracing::debug!("tx_burn_tokens {} of {} from {}", amount, token, source);
+
+    let mut ctx = env.ctx.clone();
+    storage_api::token::burn(&mut ctx, &token, &source, amount)
+        .map_err(TxRuntimeError::Token)?;
+
+    let verifiers = unsafe { env.ctx.verifiers.get() };
+    verifiers.insert(token);
+    Ok(())
+}
+
+/// Read the token address, the owner address and the amount arguments of the
+/// mint and burn functions from the wasm memory
+fn tx_read_token_args<MEM, DB, H, CA>(
+    env: &TxVmEnv<MEM, DB, H, CA>,
+    token_ptr: u64,
+    token_len: u64,
+    owner_ptr: u64,
+    owner_len: u64,
+    amount_ptr: u64,
+    amount_len: u64,
+) -> TxResult<(Address, Address, token::Amount)>
+where
+    MEM: VmMemory,
+    DB: storage::DB + for<'iter> storage::DBIter<'iter>,
+    H: StorageHasher,
+    CA: WasmCacheAccess,
+{
+    let (token, gas) = env
+        .memory
+        .read_string(token_ptr, token_len as _)
+        .map_err(|e| TxRuntimeError::MemoryError(Box::new(e)))?;
+    tx_charge_gas(env, gas)?;
+    let token =
+        Address::decode(&token).map_err(TxRuntimeError::AddressError)?;
+
+    let (owner, gas) = env
+        .memory
+        .read_string(owner_ptr, owner_len as _)
+        .map_err(|e| TxRuntimeError::MemoryError(Box::new(e)))?;
+    tx_charge_gas(env, gas)?;
+    let owner =
+        Address::decode(&owner).map_err(TxRuntimeError::AddressError)?;
+
+    let (amount, gas) = env
+        .memory
+        .read_bytes(amount_ptr, amount_len as _)
+        .map_err(|e| TxRuntimeError::MemoryError(Box::new(e)))?;
+    tx_charge_gas(env, gas)?;
+    let amount = token::Amount::try_from_slice(&amount)
+        .map_err(TxRuntimeError::EncodingError)?;
+
+    Ok((token, owner, amount))
+}
+
 /// Update a validity predicate function exposed to the wasm VM Tx environment
 pub fn tx_update_validity_predicate<MEM, DB, H, CA>(
     env: &TxVmEnv<MEM, DB, H, CA>,
@@ -1637,6 +1783,50 @@ where
     Ok(epoch.0)
 }
 
+/// Getting the block randomness function exposed to the wasm VM Tx
+/// environment. The randomness is that of the block to which the current
+/// transaction is being applied, or a zero hash if it hasn't been set yet.
+pub fn tx_get_block_randomness<MEM, DB, H, CA>(
+    env: &TxVmEnv<MEM, DB, H, CA>,
+    result_ptr: u64,
+) -> TxResult<()>
+where
+    MEM: VmMemory,
+    DB: storage::DB + for<'iter> storage::DBIter<'iter>,
+    H: StorageHasher,
+    CA: WasmCacheAccess,
+{
+    let key = parameters::storage::get_block_randomness_key();
+    // the randomness is updated by the protocol at the beginning of a block,
+    // so try to read from the write log first
+    let write_log = unsafe { env.ctx.write_log.get() };
+    let (log_val, gas) = write_log.read(&key);
+    tx_charge_gas(env, gas)?;
+    let value = match log_val {
+        Some(write_log::StorageModification::Write { ref value }) => {
+            Some(value.clone())
+        }
+        Some(_) => None,
+        None => {
+            let storage = unsafe { env.ctx.storage.get() };
+            let (value, gas) =
+                storage.read(&key).map_err(TxRuntimeError::StorageError)?;
+            tx_charge_gas(env, gas)?;
+            value
+        }
+    };
+    let randomness = value
+        .map(|value| Hash::try_from_slice(&value))
+        .transpose()
+        .map_err(TxRuntimeError::EncodingError)?
+        .unwrap_or_default();
+    let gas = env
+        .memory
+        .write_bytes(result_ptr, randomness.0)
+        .map_err(|e| TxRuntimeError::MemoryError(Box::new(e)))?;
+    tx_charge_gas(env, gas)
+}
+
 /// Get the native token's address
 pub fn tx_get_native_token<MEM, DB, H, CA>(
     env: &TxVmEnv<MEM, DB, H, CA>,
@@ -1795,6 +1985,41 @@ where
     vp_host_fns::add_gas(gas_meter, gas, sentinel)
 }
 
+/// Getting the block randomness function exposed to the wasm VM VP
+/// environment. The randomness is that of the block to which the current
+/// transaction is being applied, or a zero hash if it hasn't been set yet.
+pub fn vp_get_block_randomness<MEM, DB, H, EVAL, CA>(
+    env: &VpVmEnv<MEM, DB, H, EVAL, CA>,
+    result_ptr: u64,
+) -> vp_host_fns::EnvResult<()>
+where
+    MEM: VmMemory,
+    DB: storage::DB + for<'iter> storage::DBIter<'iter>,
+    H: StorageHasher,
+    EVAL: VpEvaluator,
+    CA: WasmCacheAccess,
+{
+    let gas_meter = unsafe { env.ctx.gas_meter.get() };
+    let sentinel = unsafe { env.ctx.sentinel.get() };
+    let storage = unsafe { env.ctx.storage.get() };
+    let write_log = unsafe { env.ctx.write_log.get() };
+    // the randomness is updated by the protocol before any tx is applied, so
+    // it is the same in the prior and posterior state
+    let key = parameters::storage::get_block_randomness_key();
+    let value =
+        vp_host_fns::read_pre(gas_meter, storage, write_log, &key, sentinel)?;
+    let randomness = value
+        .map(|value| Hash::try_from_slice(&value))
+        .transpose()
+        .map_err(vp_host_fns::RuntimeError::EncodingError)?
+        .unwrap_or_default();
+    let gas = env
+        .memory
+        .write_bytes(result_ptr, randomness.0)
+        .map_err(|e| vp_host_fns::RuntimeError::MemoryError(Box::new(e)))?;
+    vp_host_fns::add_gas(gas_meter, gas, sentinel)
+}
+
 /// Getting the transaction hash function exposed to the wasm VM VP environment.
 pub fn vp_get_tx_code_hash<MEM, DB, H, EVAL, CA>(
     env: &VpVmEnv<MEM, DB, H, EVAL, CA>,
@@ -1965,6 +2190,110 @@ where
     }
 }
 
+/// Hash the given data with SHA-256 function exposed to the wasm VM VP
+/// environment. The 32 bytes digest is written to the result pointer.
+pub fn vp_hash_sha256<MEM, DB, H, EVAL, CA>(
+    env: &VpVmEnv<MEM, DB, H, EVAL, CA>,
+    data_ptr: u64,
+    data_len: u64,
+    result_ptr: u64,
+) -> vp_host_fns::EnvResult<()>
+where
+    MEM: VmMemory,
+    DB: storage::DB + for<'iter> storage::DBIter<'iter>,
+    H: StorageHasher,
+    EVAL: VpEvaluator,
+    CA: WasmCacheAccess,
+{
+    vp_hash(env, HashFunction::Sha256, data_ptr, data_len, result_ptr)
+}
+
+/// Hash the given data with BLAKE2b-256 function exposed to the wasm VM VP
+/// environment. The 32 bytes digest is written to the result pointer.
+pub fn vp_hash_blake2b<MEM, DB, H, EVAL, CA>(
+    env: &VpVmEnv<MEM, DB, H, EVAL, CA>,
+    data_ptr: u64,
+    data_len: u64,
+    result_ptr: u64,
+) -> vp_host_fns::EnvResult<()>
+where
+    MEM: VmMemory,
+    DB: storage::DB + for<'iter> storage::DBIter<'iter>,
+    H: StorageHasher,
+    EVAL: VpEvaluator,
+    CA: WasmCacheAccess,
+{
+    vp_hash(env, HashFunction::Blake2b, data_ptr, data_len, result_ptr)
+}
+
+fn vp_hash<MEM, DB, H, EVAL, CA>(
+    env: &VpVmEnv<MEM, DB, H, EVAL, CA>,
+    function: HashFunction,
+    data_ptr: u64,
+    data_len: u64,
+    result_ptr: u64,
+) -> vp_host_fns::EnvResult<()>
+where
+    MEM: VmMemory,
+    DB: storage::DB + for<'iter> storage::DBIter<'iter>,
+    H: StorageHasher,
+    EVAL: VpEvaluator,
+    CA: WasmCacheAccess,
+{
+    let gas_meter = unsafe { env.ctx.gas_meter.get() };
+    let sentinel = unsafe { env.ctx.sentinel.get() };
+    let (data, gas) = env
+        .memory
+        .read_bytes(data_ptr, data_len as _)
+        .map_err(|e| vp_host_fns::RuntimeError::MemoryError(Box::new(e)))?;
+    vp_host_fns::add_gas(gas_meter, gas, sentinel)?;
+    vp_host_fns::add_gas(gas_meter, data_len * HASH_GAS_PER_BYTE, sentinel)?;
+    let digest = function.digest(&data);
+    let gas = env
+        .memory
+        .write_bytes(result_ptr, digest)
+        .map_err(|e| vp_host_fns::RuntimeError::MemoryError(Box::new(e)))?;
+    vp_host_fns::add_gas(gas_meter, gas, sentinel)
+}
+
+/// Verify an ed25519 signature of the given message function exposed to the
+/// wasm VM VP environment. The public key and the signature are Borsh encoded.
+/// Returns [`HostEnvResult::Success`] for a valid signature or
+/// [`HostEnvResult::Fail`] otherwise.
+#[allow(clippy::too_many_arguments)]
+pub fn vp_verify_ed25519_signature<MEM, DB, H, EVAL, CA>(
+    env: &VpVmEnv<MEM, DB, H, EVAL, CA>,
+    pk_ptr: u64,
+    pk_len: u64,
+    sig_ptr: u64,
+    sig_len: u64,
+    msg_ptr: u64,
+    msg_len: u64,
+) -> vp_host_fns::EnvResult<i64>
+where
+    MEM: VmMemory,
+    DB: storage::DB + for<'iter> storage::DBIter<'iter>,
+    H: StorageHasher,
+    EVAL: VpEvaluator,
+    CA: WasmCacheAccess,
+{
+    let gas_meter = unsafe { env.ctx.gas_meter.get() };
+    let sentinel = unsafe { env.ctx.sentinel.get() };
+    let mut read = |ptr: u64, len: u64| {
+        let (bytes, gas) = env
+            .memory
+            .read_bytes(ptr, len as _)
+            .map_err(|e| vp_host_fns::RuntimeError::MemoryError(Box::new(e)))?;
+        vp_host_fns::add_gas(gas_meter, gas, sentinel)?;
+        Ok::<_, vp_host_fns::RuntimeError>(bytes)
+    };
+    let pk = read(pk_ptr, pk_len)?;
+    let sig = read(sig_ptr, sig_len)?;
+    let msg = read(msg_ptr, msg_len)?;
+    vp_host_fns::add_gas(gas_meter, VERIFY_TX_SIG_GAS, sentinel)?;
+    Ok(HostEnvResult::from(verify_ed25519_signature(&pk, &sig, &msg)).to_i64())
+}
+
 /// Log a string from exposed to the wasm VM Tx environment. The message will be
 /// printed at the [`tracing::Level::INFO`]. This function is for development
 /// only.
@@ -2165,6 +2494,136 @@ where
     }
 }
 
+/// The hash functions exposed to the wasm VM
+#[derive(Clone, Copy, Debug)]
+enum HashFunction {
+    Sha256,
+    Blake2b,
+}
+
+impl HashFunction {
+    /// Get the 32 bytes digest of the given data
+    fn digest(self, data: &[u8]) -> [u8; 32] {
+        match self {
+            Self::Sha256 => Hash::sha256(data).0,
+            Self::Blake2b => blake2b_simd::Params::new()
+                .hash_length(32)
+                .hash(data)
+                .as_bytes()
+                .try_into()
+                .expect("The digest length must be 32 bytes"),
+        }
+    }
+}
+
+/// Verify an ed25519 signature of the message, with the Borsh encoded public
+/// key and signature. Returns `false` if any of them cannot be decoded.
+fn verify_ed25519_signature(pk: &[u8], sig: &[u8], msg: &[u8]) -> bool {
+    match (
+        ed25519::PublicKey::try_from_slice(pk),
+        ed25519::Signature::try_from_slice(sig),
+    ) {
+        (Ok(pk), Ok(sig)) => pk.0.verify(&sig.0, msg).is_ok(),
+        _ => false,
+    }
+}
+
+/// Hash the given data with SHA-256 function exposed to the wasm VM Tx
+/// environment. The 32 bytes digest is written to the result pointer.
+pub fn tx_hash_sha256<MEM, DB, H, CA>(
+    env: &TxVmEnv<MEM, DB, H, CA>,
+    data_ptr: u64,
+    data_len: u64,
+    result_ptr: u64,
+) -> TxResult<()>
+where
+    MEM: VmMemory,
+    DB: storage::DB + for<'iter> storage::DBIter<'iter>,
+    H: StorageHasher,
+    CA: WasmCacheAccess,
+{
+    tx_hash(env, HashFunction::Sha256, data_ptr, data_len, result_ptr)
+}
+
+/// Hash the given data with BLAKE2b-256 function exposed to the wasm VM Tx
+/// environment. The 32 bytes digest is written to the result pointer.
+pub fn tx_hash_blake2b<MEM, DB, H, CA>(
+    env: &TxVmEnv<MEM, DB, H, CA>,
+    data_ptr: u64,
+    data_len: u64,
+    result_ptr: u64,
+) -> TxResult<()>
+where
+    MEM: VmMemory,
+    DB: storage::DB + for<'iter> storage::DBIter<'iter>,
+    H: StorageHasher,
+    CA: WasmCacheAccess,
+{
+    tx_hash(env, HashFunction::Blake2b, data_ptr, data_len, result_ptr)
+}
+
+fn tx_hash<MEM, DB, H, CA>(
+    env: &TxVmEnv<MEM, DB, H, CA>,
+    function: HashFunction,
+    data_ptr: u64,
+    data_len: u64,
+    result_ptr: u64,
+) -> TxResult<()>
+where
+    MEM: VmMemory,
+    DB: storage::DB + for<'iter> storage::DBIter<'iter>,
+    H: StorageHasher,
+    CA: WasmCacheAccess,
+{
+    let (data, gas) = env
+        .memory
+        .read_bytes(data_ptr, data_len as _)
+        .map_err(|e| TxRuntimeError::MemoryError(Box::new(e)))?;
+    tx_charge_gas(env, gas)?;
+    tx_charge_gas(env, data_len * HASH_GAS_PER_BYTE)?;
+    let digest = function.digest(&data);
+    let gas = env
+        .memory
+        .write_bytes(result_ptr, digest)
+        .map_err(|e| TxRuntimeError::MemoryError(Box::new(e)))?;
+    tx_charge_gas(env, gas)
+}
+
+/// Verify an ed25519 signature of the given message function exposed to the
+/// wasm VM Tx environment. The public key and the signature are Borsh encoded.
+/// Returns [`HostEnvResult::Success`] for a valid signature or
+/// [`HostEnvResult::Fail`] otherwise.
+#[allow(clippy::too_many_arguments)]
+pub fn tx_verify_ed25519_signature<MEM, DB, H, CA>(
+    env: &TxVmEnv<MEM, DB, H, CA>,
+    pk_ptr: u64,
+    pk_len: u64,
+    sig_ptr: u64,
+    sig_len: u64,
+    msg_ptr: u64,
+    msg_len: u64,
+) -> TxResult<i64>
+where
+    MEM: VmMemory,
+    DB: storage::DB + for<'iter> storage::DBIter<'iter>,
+    H: StorageHasher,
+    CA: WasmCacheAccess,
+{
+    let read = |ptr: u64, len: u64| {
+        let (bytes, gas) = env
+            .memory
+            .read_bytes(ptr, len as _)
+            .map_err(|e| TxRuntimeError::MemoryError(Box::new(e)))?;
+        tx_charge_gas(env, gas)?;
+        Ok::<_, TxRuntimeError>(bytes)
+    };
+    let pk = read(pk_ptr, pk_len)?;
+    let sig = read(sig_ptr, sig_len)?;
+    let msg = read(msg_ptr, msg_len)?;
+    tx_charge_gas(env, VERIFY_TX_SIG_GAS)?;
+    Ok(HostEnvResult::from(verify_ed25519_signature(&pk, &sig, &msg)).to_i64())
+}
+
 /// Appends the new note commitments to the tree in storage
 pub fn tx_update_masp_note_commitment_tree<MEM, DB, H, CA>(
     env: &TxVmEnv<MEM, DB, H, CA>,
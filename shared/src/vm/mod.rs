@@ -4,7 +4,10 @@ use std::ffi::c_void;
 use std::marker::PhantomData;
 use std::slice;
 
-use wasmparser::{Validator, WasmFeatures};
+use wasmparser::{
+    BinaryReaderError, MemoryType, Parser, Payload, TypeRef, Validator,
+    WasmFeatures,
+};
 
 pub mod host_env;
 pub mod memory;
@@ -25,7 +28,7 @@ const UNTRUSTED_WASM_FEATURES: WasmFeatures = WasmFeatures {
     relaxed_simd: false,
     threads: false,
     tail_call: false,
-    floats: true,
+    floats: false,
     multi_memory: false,
     exceptions: false,
     memory64: false,
@@ -36,6 +39,12 @@ const UNTRUSTED_WASM_FEATURES: WasmFeatures = WasmFeatures {
     gc: false,
 };
 
+/// The maximum number of pages of linear memory (64 KiB each) that an untrusted
+/// wasm module may declare as its initial memory size. This matches the
+/// maximum memory available to transactions and validity predicates at run
+/// time.
+pub const UNTRUSTED_WASM_MAX_MEMORY_PAGES: u64 = 200;
+
 #[allow(missing_docs)]
 #[derive(Error, Debug, Clone)]
 pub enum WasmValidationError {
@@ -44,6 +53,11 @@ pub enum WasmValidationError {
          {UNTRUSTED_WASM_FEATURES:?}"
     )]
     ForbiddenWasmFeatures(wasmparser::BinaryReaderError),
+    #[error(
+        "Invalid WASM declaring a memory of {0} initial pages, the maximum is \
+         {UNTRUSTED_WASM_MAX_MEMORY_PAGES}"
+    )]
+    ExcessiveMemory(u64),
 }
 
 /// WASM Cache access level, used to limit dry-ran transactions to read-only
@@ -239,5 +253,42 @@ pub fn validate_untrusted_wasm(
     let _types = validator
         .validate_all(wasm_code.as_ref())
         .map_err(WasmValidationError::ForbiddenWasmFeatures)?;
-    Ok(())
+    // Reject modules that couldn't be instantiated within the memory limits
+    // before they get compiled
+    let memories = declared_memories(wasm_code.as_ref())
+        .map_err(WasmValidationError::ForbiddenWasmFeatures)?;
+    match memories
+        .iter()
+        .find(|memory| memory.initial > UNTRUSTED_WASM_MAX_MEMORY_PAGES)
+    {
+        Some(memory) => {
+            Err(WasmValidationError::ExcessiveMemory(memory.initial))
+        }
+        None => Ok(()),
+    }
+}
+
+/// Find the linear memories that a wasm module imports or defines
+fn declared_memories(
+    wasm_code: &[u8],
+) -> Result<Vec<MemoryType>, BinaryReaderError> {
+    let mut memories = vec![];
+    for payload in Parser::new(0).parse_all(wasm_code) {
+        match payload? {
+            Payload::ImportSection(imports) => {
+                for import in imports {
+                    if let TypeRef::Memory(memory) = import?.ty {
+                        memories.push(memory);
+                    }
+                }
+            }
+            Payload::MemorySection(section) => {
+                for memory in section {
+                    memories.push(memory?);
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(memories)
 }
@@ -21,7 +21,7 @@ use wasmer_cache::{FileSystemCache, Hash as CacheHash};
 use crate::core::types::hash::Hash;
 use crate::types::control_flow::time::{ExponentialBackoff, SleepStrategy};
 use crate::vm::wasm::run::untrusted_wasm_store;
-use crate::vm::wasm::{self, memory};
+use crate::vm::wasm::{self, memory, WasmCompiler};
 use crate::vm::{WasmCacheAccess, WasmCacheRoAccess};
 
 /// Cache handle. Thread-safe.
@@ -33,6 +33,8 @@ pub struct Cache<N: CacheName, A: WasmCacheAccess> {
     progress: Arc<RwLock<HashMap<Hash, Compilation>>>,
     /// In-memory LRU cache of compiled modules
     in_memory: Arc<RwLock<MemoryCache>>,
+    /// The compiler used for the WASM code
+    compiler: WasmCompiler,
     /// The cache's name
     name: PhantomData<N>,
     /// Cache access level
@@ -87,11 +89,19 @@ impl<N: CacheName, A: WasmCacheAccess> Cache<N, A> {
             dir,
             progress: Default::default(),
             in_memory,
+            compiler: WasmCompiler::default(),
             name: Default::default(),
             access: Default::default(),
         }
     }
 
+    /// Set the compiler used for the WASM code. The files of the modules
+    /// compiled by different compilers are kept apart.
+    pub fn with_compiler(mut self, compiler: WasmCompiler) -> Self {
+        self.compiler = compiler;
+        self
+    }
+
     /// Get a WASM module from LRU cache, from a file or compile it and cache
     /// it. If the cache access is set to [`crate::vm::WasmCacheRwAccess`], it
     /// updates the position in the LRU cache. Otherwise, the compiled
@@ -130,7 +140,7 @@ impl<N: CacheName, A: WasmCacheAccess> Cache<N, A> {
                 N::name(),
                 hash.to_string()
             );
-            return Ok(Some((module.clone(), store())));
+            return Ok(Some((module.clone(), store(self.compiler))));
         }
         drop(in_memory);
 
@@ -153,11 +163,14 @@ impl<N: CacheName, A: WasmCacheAccess> Cache<N, A> {
                             N::name(),
                             hash.to_string()
                         );
-                        return Ok(Some((module.clone(), store())));
+                        return Ok(Some((
+                            module.clone(),
+                            store(self.compiler),
+                        )));
                     }
 
                     if let Ok((module, store)) =
-                        file_load_module(&self.dir, hash)
+                        file_load_module(&self.dir, self.compiler, hash)
                     {
                         tracing::info!(
                             "{} found {} in file cache.",
@@ -186,21 +199,23 @@ impl<N: CacheName, A: WasmCacheAccess> Cache<N, A> {
                 }
                 None => {
                     drop(progress);
-                    let (module, store) = if module_file_exists(&self.dir, hash)
-                    {
-                        tracing::info!(
-                            "Trying to load {} {} from file.",
-                            N::name(),
-                            hash.to_string()
-                        );
-                        if let Ok(res) = file_load_module(&self.dir, hash) {
-                            res
+                    let (module, store) =
+                        if module_file_exists(&self.dir, self.compiler, hash) {
+                            tracing::info!(
+                                "Trying to load {} {} from file.",
+                                N::name(),
+                                hash.to_string()
+                            );
+                            if let Ok(res) =
+                                file_load_module(&self.dir, self.compiler, hash)
+                            {
+                                res
+                            } else {
+                                return Ok(None);
+                            }
                         } else {
                             return Ok(None);
-                        }
-                    } else {
-                        return Ok(None);
-                    };
+                        };
 
                     // Update progress
                     let mut progress = self.progress.write().unwrap();
@@ -230,7 +245,7 @@ impl<N: CacheName, A: WasmCacheAccess> Cache<N, A> {
                 N::name(),
                 hash.to_string()
             );
-            return Ok(Some((module.clone(), store())));
+            return Ok(Some((module.clone(), store(self.compiler))));
         }
         drop(in_memory);
 
@@ -253,11 +268,14 @@ impl<N: CacheName, A: WasmCacheAccess> Cache<N, A> {
                             N::name(),
                             hash.to_string()
                         );
-                        return Ok(Some((module.clone(), store())));
+                        return Ok(Some((
+                            module.clone(),
+                            store(self.compiler),
+                        )));
                     }
 
                     if let Ok((module, store)) =
-                        file_load_module(&self.dir, hash)
+                        file_load_module(&self.dir, self.compiler, hash)
                     {
                         tracing::info!(
                             "{} found {} in file cache.",
@@ -283,13 +301,16 @@ impl<N: CacheName, A: WasmCacheAccess> Cache<N, A> {
                 None => {
                     drop(progress);
 
-                    return if module_file_exists(&self.dir, hash) {
+                    return if module_file_exists(&self.dir, self.compiler, hash)
+                    {
                         tracing::info!(
                             "Trying to load {} {} from file.",
                             N::name(),
                             hash.to_string()
                         );
-                        if let Ok(res) = file_load_module(&self.dir, hash) {
+                        if let Ok(res) =
+                            file_load_module(&self.dir, self.compiler, hash)
+                        {
                             return Ok(Some(res));
                         } else {
                             return Ok(None);
@@ -316,7 +337,7 @@ impl<N: CacheName, A: WasmCacheAccess> Cache<N, A> {
                 Some(_) => return self.peek(&hash),
                 None => {
                     let code = wasm::run::prepare_wasm_code(code)?;
-                    return Ok(Some(compile(code)?));
+                    return Ok(Some(compile(self.compiler, code)?));
                 }
             }
         }
@@ -332,10 +353,10 @@ impl<N: CacheName, A: WasmCacheAccess> Cache<N, A> {
         tracing::info!("Compiling {} {}.", N::name(), hash.to_string());
 
         match wasm::run::prepare_wasm_code(code) {
-            Ok(code) => match compile(code) {
+            Ok(code) => match compile(self.compiler, code) {
                 Ok((module, store)) => {
                     // Write the file
-                    file_write_module(&self.dir, &module, &hash);
+                    file_write_module(&self.dir, self.compiler, &module, &hash);
 
                     // Update progress
                     let mut progress = self.progress.write().unwrap();
@@ -382,7 +403,7 @@ impl<N: CacheName, A: WasmCacheAccess> Cache<N, A> {
                     // Already known, do nothing
                 }
                 None => {
-                    if module_file_exists(&self.dir, &hash) {
+                    if module_file_exists(&self.dir, self.compiler, &hash) {
                         progress.insert(hash, Compilation::Done);
                         return;
                     }
@@ -391,12 +412,13 @@ impl<N: CacheName, A: WasmCacheAccess> Cache<N, A> {
                     let progress = self.progress.clone();
                     let code = code.as_ref().to_vec();
                     let dir = self.dir.clone();
+                    let compiler = self.compiler;
                     std::thread::spawn(move || {
                         tracing::info!("Compiling WASM {}.", hash.to_string());
 
                         let (_module, _store) =
                             match wasm::run::prepare_wasm_code(code) {
-                                Ok(code) => match compile(code) {
+                                Ok(code) => match compile(compiler, code) {
                                     Ok((module, store)) => {
                                         let mut progress =
                                             progress.write().unwrap();
@@ -418,7 +440,9 @@ impl<N: CacheName, A: WasmCacheAccess> Cache<N, A> {
                                                 N::name()
                                             )
                                         }
-                                        file_write_module(&dir, &module, &hash);
+                                        file_write_module(
+                                            &dir, compiler, &module, &hash,
+                                        );
                                         (module, store)
                                     }
                                     Err(err) => {
@@ -460,6 +484,7 @@ impl<N: CacheName, A: WasmCacheAccess> Cache<N, A> {
             dir: self.dir.clone(),
             progress: self.progress.clone(),
             in_memory: self.in_memory.clone(),
+            compiler: self.compiler,
             name: Default::default(),
             access: Default::default(),
         }
@@ -471,39 +496,49 @@ fn hash_of_code(code: impl AsRef<[u8]>) -> Hash {
 }
 
 fn compile(
+    compiler: WasmCompiler,
     code: impl AsRef<[u8]>,
 ) -> Result<(Module, Store), wasm::run::Error> {
     // There's an issue with dylib compiler on mac in linker and on linux
     // with the dylib's store loading the dylib from a file, so we're caching a
     // module serialized to bytes instead for now.
-    universal::compile(code).map_err(wasm::run::Error::CompileError)
+    universal::compile(compiler, code).map_err(wasm::run::Error::CompileError)
 }
 
-fn file_ext() -> &'static str {
+fn file_ext(compiler: WasmCompiler) -> &'static str {
     // This has to be using the file_ext matching the compilation method in the
     // `fn compile`
-    universal::FILE_EXT
+    match compiler {
+        WasmCompiler::Singlepass => universal::FILE_EXT,
+        WasmCompiler::Cranelift => universal::CRANELIFT_FILE_EXT,
+    }
 }
 
-fn store() -> Store {
+fn store(compiler: WasmCompiler) -> Store {
     // This has to be using the store matching the compilation method in the
     // `fn compile`
-    universal::store()
+    universal::store(compiler)
 }
 
-fn file_write_module(dir: impl AsRef<Path>, module: &Module, hash: &Hash) {
+fn file_write_module(
+    dir: impl AsRef<Path>,
+    compiler: WasmCompiler,
+    module: &Module,
+    hash: &Hash,
+) {
     use wasmer_cache::Cache;
-    let mut fs_cache = fs_cache(dir, hash);
+    let mut fs_cache = fs_cache(dir, compiler, hash);
     fs_cache.store(CacheHash::new(hash.0), module).unwrap();
 }
 
 fn file_load_module(
     dir: impl AsRef<Path>,
+    compiler: WasmCompiler,
     hash: &Hash,
 ) -> Result<(Module, Store), wasmer::DeserializeError> {
     use wasmer_cache::Cache;
-    let fs_cache = fs_cache(dir, hash);
-    let store = store();
+    let fs_cache = fs_cache(dir, compiler, hash);
+    let store = store(compiler);
     let hash = CacheHash::new(hash.0);
     let module = unsafe { fs_cache.load(&store, hash) };
     if let Err(err) = module.as_ref() {
@@ -515,21 +550,29 @@ fn file_load_module(
     Ok((module?, store))
 }
 
-fn fs_cache(dir: impl AsRef<Path>, hash: &Hash) -> FileSystemCache {
+fn fs_cache(
+    dir: impl AsRef<Path>,
+    compiler: WasmCompiler,
+    hash: &Hash,
+) -> FileSystemCache {
     let path = dir.as_ref().join(hash.to_string().to_lowercase());
     let mut fs_cache = FileSystemCache::new(path).unwrap();
-    fs_cache.set_cache_extension(Some(file_ext()));
+    fs_cache.set_cache_extension(Some(file_ext(compiler)));
     fs_cache
 }
 
-fn module_file_exists(dir: impl AsRef<Path>, hash: &Hash) -> bool {
+fn module_file_exists(
+    dir: impl AsRef<Path>,
+    compiler: WasmCompiler,
+    hash: &Hash,
+) -> bool {
     let file =
         dir.as_ref()
             .join(hash.to_string().to_lowercase())
             .join(format!(
                 "{}.{}",
                 hash.to_string().to_lowercase(),
-                file_ext()
+                file_ext(compiler)
             ));
     file.exists()
 }
@@ -538,23 +581,30 @@ fn module_file_exists(dir: impl AsRef<Path>, hash: &Hash) -> bool {
 mod universal {
     use super::*;
 
+    // NOTE: The version in the file extensions must be bumped whenever the
+    // compiler settings change, so that the modules compiled with the old
+    // settings are not loaded from the cache
     #[allow(dead_code)]
-    pub const FILE_EXT: &str = "bin";
+    pub const FILE_EXT: &str = "v1.bin";
+
+    /// The file extension of the modules compiled with Cranelift
+    pub const CRANELIFT_FILE_EXT: &str = "cranelift.v1.bin";
 
     /// Compile wasm with a universal engine.
     #[allow(dead_code)]
     pub fn compile(
+        compiler: WasmCompiler,
         code: impl AsRef<[u8]>,
     ) -> Result<(Module, Store), wasmer::CompileError> {
-        let store = store();
+        let store = store(compiler);
         let module = Module::new(&store, code.as_ref())?;
         Ok((module, store))
     }
 
     /// Universal WASM store
     #[allow(dead_code)]
-    pub fn store() -> Store {
-        untrusted_wasm_store(memory::vp_limit())
+    pub fn store(compiler: WasmCompiler) -> Store {
+        untrusted_wasm_store(compiler, memory::vp_limit())
     }
 }
 
@@ -585,7 +635,8 @@ mod dylib {
     /// Dylib WASM store
     #[allow(dead_code)]
     pub fn store() -> Store {
-        let compiler = wasmer_compiler_singlepass::Singlepass::default();
+        let mut compiler = wasmer_compiler_singlepass::Singlepass::default();
+        compiler.canonicalize_nans(true);
         let engine = wasmer_engine_dylib::Dylib::new(compiler).engine();
         Store::new_with_tunables(&engine, memory::vp_limit())
     }
@@ -671,7 +722,11 @@ mod test {
                 );
 
                 assert!(
-                    module_file_exists(&cache.dir, &tx_read_storage_key.hash),
+                    module_file_exists(
+                        &cache.dir,
+                        cache.compiler,
+                        &tx_read_storage_key.hash
+                    ),
                     "The file must be written"
                 );
             }
@@ -708,13 +763,21 @@ mod test {
                 );
 
                 assert!(
-                    module_file_exists(&cache.dir, &tx_no_op.hash),
+                    module_file_exists(
+                        &cache.dir,
+                        cache.compiler,
+                        &tx_no_op.hash
+                    ),
                     "The file must be written"
                 );
 
                 // The previous module's file should still exist
                 assert!(
-                    module_file_exists(&cache.dir, &tx_read_storage_key.hash),
+                    module_file_exists(
+                        &cache.dir,
+                        cache.compiler,
+                        &tx_read_storage_key.hash
+                    ),
                     "The file must be written"
                 );
                 // But it should not be in-memory
@@ -758,13 +821,21 @@ mod test {
                 );
 
                 assert!(
-                    module_file_exists(&cache.dir, &tx_read_storage_key.hash),
+                    module_file_exists(
+                        &cache.dir,
+                        cache.compiler,
+                        &tx_read_storage_key.hash
+                    ),
                     "The file must be written"
                 );
 
                 // The previous module's file should still exist
                 assert!(
-                    module_file_exists(&cache.dir, &tx_no_op.hash),
+                    module_file_exists(
+                        &cache.dir,
+                        cache.compiler,
+                        &tx_no_op.hash
+                    ),
                     "The file must be written"
                 );
                 // But it should not be in-memory
@@ -799,13 +870,21 @@ mod test {
                 );
 
                 assert!(
-                    module_file_exists(&cache.dir, &tx_read_storage_key.hash),
+                    module_file_exists(
+                        &cache.dir,
+                        cache.compiler,
+                        &tx_read_storage_key.hash
+                    ),
                     "The file must be written"
                 );
 
                 // The previous module's file should still exist
                 assert!(
-                    module_file_exists(&cache.dir, &tx_no_op.hash),
+                    module_file_exists(
+                        &cache.dir,
+                        cache.compiler,
+                        &tx_no_op.hash
+                    ),
                     "The file must be written"
                 );
                 // But it should not be in-memory
@@ -876,7 +955,7 @@ mod test {
         assert_matches!(progress.get(&hash), None, "Any progress is removed");
 
         assert!(
-            !module_file_exists(&cache.dir, &hash),
+            !module_file_exists(&cache.dir, cache.compiler, &hash),
             "The file must not be written"
         );
     }
@@ -934,7 +1013,11 @@ mod test {
                 );
 
                 assert!(
-                    module_file_exists(&cache.dir, &vp_always_true.hash),
+                    module_file_exists(
+                        &cache.dir,
+                        cache.compiler,
+                        &vp_always_true.hash
+                    ),
                     "The file must be written"
                 );
             }
@@ -970,13 +1053,21 @@ mod test {
                 );
 
                 assert!(
-                    module_file_exists(&cache.dir, &vp_eval.hash),
+                    module_file_exists(
+                        &cache.dir,
+                        cache.compiler,
+                        &vp_eval.hash
+                    ),
                     "The file must be written"
                 );
 
                 // The previous module's file should still exist
                 assert!(
-                    module_file_exists(&cache.dir, &vp_always_true.hash),
+                    module_file_exists(
+                        &cache.dir,
+                        cache.compiler,
+                        &vp_always_true.hash
+                    ),
                     "The file must be written"
                 );
                 // But it should not be in-memory
@@ -1031,12 +1122,48 @@ mod test {
             );
 
             assert!(
-                !module_file_exists(&cache.dir, &hash),
+                !module_file_exists(&cache.dir, cache.compiler, &hash),
                 "The file must not be written"
             );
         }
     }
 
+    /// Test that the modules compiled with Cranelift are cached apart from the
+    /// ones compiled with Singlepass
+    #[test]
+    fn test_compile_with_cranelift() {
+        let tx_no_op = load_wasm(TestWasms::TxNoOp.path());
+        let (cache, _tmp_dir) = cache(50 * 1024 * 1024);
+        let mut cache = cache.with_compiler(WasmCompiler::Cranelift);
+
+        let fetched = cache.compile_or_fetch(&tx_no_op.code).unwrap();
+        assert_matches!(fetched, Some(_), "The code should be compiled");
+        assert!(
+            module_file_exists(
+                &cache.dir,
+                WasmCompiler::Cranelift,
+                &tx_no_op.hash
+            ),
+            "The file must be written"
+        );
+        assert!(
+            !module_file_exists(
+                &cache.dir,
+                WasmCompiler::Singlepass,
+                &tx_no_op.hash
+            ),
+            "The Singlepass file must not be written"
+        );
+
+        // The module compiled with Cranelift can be loaded from its file
+        assert!(file_load_module(
+            &cache.dir,
+            WasmCompiler::Cranelift,
+            &tx_no_op.hash
+        )
+        .is_ok());
+    }
+
     /// Get the WASM code bytes, its hash and find the compiled module's size
     fn load_wasm(file: impl AsRef<Path>) -> WasmWithMeta {
         // When `WeightScale` calls `loupe::size_of_val` in the cache, for some
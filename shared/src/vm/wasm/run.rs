@@ -9,6 +9,7 @@ use namada_core::ledger::storage::write_log::StorageModification;
 use namada_core::types::transaction::TxSentinel;
 use namada_core::types::validity_predicate::VpSentinel;
 use parity_wasm::elements;
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use wasmer::{BaseTunables, Module, Store};
 
@@ -474,14 +475,45 @@ where
     }
 }
 
+/// The compiler used to compile WASM code to native code. Both compilers
+/// produce deterministic results, as the gas metering is injected into the
+/// WASM code itself and the NaNs are canonicalized.
+#[derive(
+    Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum WasmCompiler {
+    /// Single-pass compiler with a fast and predictable compilation, best
+    /// suited for validators
+    #[default]
+    Singlepass,
+    /// Optimizing compiler with a slower compilation, but a faster execution
+    /// of the compiled code, best suited for archive and query nodes
+    Cranelift,
+}
+
 /// Prepare a wasm store for untrusted code.
-pub fn untrusted_wasm_store(limit: Limit<BaseTunables>) -> wasmer::Store {
-    // Use Singlepass compiler with the default settings
-    let compiler = wasmer_compiler_singlepass::Singlepass::default();
-    wasmer::Store::new_with_tunables(
-        &wasmer_engine_universal::Universal::new(compiler).engine(),
-        limit,
-    )
+pub fn untrusted_wasm_store(
+    compiler: WasmCompiler,
+    limit: Limit<BaseTunables>,
+) -> wasmer::Store {
+    // Use the compilers with the default settings, except for NaN
+    // canonicalization that makes the results of floating point operations
+    // deterministic across platforms
+    let engine = match compiler {
+        WasmCompiler::Singlepass => {
+            let mut compiler =
+                wasmer_compiler_singlepass::Singlepass::default();
+            compiler.canonicalize_nans(true);
+            wasmer_engine_universal::Universal::new(compiler).engine()
+        }
+        WasmCompiler::Cranelift => {
+            let mut compiler = wasmer::Cranelift::default();
+            compiler.canonicalize_nans(true);
+            wasmer_engine_universal::Universal::new(compiler).engine()
+        }
+    };
+    wasmer::Store::new_with_tunables(&engine, limit)
 }
 
 /// Inject gas counter and stack-height limiter into the given wasm code
@@ -642,7 +674,7 @@ mod tests {
     use crate::types::hash::Hash;
     use crate::types::transaction::TxType;
     use crate::types::validity_predicate::EvalVp;
-    use crate::vm::wasm;
+    use crate::vm::{wasm, UNTRUSTED_WASM_MAX_MEMORY_PAGES};
 
     const TX_GAS_LIMIT: u64 = 10_000_000_000;
 
@@ -694,6 +726,74 @@ mod tests {
         assert!(result.is_ok(), "Expected success. Got {:?}", result);
     }
 
+    /// Test that wasm declaring more initial memory than what is available at
+    /// run time is rejected by the validation, before it gets compiled.
+    #[test]
+    fn test_wasm_validation_memory_limit() {
+        assert_eq!(
+            UNTRUSTED_WASM_MAX_MEMORY_PAGES,
+            u64::from(memory::TX_MEMORY_MAX_PAGES)
+                .max(u64::from(memory::VP_MEMORY_MAX_PAGES))
+        );
+        let module = |pages: u64| {
+            wasmer::wat2wasm(
+                format!(
+                    r#"(module (memory {pages}) (export "memory" (memory 0)))"#
+                )
+                .as_bytes(),
+            )
+            .expect("unexpected error converting wat2wasm")
+            .into_owned()
+        };
+
+        let max_pages = UNTRUSTED_WASM_MAX_MEMORY_PAGES;
+        let result = validate_untrusted_wasm(module(max_pages));
+        assert!(result.is_ok(), "Expected success. Got {:?}", result);
+
+        let error = validate_untrusted_wasm(module(max_pages + 1))
+            .expect_err("Expecting the excessive memory to be rejected");
+        assert!(matches!(
+            error,
+            WasmValidationError::ExcessiveMemory(pages)
+                if pages == max_pages + 1
+        ));
+    }
+
+    /// Test that wasm using floating point types or instructions is rejected
+    /// by the validation, as their results may differ across platforms.
+    #[test]
+    fn test_wasm_validation_floats() {
+        let module = |body: &str| {
+            wasmer::wat2wasm(
+                format!(
+                    r#"(module (memory 1) (export "memory" (memory 0))
+                        (func $f (param i32) (result i32) {body}))"#
+                )
+                .as_bytes(),
+            )
+            .expect("unexpected error converting wat2wasm")
+            .into_owned()
+        };
+
+        let result = validate_untrusted_wasm(module(
+            "(i32.add (local.get 0) (i32.const 1))",
+        ));
+        assert!(result.is_ok(), "Expected success. Got {:?}", result);
+
+        for body in [
+            "(i32.trunc_f32_s (f32.add (f32.convert_i32_s (local.get 0)) \
+             (f32.const 1.5)))",
+            "(i32.trunc_f64_s (f64.const 1.5))",
+        ] {
+            let error = validate_untrusted_wasm(module(body))
+                .expect_err("Expecting the floats to be rejected");
+            assert!(matches!(
+                error,
+                WasmValidationError::ForbiddenWasmFeatures(_)
+            ));
+        }
+    }
+
     /// Test that when a transaction wasm goes over the memory limit inside the
     /// wasm execution, the execution is aborted.
     #[test]
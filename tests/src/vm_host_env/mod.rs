@@ -18,7 +18,7 @@ pub mod vp;
 #[cfg(test)]
 mod tests {
 
-    use std::collections::BTreeSet;
+    use std::collections::{BTreeMap, BTreeSet};
     use std::panic;
 
     use borsh_ext::BorshSerializeExt;
@@ -29,6 +29,7 @@ mod tests {
         get_dummy_header as tm_dummy_header, Error as IbcError,
     };
     use namada::ledger::tx_env::TxEnv;
+    use namada::ledger::{parameters, storage_api};
     use namada::proto::Tx;
     use namada::types::hash::Hash;
     use namada::types::key::*;
@@ -206,6 +207,31 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_tx_emit_event() {
+        // The environment must be initialized first
+        tx_host_env::init();
+
+        let attributes =
+            BTreeMap::from([("outcome".to_string(), "success".to_string())]);
+        tx::ctx().emit_event("swap", attributes.clone()).unwrap();
+        tx::ctx().emit_event("transfer", BTreeMap::new()).unwrap();
+
+        let events =
+            tx_host_env::with(|env| env.wl_storage.write_log.take_events());
+        let emitted = events
+            .into_iter()
+            .map(|event| (event.event_type, event.attributes))
+            .collect_vec();
+        assert_eq!(
+            emitted,
+            vec![
+                ("swap".to_string(), attributes),
+                ("transfer".to_string(), BTreeMap::new()),
+            ]
+        );
+    }
+
     #[test]
     #[should_panic]
     fn test_tx_init_account_with_invalid_vp() {
@@ -270,6 +296,66 @@ mod tests {
         );
     }
 
+    /// Test that a tx reads the randomness beacon value of the current block
+    #[test]
+    fn test_tx_get_block_randomness() {
+        // The environment must be initialized first
+        tx_host_env::init();
+
+        // A zero hash is returned before the randomness is set
+        assert_eq!(tx::ctx().get_block_randomness().unwrap(), Hash::default());
+
+        // Update the randomness for a new block, as done by the protocol
+        let randomness = tx_host_env::with(|env| {
+            parameters::update_block_randomness(
+                &mut env.wl_storage,
+                &BlockHash([1; 32]),
+                BlockHeight(1),
+            )
+            .unwrap();
+            parameters::read_block_randomness(&env.wl_storage)
+                .unwrap()
+                .unwrap()
+        });
+        assert_ne!(randomness, Hash::default());
+        assert_eq!(tx::ctx().get_block_randomness().unwrap(), randomness);
+    }
+
+    /// Test that a tx mints and burns tokens and that the token is added to
+    /// the verifiers
+    #[test]
+    fn test_tx_mint_and_burn_tokens() {
+        // The environment must be initialized first
+        tx_host_env::init();
+
+        let token = address::testing::established_address_1();
+        let owner = address::testing::established_address_2();
+        tx::ctx()
+            .mint_tokens(&token, &owner, Amount::from_u64(100))
+            .unwrap();
+        tx::ctx()
+            .burn_tokens(&token, &owner, Amount::from_u64(30))
+            .unwrap();
+
+        tx_host_env::with(|env| {
+            assert!(env.verifiers.contains(&token));
+            assert_eq!(
+                storage_api::token::read_balance(
+                    &env.wl_storage,
+                    &token,
+                    &owner
+                )
+                .unwrap(),
+                Amount::from_u64(70)
+            );
+            assert_eq!(
+                storage_api::token::read_total_supply(&env.wl_storage, &token)
+                    .unwrap(),
+                Amount::from_u64(70)
+            );
+        });
+    }
+
     /// An example how to write a VP host environment integration test
     #[test]
     fn test_vp_host_env() {
@@ -501,6 +587,37 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_vp_crypto_host_fns() {
+        // The environment must be initialized first
+        vp_host_env::init();
+
+        let data = b"some arbitrary data";
+        assert_eq!(namada_vp_prelude::hash_sha256(data), Hash::sha256(data).0);
+        // The BLAKE2b-256 digest of an empty input
+        assert_eq!(
+            Hash(namada_vp_prelude::hash_blake2b(b"")).to_string(),
+            "0E5751C026E543B2E8AB2EB06099DAA1D1E5DF47778F7787FAAB45CDF12FE3A8"
+        );
+
+        let keypair = key::testing::gen_keypair::<ed25519::SigScheme>();
+        let pk = keypair.ref_to();
+        let sig = ed25519::Signature(keypair.0.sign(data));
+        assert!(namada_vp_prelude::key::verify_ed25519_signature(
+            &pk, &sig, data
+        ));
+        assert!(!namada_vp_prelude::key::verify_ed25519_signature(
+            &pk,
+            &sig,
+            b"other data"
+        ));
+        let other_pk =
+            key::testing::gen_keypair::<ed25519::SigScheme>().ref_to();
+        assert!(!namada_vp_prelude::key::verify_ed25519_signature(
+            &other_pk, &sig, data
+        ));
+    }
+
     #[test]
     fn test_vp_get_metadata() {
         // The environment must be initialized first
@@ -540,6 +657,31 @@ mod tests {
         );
     }
 
+    /// Test that a VP reads the randomness beacon value of the current block
+    #[test]
+    fn test_vp_get_block_randomness() {
+        // The environment must be initialized first
+        vp_host_env::init();
+
+        // A zero hash is returned before the randomness is set
+        assert_eq!(vp::CTX.get_block_randomness().unwrap(), Hash::default());
+
+        // Update the randomness for a new block, as done by the protocol
+        let randomness = vp_host_env::with(|env| {
+            parameters::update_block_randomness(
+                &mut env.wl_storage,
+                &BlockHash([1; 32]),
+                BlockHeight(1),
+            )
+            .unwrap();
+            parameters::read_block_randomness(&env.wl_storage)
+                .unwrap()
+                .unwrap()
+        });
+        assert_ne!(randomness, Hash::default());
+        assert_eq!(vp::CTX.get_block_randomness().unwrap(), randomness);
+    }
+
     #[test]
     fn test_vp_eval() {
         // The environment must be initialized first
@@ -484,6 +484,22 @@ mod native_tx_host_env {
     native_host_fn!(tx_iter_prefix(prefix_ptr: u64, prefix_len: u64) -> u64);
     native_host_fn!(tx_iter_next(iter_id: u64) -> i64);
     native_host_fn!(tx_insert_verifier(addr_ptr: u64, addr_len: u64));
+    native_host_fn!(tx_mint_tokens(
+        token_ptr: u64,
+        token_len: u64,
+        target_ptr: u64,
+        target_len: u64,
+        amount_ptr: u64,
+        amount_len: u64
+    ));
+    native_host_fn!(tx_burn_tokens(
+        token_ptr: u64,
+        token_len: u64,
+        source_ptr: u64,
+        source_len: u64,
+        amount_ptr: u64,
+        amount_len: u64
+    ));
     native_host_fn!(tx_update_validity_predicate(
         addr_ptr: u64,
         addr_len: u64,
@@ -500,6 +516,7 @@ mod native_tx_host_env {
         result_ptr: u64
     ));
     native_host_fn!(tx_emit_ibc_event(event_ptr: u64, event_len: u64));
+    native_host_fn!(tx_emit_event(event_ptr: u64, event_len: u64));
     native_host_fn!(tx_get_ibc_events(event_type_ptr: u64, event_type_len: u64) -> i64);
     native_host_fn!(tx_get_chain_id(result_ptr: u64));
     native_host_fn!(tx_get_block_height() -> u64);
@@ -507,6 +524,7 @@ mod native_tx_host_env {
     native_host_fn!(tx_get_block_header(height: u64) -> i64);
     native_host_fn!(tx_get_block_hash(result_ptr: u64));
     native_host_fn!(tx_get_block_epoch() -> u64);
+    native_host_fn!(tx_get_block_randomness(result_ptr: u64));
     native_host_fn!(tx_get_native_token(result_ptr: u64));
     native_host_fn!(tx_log_string(str_ptr: u64, str_len: u64));
     native_host_fn!(tx_charge_gas(used_gas: u64));
@@ -520,4 +538,22 @@ mod native_tx_host_env {
         max_signatures_ptr: u64,
         max_signatures_len: u64,
     ) -> i64);
+    native_host_fn!(tx_hash_sha256(
+        data_ptr: u64,
+        data_len: u64,
+        result_ptr: u64,
+    ));
+    native_host_fn!(tx_hash_blake2b(
+        data_ptr: u64,
+        data_len: u64,
+        result_ptr: u64,
+    ));
+    native_host_fn!(tx_verify_ed25519_signature(
+        pk_ptr: u64,
+        pk_len: u64,
+        sig_ptr: u64,
+        sig_len: u64,
+        msg_ptr: u64,
+        msg_len: u64,
+    ) -> i64);
 }
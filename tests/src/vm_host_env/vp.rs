@@ -358,6 +358,7 @@ mod native_vp_host_env {
     native_host_fn!(vp_get_tx_index() -> u32);
     native_host_fn!(vp_get_block_header(height: u64) -> i64);
     native_host_fn!(vp_get_block_hash(result_ptr: u64));
+    native_host_fn!(vp_get_block_randomness(result_ptr: u64));
     native_host_fn!(vp_get_tx_code_hash(result_ptr: u64));
     native_host_fn!(vp_get_block_epoch() -> u64);
     native_host_fn!(vp_get_native_token(result_ptr: u64));
@@ -379,5 +380,23 @@ mod native_vp_host_env {
         max_signatures_ptr: u64,
         max_signatures_len: u64,
     ) -> i64);
+    native_host_fn!(vp_hash_sha256(
+        data_ptr: u64,
+        data_len: u64,
+        result_ptr: u64,
+    ));
+    native_host_fn!(vp_hash_blake2b(
+        data_ptr: u64,
+        data_len: u64,
+        result_ptr: u64,
+    ));
+    native_host_fn!(vp_verify_ed25519_signature(
+        pk_ptr: u64,
+        pk_len: u64,
+        sig_ptr: u64,
+        sig_len: u64,
+        msg_ptr: u64,
+        msg_len: u64,
+    ) -> i64);
     native_host_fn!(vp_charge_gas(used_gas: u64));
 }
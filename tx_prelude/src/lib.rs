@@ -14,6 +14,7 @@ pub mod proof_of_stake;
 pub mod token;
 
 use core::slice;
+use std::collections::BTreeMap;
 use std::marker::PhantomData;
 
 pub use borsh::{BorshDeserialize, BorshSerialize};
@@ -40,6 +41,7 @@ use namada_core::types::storage::TxIndex;
 pub use namada_core::types::storage::{
     self, BlockHash, BlockHeight, Epoch, Header, BLOCK_HASH_LENGTH,
 };
+use namada_core::types::transaction::TxEvent;
 pub use namada_core::types::{eth_bridge_pool, *};
 pub use namada_macros::transaction;
 use namada_vm_env::tx::*;
@@ -53,6 +55,34 @@ pub fn log_string<T: AsRef<str>>(msg: T) {
     }
 }
 
+/// Hash the data with SHA-256 in the host environment
+pub fn hash_sha256(data: impl AsRef<[u8]>) -> [u8; 32] {
+    let data = data.as_ref();
+    let mut result = [0_u8; 32];
+    unsafe {
+        namada_tx_hash_sha256(
+            data.as_ptr() as _,
+            data.len() as _,
+            result.as_mut_ptr() as _,
+        );
+    }
+    result
+}
+
+/// Hash the data with BLAKE2b-256 in the host environment
+pub fn hash_blake2b(data: impl AsRef<[u8]>) -> [u8; 32] {
+    let data = data.as_ref();
+    let mut result = [0_u8; 32];
+    unsafe {
+        namada_tx_hash_blake2b(
+            data.as_ptr() as _,
+            data.len() as _,
+            result.as_mut_ptr() as _,
+        );
+    }
+    result
+}
+
 /// Format and log a string in a debug build.
 ///
 /// In WASM target debug build, the message will be printed at the
@@ -103,6 +133,68 @@ impl Ctx {
     pub const unsafe fn new() -> Self {
         Self(())
     }
+
+    /// Get the randomness beacon value of the current block, or a zero hash
+    /// if it hasn't been set yet. The value can be biased by the block
+    /// proposer, see [`parameters::read_block_randomness`].
+    pub fn get_block_randomness(&self) -> Result<hash::Hash, Error> {
+        let result = Vec::with_capacity(hash::HASH_LENGTH);
+        unsafe {
+            namada_tx_get_block_randomness(result.as_ptr() as _);
+        }
+        let slice = unsafe {
+            slice::from_raw_parts(result.as_ptr(), hash::HASH_LENGTH)
+        };
+        Ok(hash::Hash::try_from(slice).expect("Cannot convert the hash"))
+    }
+
+    /// Mint an amount of the token to the target address. The mint has to be
+    /// accepted by the validity predicate of the token.
+    pub fn mint_tokens(
+        &mut self,
+        token: &Address,
+        target: &Address,
+        amount: token::Amount,
+    ) -> TxResult {
+        let token = token.encode();
+        let target = target.encode();
+        let amount = amount.serialize_to_vec();
+        unsafe {
+            namada_tx_mint_tokens(
+                token.as_ptr() as _,
+                token.len() as _,
+                target.as_ptr() as _,
+                target.len() as _,
+                amount.as_ptr() as _,
+                amount.len() as _,
+            )
+        }
+        Ok(())
+    }
+
+    /// Burn an amount of the token from the source address. The burn has to
+    /// be accepted by the validity predicate of the token.
+    pub fn burn_tokens(
+        &mut self,
+        token: &Address,
+        source: &Address,
+        amount: token::Amount,
+    ) -> TxResult {
+        let token = token.encode();
+        let source = source.encode();
+        let amount = amount.serialize_to_vec();
+        unsafe {
+            namada_tx_burn_tokens(
+                token.as_ptr() as _,
+                token.len() as _,
+                source.as_ptr() as _,
+                source.len() as _,
+                amount.as_ptr() as _,
+                amount.len() as _,
+            )
+        }
+        Ok(())
+    }
 }
 
 /// Result of `TxEnv`, `storage_api::StorageRead` or `storage_api::StorageWrite`
@@ -339,6 +431,20 @@ impl TxEnv for Ctx {
         Ok(())
     }
 
+    fn emit_event(
+        &mut self,
+        event_type: impl AsRef<str>,
+        attributes: BTreeMap<String, String>,
+    ) -> Result<(), Error> {
+        let event = borsh::to_vec(&TxEvent {
+            event_type: event_type.as_ref().to_string(),
+            attributes,
+        })
+        .unwrap();
+        unsafe { namada_tx_emit_event(event.as_ptr() as _, event.len() as _) };
+        Ok(())
+    }
+
     fn charge_gas(&mut self, used_gas: u64) -> Result<(), Error> {
         unsafe { namada_tx_charge_gas(used_gas) };
         Ok(())
@@ -60,6 +60,26 @@ pub mod tx {
         // Insert a verifier
         pub fn namada_tx_insert_verifier(addr_ptr: u64, addr_len: u64);
 
+        // Mint tokens to a target address
+        pub fn namada_tx_mint_tokens(
+            token_ptr: u64,
+            token_len: u64,
+            target_ptr: u64,
+            target_len: u64,
+            amount_ptr: u64,
+            amount_len: u64,
+        );
+
+        // Burn tokens from a source address
+        pub fn namada_tx_burn_tokens(
+            token_ptr: u64,
+            token_len: u64,
+            source_ptr: u64,
+            source_len: u64,
+            amount_ptr: u64,
+            amount_len: u64,
+        );
+
         // Update a validity predicate
         pub fn namada_tx_update_validity_predicate(
             addr_ptr: u64,
@@ -82,6 +102,9 @@ pub mod tx {
         // Emit an IBC event
         pub fn namada_tx_emit_ibc_event(event_ptr: u64, event_len: u64);
 
+        // Emit a custom event
+        pub fn namada_tx_emit_event(event_ptr: u64, event_len: u64);
+
         // Get IBC events
         pub fn namada_tx_get_ibc_events(
             event_type_ptr: u64,
@@ -103,6 +126,9 @@ pub mod tx {
         // Get the current block epoch
         pub fn namada_tx_get_block_epoch() -> u64;
 
+        // Get the current block randomness
+        pub fn namada_tx_get_block_randomness(result_ptr: u64);
+
         // Get the current tx index
         pub fn namada_tx_get_tx_index() -> u32;
 
@@ -133,6 +159,33 @@ pub mod tx {
             max_signatures_len: u64,
         ) -> i64;
 
+        // Hash the data with SHA-256, the 32 bytes digest is written to the
+        // result pointer
+        pub fn namada_tx_hash_sha256(
+            data_ptr: u64,
+            data_len: u64,
+            result_ptr: u64,
+        );
+
+        // Hash the data with BLAKE2b-256, the 32 bytes digest is written to
+        // the result pointer
+        pub fn namada_tx_hash_blake2b(
+            data_ptr: u64,
+            data_len: u64,
+            result_ptr: u64,
+        );
+
+        // Verify an ed25519 signature of a message, returns 1 if the
+        // signature is valid, -1 otherwise
+        pub fn namada_tx_verify_ed25519_signature(
+            pk_ptr: u64,
+            pk_len: u64,
+            sig_ptr: u64,
+            sig_len: u64,
+            msg_ptr: u64,
+            msg_len: u64,
+        ) -> i64;
+
         /// Update the masp note commitment tree with the new notes
         pub fn namada_tx_update_masp_note_commitment_tree(
             transaction_ptr: u64,
@@ -209,6 +262,9 @@ pub mod vp {
         // Get the current block hash
         pub fn namada_vp_get_block_hash(result_ptr: u64);
 
+        // Get the current block randomness
+        pub fn namada_vp_get_block_randomness(result_ptr: u64);
+
         // Get the current tx hash
         pub fn namada_vp_get_tx_code_hash(result_ptr: u64);
 
@@ -243,6 +299,33 @@ pub mod vp {
             max_signatures_len: u64,
         ) -> i64;
 
+        // Hash the data with SHA-256, the 32 bytes digest is written to the
+        // result pointer
+        pub fn namada_vp_hash_sha256(
+            data_ptr: u64,
+            data_len: u64,
+            result_ptr: u64,
+        );
+
+        // Hash the data with BLAKE2b-256, the 32 bytes digest is written to
+        // the result pointer
+        pub fn namada_vp_hash_blake2b(
+            data_ptr: u64,
+            data_len: u64,
+            result_ptr: u64,
+        );
+
+        // Verify an ed25519 signature of a message, returns 1 if the
+        // signature is valid, -1 otherwise
+        pub fn namada_vp_verify_ed25519_signature(
+            pk_ptr: u64,
+            pk_len: u64,
+            sig_ptr: u64,
+            sig_len: u64,
+            msg_ptr: u64,
+            msg_len: u64,
+        ) -> i64;
+
         pub fn namada_vp_eval(
             vp_code_hash_ptr: u64,
             vp_code_hash_len: u64,
@@ -10,3 +10,25 @@ use super::*;
 pub fn get(ctx: &Ctx, owner: &Address) -> EnvResult<Option<common::PublicKey>> {
     storage_api::key::get(&ctx.pre(), owner)
 }
+
+/// Verify an ed25519 signature of the message in the host environment
+pub fn verify_ed25519_signature(
+    pk: &ed25519::PublicKey,
+    sig: &ed25519::Signature,
+    msg: impl AsRef<[u8]>,
+) -> bool {
+    let pk = pk.serialize_to_vec();
+    let sig = sig.serialize_to_vec();
+    let msg = msg.as_ref();
+    let result = unsafe {
+        namada_vp_verify_ed25519_signature(
+            pk.as_ptr() as _,
+            pk.len() as _,
+            sig.as_ptr() as _,
+            sig.len() as _,
+            msg.as_ptr() as _,
+            msg.len() as _,
+        )
+    };
+    HostEnvResult::is_success(result)
+}
@@ -71,6 +71,34 @@ pub fn log_string<T: AsRef<str>>(msg: T) {
     }
 }
 
+/// Hash the data with SHA-256 in the host environment
+pub fn hash_sha256(data: impl AsRef<[u8]>) -> [u8; 32] {
+    let data = data.as_ref();
+    let mut result = [0_u8; 32];
+    unsafe {
+        namada_vp_hash_sha256(
+            data.as_ptr() as _,
+            data.len() as _,
+            result.as_mut_ptr() as _,
+        );
+    }
+    result
+}
+
+/// Hash the data with BLAKE2b-256 in the host environment
+pub fn hash_blake2b(data: impl AsRef<[u8]>) -> [u8; 32] {
+    let data = data.as_ref();
+    let mut result = [0_u8; 32];
+    unsafe {
+        namada_vp_hash_blake2b(
+            data.as_ptr() as _,
+            data.len() as _,
+            result.as_mut_ptr() as _,
+        );
+    }
+    result
+}
+
 /// Checks if a proposal id is being executed
 pub fn is_proposal_accepted(ctx: &Ctx, proposal_id: u64) -> VpResult {
     let proposal_execution_key =
@@ -190,6 +218,19 @@ impl Ctx {
     pub fn post(&self) -> CtxPostStorageRead<'_> {
         CtxPostStorageRead { _ctx: self }
     }
+
+    /// Get the randomness beacon value of the current block, or a zero hash
+    /// if it hasn't been set yet. The value can be biased by the block
+    /// proposer, see [`parameters::read_block_randomness`].
+    pub fn get_block_randomness(&self) -> Result<Hash, Error> {
+        let result = Vec::with_capacity(HASH_LENGTH);
+        unsafe {
+            namada_vp_get_block_randomness(result.as_ptr() as _);
+        }
+        let slice =
+            unsafe { slice::from_raw_parts(result.as_ptr(), HASH_LENGTH) };
+        Ok(Hash::try_from(slice).expect("Cannot convert the hash"))
+    }
 }
 
 /// Read access to the prior storage (state before tx execution) via